@@ -7,7 +7,8 @@ use bitflags::bitflags;
 use knuffel::errors::DecodeError;
 use miette::miette;
 use niri_ipc::{
-    ColumnDisplay, LayoutSwitchTarget, PositionChange, SizeChange, WorkspaceReferenceArg,
+    ColumnDisplay, LayoutMode, LayoutSwitchTarget, PositionChange, SizeChange, SnapZone,
+    WorkspaceReferenceArg,
 };
 use smithay::input::keyboard::keysyms::KEY_NoSymbol;
 use smithay::input::keyboard::xkb::{keysym_from_name, KEYSYM_CASE_INSENSITIVE, KEYSYM_NO_FLAGS};
@@ -15,21 +16,56 @@ use smithay::input::keyboard::Keysym;
 
 use crate::recent_windows::{MruDirection, MruFilter, MruScope};
 use crate::utils::{expect_only_children, MergeWith};
+use crate::window_rule::Match;
 
 #[derive(Debug, Default, PartialEq)]
 pub struct Binds(pub Vec<Bind>);
 
+/// Named set of binds that replaces the regular binds while it is active.
+#[derive(knuffel::Decode, Debug, PartialEq)]
+pub struct BindMode {
+    #[knuffel(argument)]
+    pub name: String,
+    #[knuffel(property)]
+    pub hotkey_overlay_title: Option<String>,
+    #[knuffel(children)]
+    pub binds: Binds,
+}
+
+/// Named set of binds that take precedence over the regular binds while a matching window is
+/// focused.
+#[derive(Debug, PartialEq)]
+pub struct AppBinds {
+    pub name: String,
+    pub matches: Vec<Match>,
+    pub excludes: Vec<Match>,
+    pub binds: Binds,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Bind {
     pub key: Key,
+    /// Keys to press one after another following `key` to trigger the bind.
+    pub sequence: Vec<Key>,
     pub action: Action,
     pub repeat: bool,
+    /// Whether the bind triggers when its key is released rather than pressed.
+    pub on_release: bool,
+    /// Whether the bind triggers on release only if no other key was pressed in between.
+    pub tap: bool,
     pub cooldown: Option<Duration>,
     pub allow_when_locked: bool,
     pub allow_inhibiting: bool,
     pub hotkey_overlay_title: Option<Option<String>>,
 }
 
+impl Bind {
+    /// Returns all keys to press to trigger the bind, starting with `key`.
+    pub fn keys(&self) -> impl Iterator<Item = &Key> + '_ {
+        std::iter::once(&self.key).chain(&self.sequence)
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub struct Key {
     pub trigger: Trigger,
@@ -148,6 +184,13 @@ pub enum Action {
         path: Option<String>,
     },
     ToggleKeyboardShortcutsInhibit,
+    ToggleStickyKeys,
+    ToggleSlowKeys,
+    ToggleBounceKeys,
+    ToggleMouseKeys,
+    ZoomIn,
+    ZoomOut,
+    ToggleZoom,
     CloseWindow,
     #[knuffel(skip)]
     CloseWindowById(u64),
@@ -211,6 +254,7 @@ pub enum Action {
     SwapWindowRight,
     ToggleColumnTabbedDisplay,
     SetColumnDisplay(#[knuffel(argument, str)] ColumnDisplay),
+    SetLayoutMode(#[knuffel(argument, str)] LayoutMode),
     CenterColumn,
     CenterWindow,
     #[knuffel(skip)]
@@ -343,6 +387,37 @@ pub enum Action {
     MoveWindowToTiling,
     #[knuffel(skip)]
     MoveWindowToTilingById(u64),
+    ToggleWindowSticky,
+    #[knuffel(skip)]
+    ToggleWindowStickyById(u64),
+    ToggleScratchpad,
+    MoveWindowToScratchpad,
+    #[knuffel(skip)]
+    MoveWindowToScratchpadById(u64),
+    MinimizeWindow,
+    #[knuffel(skip)]
+    MinimizeWindowById(u64),
+    RestoreMinimizedWindow,
+    #[knuffel(skip)]
+    RestoreMinimizedWindowById(u64),
+    UndoLayoutChange,
+    RedoLayoutChange,
+    SetWindowMark(#[knuffel(argument)] String),
+    #[knuffel(skip)]
+    SetWindowMarkById {
+        id: u64,
+        mark: String,
+    },
+    FocusWindowByMark(#[knuffel(argument)] String),
+    SwapWithMark(#[knuffel(argument)] String),
+    SnapFloatingWindow(#[knuffel(argument, str)] SnapZone),
+    #[knuffel(skip)]
+    SnapFloatingWindowById {
+        id: u64,
+        zone: SnapZone,
+    },
+    EnterBindMode(#[knuffel(argument)] String),
+    ExitBindMode,
     FocusFloating,
     FocusTiling,
     SwitchFocusBetweenFloatingAndTiling,
@@ -393,6 +468,19 @@ pub enum Action {
     MruSetScope(MruScope),
     #[knuffel(skip)]
     MruCycleScope,
+    /// Several actions run one after another.
+    #[knuffel(skip)]
+    Sequence(Vec<Action>),
+}
+
+impl Action {
+    /// Returns the actions that this action runs, which is just itself unless it's a sequence.
+    pub fn actions(&self) -> impl Iterator<Item = &Action> {
+        match self {
+            Action::Sequence(actions) => actions.iter(),
+            action => std::slice::from_ref(action).iter(),
+        }
+    }
 }
 
 impl From<niri_ipc::Action> for Action {
@@ -432,6 +520,13 @@ impl From<niri_ipc::Action> for Action {
             niri_ipc::Action::ToggleKeyboardShortcutsInhibit {} => {
                 Self::ToggleKeyboardShortcutsInhibit
             }
+            niri_ipc::Action::ToggleStickyKeys {} => Self::ToggleStickyKeys,
+            niri_ipc::Action::ToggleSlowKeys {} => Self::ToggleSlowKeys,
+            niri_ipc::Action::ToggleBounceKeys {} => Self::ToggleBounceKeys,
+            niri_ipc::Action::ToggleMouseKeys {} => Self::ToggleMouseKeys,
+            niri_ipc::Action::ZoomIn {} => Self::ZoomIn,
+            niri_ipc::Action::ZoomOut {} => Self::ZoomOut,
+            niri_ipc::Action::ToggleZoom {} => Self::ToggleZoom,
             niri_ipc::Action::CloseWindow { id: None } => Self::CloseWindow,
             niri_ipc::Action::CloseWindow { id: Some(id) } => Self::CloseWindowById(id),
             niri_ipc::Action::FullscreenWindow { id: None } => Self::FullscreenWindow,
@@ -503,6 +598,7 @@ impl From<niri_ipc::Action> for Action {
             niri_ipc::Action::SwapWindowLeft {} => Self::SwapWindowLeft,
             niri_ipc::Action::ToggleColumnTabbedDisplay {} => Self::ToggleColumnTabbedDisplay,
             niri_ipc::Action::SetColumnDisplay { display } => Self::SetColumnDisplay(display),
+            niri_ipc::Action::SetLayoutMode { mode } => Self::SetLayoutMode(mode),
             niri_ipc::Action::CenterColumn {} => Self::CenterColumn,
             niri_ipc::Action::CenterWindow { id: None } => Self::CenterWindow,
             niri_ipc::Action::CenterWindow { id: Some(id) } => Self::CenterWindowById(id),
@@ -676,6 +772,37 @@ impl From<niri_ipc::Action> for Action {
             niri_ipc::Action::MoveWindowToTiling { id: Some(id) } => {
                 Self::MoveWindowToTilingById(id)
             }
+            niri_ipc::Action::ToggleWindowSticky { id: None } => Self::ToggleWindowSticky,
+            niri_ipc::Action::ToggleWindowSticky { id: Some(id) } => {
+                Self::ToggleWindowStickyById(id)
+            }
+            niri_ipc::Action::ToggleScratchpad {} => Self::ToggleScratchpad,
+            niri_ipc::Action::MoveWindowToScratchpad { id: None } => Self::MoveWindowToScratchpad,
+            niri_ipc::Action::MoveWindowToScratchpad { id: Some(id) } => {
+                Self::MoveWindowToScratchpadById(id)
+            }
+            niri_ipc::Action::MinimizeWindow { id: None } => Self::MinimizeWindow,
+            niri_ipc::Action::MinimizeWindow { id: Some(id) } => Self::MinimizeWindowById(id),
+            niri_ipc::Action::RestoreMinimizedWindow { id: None } => Self::RestoreMinimizedWindow,
+            niri_ipc::Action::RestoreMinimizedWindow { id: Some(id) } => {
+                Self::RestoreMinimizedWindowById(id)
+            }
+            niri_ipc::Action::UndoLayoutChange {} => Self::UndoLayoutChange,
+            niri_ipc::Action::RedoLayoutChange {} => Self::RedoLayoutChange,
+            niri_ipc::Action::SetWindowMark { mark, id: None } => Self::SetWindowMark(mark),
+            niri_ipc::Action::SetWindowMark { mark, id: Some(id) } => {
+                Self::SetWindowMarkById { id, mark }
+            }
+            niri_ipc::Action::FocusWindowByMark { mark } => Self::FocusWindowByMark(mark),
+            niri_ipc::Action::SwapWithMark { mark } => Self::SwapWithMark(mark),
+            niri_ipc::Action::SnapFloatingWindow { zone, id: None } => {
+                Self::SnapFloatingWindow(zone)
+            }
+            niri_ipc::Action::SnapFloatingWindow { zone, id: Some(id) } => {
+                Self::SnapFloatingWindowById { id, zone }
+            }
+            niri_ipc::Action::EnterBindMode { name } => Self::EnterBindMode(name),
+            niri_ipc::Action::ExitBindMode {} => Self::ExitBindMode,
             niri_ipc::Action::FocusFloating {} => Self::FocusFloating,
             niri_ipc::Action::FocusTiling {} => Self::FocusTiling,
             niri_ipc::Action::SwitchFocusBetweenFloatingAndTiling {} => {
@@ -773,43 +900,145 @@ where
     ) -> Result<Self, DecodeError<S>> {
         expect_only_children(node, ctx);
 
-        let mut seen_keys: HashMap<Key, &knuffel::ast::SpannedNode<S>> = HashMap::new();
+        let children = node.children.as_ref().map(|lst| &lst[..]).unwrap_or(&[]);
+        knuffel::DecodeChildren::decode_children(children, ctx)
+    }
+}
+
+impl<S> knuffel::DecodeChildren<S> for Binds
+where
+    S: knuffel::traits::ErrorSpan,
+{
+    fn decode_children(
+        nodes: &[knuffel::ast::SpannedNode<S>],
+        ctx: &mut knuffel::decode::Context<S>,
+    ) -> Result<Self, DecodeError<S>> {
+        Ok(decode_binds(nodes, ctx))
+    }
+}
+
+impl<S> knuffel::Decode<S> for AppBinds
+where
+    S: knuffel::traits::ErrorSpan,
+{
+    fn decode_node(
+        node: &knuffel::ast::SpannedNode<S>,
+        ctx: &mut knuffel::decode::Context<S>,
+    ) -> Result<Self, DecodeError<S>> {
+        if let Some(type_name) = &node.type_name {
+            ctx.emit_error(DecodeError::unexpected(
+                type_name,
+                "type name",
+                "no type name expected for this node",
+            ));
+        }
+
+        let mut args = node.arguments.iter();
+        let val = args
+            .next()
+            .ok_or_else(|| DecodeError::missing(node, "additional argument `name` is required"))?;
+        let name = knuffel::traits::DecodeScalar::decode(val, ctx)?;
+        for val in args {
+            ctx.emit_error(DecodeError::unexpected(
+                &val.literal,
+                "argument",
+                "unexpected argument",
+            ));
+        }
 
-        let mut binds = Vec::new();
+        for name in node.properties.keys() {
+            ctx.emit_error(DecodeError::unexpected(
+                name,
+                "property",
+                format!("unexpected property `{}`", name.escape_default()),
+            ));
+        }
 
+        // Matchers and binds share the children.
+        let mut matches = Vec::new();
+        let mut excludes = Vec::new();
+        let mut bind_nodes = Vec::new();
         for child in node.children() {
-            match Bind::decode_node(child, ctx) {
-                Err(e) => {
-                    ctx.emit_error(e);
+            match &**child.node_name {
+                "match" => matches.push(Match::decode_node(child, ctx)?),
+                "exclude" => excludes.push(Match::decode_node(child, ctx)?),
+                _ => bind_nodes.push(child),
+            }
+        }
+
+        Ok(Self {
+            name,
+            matches,
+            excludes,
+            binds: decode_binds(bind_nodes, ctx),
+        })
+    }
+}
+
+fn decode_binds<'a, S>(
+    nodes: impl IntoIterator<Item = &'a knuffel::ast::SpannedNode<S>>,
+    ctx: &mut knuffel::decode::Context<S>,
+) -> Binds
+where
+    S: knuffel::traits::ErrorSpan + 'a,
+{
+    let mut seen_keys: HashMap<Vec<Key>, &knuffel::ast::SpannedNode<S>> = HashMap::new();
+
+    let mut binds = Vec::new();
+
+    for child in nodes {
+        match Bind::decode_node(child, ctx) {
+            Err(e) => {
+                ctx.emit_error(e);
+            }
+            Ok(bind) => {
+                let keys = bind.keys().copied().collect::<Vec<_>>();
+
+                // A sequence can't continue past a key that already triggers another bind.
+                let prefix = seen_keys.iter().find(|(seen, _)| {
+                    let len = seen.len().min(keys.len());
+                    seen.len() != keys.len() && seen[..len] == keys[..len]
+                });
+                if let Some((_, node)) = prefix {
+                    ctx.emit_error(DecodeError::missing(
+                        node,
+                        "keybind sequence first defined here",
+                    ));
+
+                    ctx.emit_error(DecodeError::unexpected(
+                        &child.node_name,
+                        "keybind",
+                        "keybind sequence overlapping with another one defined here",
+                    ));
+                    continue;
                 }
-                Ok(bind) => {
-                    match seen_keys.entry(bind.key) {
-                        Entry::Occupied(entry) => {
-                            // Even though it's technically incorrect, we use
-                            // `DecodeError::Missing` here because it labels the bind with
-                            // "node starts here", which is the least bad option
-                            ctx.emit_error(DecodeError::missing(
-                                entry.get(),
-                                "keybind first defined here",
-                            ));
 
-                            ctx.emit_error(DecodeError::unexpected(
-                                &child.node_name,
-                                "keybind",
-                                "duplicate keybind later defined here",
-                            ));
-                        }
-                        Entry::Vacant(entry) => {
-                            entry.insert(child);
-                            binds.push(bind);
-                        }
+                match seen_keys.entry(keys) {
+                    Entry::Occupied(entry) => {
+                        // Even though it's technically incorrect, we use
+                        // `DecodeError::Missing` here because it labels the bind with
+                        // "node starts here", which is the least bad option
+                        ctx.emit_error(DecodeError::missing(
+                            entry.get(),
+                            "keybind first defined here",
+                        ));
+
+                        ctx.emit_error(DecodeError::unexpected(
+                            &child.node_name,
+                            "keybind",
+                            "duplicate keybind later defined here",
+                        ));
+                    }
+                    Entry::Vacant(entry) => {
+                        entry.insert(child);
+                        binds.push(bind);
                     }
                 }
             }
         }
-
-        Ok(Self(binds))
     }
+
+    Binds(binds)
 }
 
 impl<S> knuffel::Decode<S> for Bind
@@ -836,12 +1065,20 @@ where
             ));
         }
 
-        let key = node
-            .node_name
-            .parse::<Key>()
-            .map_err(|e| DecodeError::conversion(&node.node_name, e.wrap_err("invalid keybind")))?;
+        let parse_key = |key: &str| {
+            key.parse::<Key>().map_err(|e| {
+                DecodeError::conversion(&node.node_name, e.wrap_err("invalid keybind"))
+            })
+        };
+        let mut keys = split_key_sequence(&node.node_name).into_iter();
+        let key = parse_key(&keys.next().unwrap_or_default())?;
+        let sequence = keys
+            .map(|key| parse_key(&key))
+            .collect::<Result<Vec<_>, _>>()?;
 
         let mut repeat = true;
+        let mut on_release = false;
+        let mut tap = false;
         let mut cooldown = None;
         let mut allow_when_locked = false;
         let mut allow_when_locked_node = None;
@@ -852,6 +1089,12 @@ where
                 "repeat" => {
                     repeat = knuffel::traits::DecodeScalar::decode(val, ctx)?;
                 }
+                "on-release" => {
+                    on_release = knuffel::traits::DecodeScalar::decode(val, ctx)?;
+                }
+                "tap" => {
+                    tap = knuffel::traits::DecodeScalar::decode(val, ctx)?;
+                }
                 "cooldown-ms" => {
                     cooldown = Some(Duration::from_millis(
                         knuffel::traits::DecodeScalar::decode(val, ctx)?,
@@ -877,70 +1120,102 @@ where
             }
         }
 
-        let mut children = node.children();
+        let children = node.children();
 
         // If the action is invalid but the key is fine, we still want to return something.
         // That way, the parent can handle the existence of duplicate keybinds,
         // even if their contents are not valid.
         let dummy = Self {
             key,
+            sequence: sequence.clone(),
             action: Action::Spawn(vec![]),
             repeat: true,
+            on_release: false,
+            tap: false,
             cooldown: None,
             allow_when_locked: false,
             allow_inhibiting: true,
             hotkey_overlay_title: None,
         };
 
-        if let Some(child) = children.next() {
-            for unwanted_child in children {
-                ctx.emit_error(DecodeError::unexpected(
-                    unwanted_child,
-                    "node",
-                    "only one action is allowed per keybind",
-                ));
-            }
+        let mut actions = Vec::new();
+        let mut has_errors = false;
+        for child in children {
             match Action::decode_node(child, ctx) {
-                Ok(action) => {
-                    if !matches!(action, Action::Spawn(_) | Action::SpawnSh(_)) {
-                        if let Some(node) = allow_when_locked_node {
-                            ctx.emit_error(DecodeError::unexpected(
-                                node,
-                                "property",
-                                "allow-when-locked can only be set on spawn binds",
-                            ));
-                        }
-                    }
-
-                    // The toggle-inhibit action must always be uninhibitable.
-                    // Otherwise, it would be impossible to trigger it.
-                    if matches!(action, Action::ToggleKeyboardShortcutsInhibit) {
-                        allow_inhibiting = false;
-                    }
-
-                    Ok(Self {
-                        key,
-                        action,
-                        repeat,
-                        cooldown,
-                        allow_when_locked,
-                        allow_inhibiting,
-                        hotkey_overlay_title,
-                    })
-                }
+                Ok(action) => actions.push(action),
                 Err(e) => {
                     ctx.emit_error(e);
-                    Ok(dummy)
+                    has_errors = true;
                 }
             }
-        } else {
+        }
+
+        if has_errors {
+            Ok(dummy)
+        } else if actions.is_empty() {
             ctx.emit_error(DecodeError::missing(
                 node,
                 "expected an action for this keybind",
             ));
             Ok(dummy)
+        } else {
+            // Several actions make a sequence that runs them in order.
+            let action = if actions.len() == 1 {
+                actions.pop().unwrap()
+            } else {
+                Action::Sequence(actions)
+            };
+
+            if !action
+                .actions()
+                .all(|action| matches!(action, Action::Spawn(_) | Action::SpawnSh(_)))
+            {
+                if let Some(node) = allow_when_locked_node {
+                    ctx.emit_error(DecodeError::unexpected(
+                        node,
+                        "property",
+                        "allow-when-locked can only be set on spawn binds",
+                    ));
+                }
+            }
+
+            // The toggle-inhibit action must always be uninhibitable.
+            // Otherwise, it would be impossible to trigger it.
+            if action
+                .actions()
+                .any(|action| matches!(action, Action::ToggleKeyboardShortcutsInhibit))
+            {
+                allow_inhibiting = false;
+            }
+
+            Ok(Self {
+                key,
+                sequence,
+                action,
+                repeat,
+                on_release,
+                tap,
+                cooldown,
+                allow_when_locked,
+                allow_inhibiting,
+                hotkey_overlay_title,
+            })
+        }
+    }
+}
+
+/// Splits a bind name like `Mod+Space w h` into the keys of its sequence.
+///
+/// Whitespace around `+` doesn't separate keys, so `Mod + T` remains a single key.
+fn split_key_sequence(name: &str) -> Vec<String> {
+    let mut keys: Vec<String> = Vec::new();
+    for part in name.split_whitespace() {
+        match keys.last_mut() {
+            Some(last) if last.ends_with('+') || part.starts_with('+') => last.push_str(part),
+            _ => keys.push(part.to_owned()),
         }
     }
+    keys
 }
 
 impl FromStr for Key {
@@ -1,14 +1,18 @@
-use crate::utils::MergeWith;
+use knuffel::errors::DecodeError;
+
+use crate::binds::Action;
+use crate::utils::{expect_only_children, MergeWith};
 use crate::FloatOrInt;
 
-#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct Gestures {
     pub dnd_edge_view_scroll: DndEdgeViewScroll,
     pub dnd_edge_workspace_switch: DndEdgeWorkspaceSwitch,
     pub hot_corners: HotCorners,
+    pub binds: Vec<GestureBind>,
 }
 
-#[derive(knuffel::Decode, Debug, Default, Clone, Copy, PartialEq)]
+#[derive(knuffel::Decode, Debug, Default, Clone, PartialEq)]
 pub struct GesturesPart {
     #[knuffel(child)]
     pub dnd_edge_view_scroll: Option<DndEdgeViewScrollPart>,
@@ -16,6 +20,12 @@ pub struct GesturesPart {
     pub dnd_edge_workspace_switch: Option<DndEdgeWorkspaceSwitchPart>,
     #[knuffel(child)]
     pub hot_corners: Option<HotCorners>,
+    #[knuffel(children(name = "swipe"))]
+    pub swipe: Vec<GestureBind>,
+    #[knuffel(children(name = "pinch"))]
+    pub pinch: Vec<GestureBind>,
+    #[knuffel(children(name = "hold"))]
+    pub hold: Vec<GestureBind>,
 }
 
 impl MergeWith<GesturesPart> for Gestures {
@@ -26,6 +36,179 @@ impl MergeWith<GesturesPart> for Gestures {
             dnd_edge_workspace_switch,
         );
         merge_clone!((self, part), hot_corners);
+
+        for bind in part.swipe.iter().chain(&part.pinch).chain(&part.hold) {
+            self.binds.retain(|b| b.trigger != bind.trigger);
+            self.binds.push(bind.clone());
+        }
+    }
+}
+
+impl Gestures {
+    pub fn find_bind(&self, trigger: GestureTrigger) -> Option<&GestureBind> {
+        self.binds.iter().find(|bind| bind.trigger == trigger)
+    }
+
+    pub fn has_swipe_binds(&self, fingers: u32) -> bool {
+        self.binds.iter().any(
+            |bind| matches!(bind.trigger, GestureTrigger::Swipe { fingers: f, .. } if f == fingers),
+        )
+    }
+
+    pub fn has_pinch_binds(&self, fingers: u32) -> bool {
+        self.binds.iter().any(
+            |bind| matches!(bind.trigger, GestureTrigger::Pinch { fingers: f, .. } if f == fingers),
+        )
+    }
+
+    pub fn has_hold_binds(&self, fingers: u32) -> bool {
+        self.binds
+            .iter()
+            .any(|bind| matches!(bind.trigger, GestureTrigger::Hold { fingers: f } if f == fingers))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GestureBind {
+    pub trigger: GestureTrigger,
+    pub action: GestureAction,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GestureTrigger {
+    Swipe {
+        fingers: u32,
+        direction: SwipeDirection,
+    },
+    Pinch {
+        fingers: u32,
+        direction: PinchDirection,
+    },
+    Hold {
+        fingers: u32,
+    },
+}
+
+#[derive(knuffel::DecodeScalar, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwipeDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+#[derive(knuffel::DecodeScalar, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PinchDirection {
+    In,
+    Out,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GestureAction {
+    /// Scrolls the view following the fingers.
+    ViewScroll,
+    /// Switches workspaces following the fingers.
+    WorkspaceSwitch,
+    /// Opens and closes the overview following the fingers.
+    Overview,
+    /// Runs the action once the gesture is recognized.
+    Action(Action),
+}
+
+impl GestureAction {
+    pub fn is_interactive(&self) -> bool {
+        !matches!(self, GestureAction::Action(_))
+    }
+}
+
+impl<S> knuffel::Decode<S> for GestureBind
+where
+    S: knuffel::traits::ErrorSpan,
+{
+    fn decode_node(
+        node: &knuffel::ast::SpannedNode<S>,
+        ctx: &mut knuffel::decode::Context<S>,
+    ) -> Result<Self, DecodeError<S>> {
+        if let Some(type_name) = &node.type_name {
+            ctx.emit_error(DecodeError::unexpected(
+                type_name,
+                "type name",
+                "no type name expected for this node",
+            ));
+        }
+
+        for val in node.arguments.iter() {
+            ctx.emit_error(DecodeError::unexpected(
+                &val.literal,
+                "argument",
+                "no arguments expected for this node",
+            ));
+        }
+
+        let kind = &**node.node_name;
+
+        let mut fingers = None;
+        let mut swipe_direction = None;
+        let mut pinch_direction = None;
+        for (name, val) in &node.properties {
+            match &***name {
+                "fingers" => {
+                    let value: u32 = knuffel::traits::DecodeScalar::decode(val, ctx)?;
+                    // Two-finger swipes are scrolling, and one-finger pinches don't exist.
+                    let min = match kind {
+                        "swipe" => 3,
+                        "pinch" => 2,
+                        _ => 1,
+                    };
+                    if value < min {
+                        ctx.emit_error(DecodeError::conversion(
+                            &val.literal,
+                            format!("{kind} gestures need at least {min} fingers"),
+                        ));
+                    }
+                    fingers = Some(value);
+                }
+                "direction" if kind == "swipe" => {
+                    swipe_direction = Some(knuffel::traits::DecodeScalar::decode(val, ctx)?);
+                }
+                "direction" if kind == "pinch" => {
+                    pinch_direction = Some(knuffel::traits::DecodeScalar::decode(val, ctx)?);
+                }
+                name_str => {
+                    ctx.emit_error(DecodeError::unexpected(
+                        name,
+                        "property",
+                        format!("unexpected property `{}`", name_str.escape_default()),
+                    ));
+                }
+            }
+        }
+
+        let fingers =
+            fingers.ok_or_else(|| DecodeError::missing(node, "property `fingers` is required"))?;
+        let missing_direction = || DecodeError::missing(node, "property `direction` is required");
+        let trigger = match kind {
+            "swipe" => GestureTrigger::Swipe {
+                fingers,
+                direction: swipe_direction.ok_or_else(missing_direction)?,
+            },
+            "pinch" => GestureTrigger::Pinch {
+                fingers,
+                direction: pinch_direction.ok_or_else(missing_direction)?,
+            },
+            _ => GestureTrigger::Hold { fingers },
+        };
+
+        let action = decode_action_child(node, ctx)?;
+        if kind != "swipe" && action.is_interactive() {
+            ctx.emit_error(DecodeError::unexpected(
+                node,
+                "node",
+                "interactive gestures can only be bound to swipes",
+            ));
+        }
+
+        Ok(Self { trigger, action })
     }
 }
 
@@ -110,3 +293,49 @@ pub struct HotCorners {
     #[knuffel(child)]
     pub bottom_right: bool,
 }
+
+impl<S> knuffel::Decode<S> for GestureAction
+where
+    S: knuffel::traits::ErrorSpan,
+{
+    fn decode_node(
+        node: &knuffel::ast::SpannedNode<S>,
+        ctx: &mut knuffel::decode::Context<S>,
+    ) -> Result<Self, DecodeError<S>> {
+        let action = match &**node.node_name {
+            "view-scroll-gesture" => GestureAction::ViewScroll,
+            "workspace-switch-gesture" => GestureAction::WorkspaceSwitch,
+            "overview-gesture" => GestureAction::Overview,
+            _ => return Ok(GestureAction::Action(Action::decode_node(node, ctx)?)),
+        };
+
+        expect_only_children(node, ctx);
+        Ok(action)
+    }
+}
+
+/// Decodes the single action child of a gesture node.
+pub(crate) fn decode_action_child<S>(
+    node: &knuffel::ast::SpannedNode<S>,
+    ctx: &mut knuffel::decode::Context<S>,
+) -> Result<GestureAction, DecodeError<S>>
+where
+    S: knuffel::traits::ErrorSpan,
+{
+    let mut children = node.children();
+    let Some(child) = children.next() else {
+        return Err(DecodeError::missing(
+            node,
+            "expected an action for this gesture",
+        ));
+    };
+    for unwanted_child in children {
+        ctx.emit_error(DecodeError::unexpected(
+            unwanted_child,
+            "node",
+            "only one action is allowed per gesture",
+        ));
+    }
+
+    GestureAction::decode_node(child, ctx)
+}
@@ -0,0 +1,253 @@
+use std::str::FromStr;
+
+use knuffel::errors::DecodeError;
+use knuffel::Decode as _;
+use niri_ipc::Event;
+
+use crate::utils::RegexEq;
+
+/// Commands to run when the compositor emits events.
+#[derive(knuffel::Decode, Debug, Default, Clone, PartialEq)]
+pub struct Hooks(#[knuffel(children)] pub Vec<Hook>);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hook {
+    pub event: HookEvent,
+    /// Window matchers, only allowed for events about windows.
+    pub matches: Vec<HookMatch>,
+    pub excludes: Vec<HookMatch>,
+    pub command: HookCommand,
+}
+
+/// Kind of an event-stream event, named like the `niri_ipc::Event` variants in kebab-case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HookEvent {
+    WorkspacesChanged,
+    WorkspaceUrgencyChanged,
+    WorkspaceActivated,
+    WorkspaceActiveWindowChanged,
+    WindowsChanged,
+    WindowOpenedOrChanged,
+    WindowClosed,
+    WindowFocusChanged,
+    WindowFocusTimestampChanged,
+    WindowUrgencyChanged,
+    WindowLayoutsChanged,
+    KeyboardLayoutsChanged,
+    KeyboardLayoutSwitched,
+    OverviewOpenedOrClosed,
+    MouseKeysToggled,
+    BindModeChanged,
+    AppBindsChanged,
+    ConfigLoaded,
+    ScreenshotCaptured,
+    CastsChanged,
+    CastStartedOrChanged,
+    CastStopped,
+}
+
+#[derive(knuffel::Decode, Debug, Default, Clone, PartialEq)]
+pub struct HookMatch {
+    #[knuffel(property, str)]
+    pub app_id: Option<RegexEq>,
+    #[knuffel(property, str)]
+    pub title: Option<RegexEq>,
+    #[knuffel(property)]
+    pub is_focused: Option<bool>,
+    #[knuffel(property)]
+    pub is_floating: Option<bool>,
+    #[knuffel(property)]
+    pub is_urgent: Option<bool>,
+}
+
+#[derive(knuffel::Decode, Debug, Clone, PartialEq)]
+pub enum HookCommand {
+    Spawn(#[knuffel(arguments)] Vec<String>),
+    SpawnSh(#[knuffel(argument)] String),
+}
+
+impl Hooks {
+    /// Returns the hooks that run for this kind of event.
+    pub fn for_event(&self, event: HookEvent) -> impl Iterator<Item = &Hook> + '_ {
+        self.0.iter().filter(move |hook| hook.event == event)
+    }
+}
+
+impl HookEvent {
+    /// Whether events of this kind refer to a window that hook matchers can check.
+    pub fn is_window_event(self) -> bool {
+        matches!(
+            self,
+            Self::WindowOpenedOrChanged
+                | Self::WindowClosed
+                | Self::WindowFocusChanged
+                | Self::WindowFocusTimestampChanged
+                | Self::WindowUrgencyChanged
+        )
+    }
+
+    /// Whether events of this kind can come many times a second for the same window, so that
+    /// hooks for them need throttling.
+    pub fn is_frequent(self) -> bool {
+        matches!(
+            self,
+            Self::WindowOpenedOrChanged | Self::WindowFocusTimestampChanged
+        )
+    }
+}
+
+impl From<&Event> for HookEvent {
+    fn from(event: &Event) -> Self {
+        match event {
+            Event::WorkspacesChanged { .. } => Self::WorkspacesChanged,
+            Event::WorkspaceUrgencyChanged { .. } => Self::WorkspaceUrgencyChanged,
+            Event::WorkspaceActivated { .. } => Self::WorkspaceActivated,
+            Event::WorkspaceActiveWindowChanged { .. } => Self::WorkspaceActiveWindowChanged,
+            Event::WindowsChanged { .. } => Self::WindowsChanged,
+            Event::WindowOpenedOrChanged { .. } => Self::WindowOpenedOrChanged,
+            Event::WindowClosed { .. } => Self::WindowClosed,
+            Event::WindowFocusChanged { .. } => Self::WindowFocusChanged,
+            Event::WindowFocusTimestampChanged { .. } => Self::WindowFocusTimestampChanged,
+            Event::WindowUrgencyChanged { .. } => Self::WindowUrgencyChanged,
+            Event::WindowLayoutsChanged { .. } => Self::WindowLayoutsChanged,
+            Event::KeyboardLayoutsChanged { .. } => Self::KeyboardLayoutsChanged,
+            Event::KeyboardLayoutSwitched { .. } => Self::KeyboardLayoutSwitched,
+            Event::OverviewOpenedOrClosed { .. } => Self::OverviewOpenedOrClosed,
+            Event::MouseKeysToggled { .. } => Self::MouseKeysToggled,
+            Event::BindModeChanged { .. } => Self::BindModeChanged,
+            Event::AppBindsChanged { .. } => Self::AppBindsChanged,
+            Event::ConfigLoaded { .. } => Self::ConfigLoaded,
+            Event::ScreenshotCaptured { .. } => Self::ScreenshotCaptured,
+            Event::CastsChanged { .. } => Self::CastsChanged,
+            Event::CastStartedOrChanged { .. } => Self::CastStartedOrChanged,
+            Event::CastStopped { .. } => Self::CastStopped,
+        }
+    }
+}
+
+impl FromStr for HookEvent {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "workspaces-changed" => Self::WorkspacesChanged,
+            "workspace-urgency-changed" => Self::WorkspaceUrgencyChanged,
+            "workspace-activated" => Self::WorkspaceActivated,
+            "workspace-active-window-changed" => Self::WorkspaceActiveWindowChanged,
+            "windows-changed" => Self::WindowsChanged,
+            "window-opened-or-changed" => Self::WindowOpenedOrChanged,
+            "window-closed" => Self::WindowClosed,
+            "window-focus-changed" => Self::WindowFocusChanged,
+            "window-focus-timestamp-changed" => Self::WindowFocusTimestampChanged,
+            "window-urgency-changed" => Self::WindowUrgencyChanged,
+            "window-layouts-changed" => Self::WindowLayoutsChanged,
+            "keyboard-layouts-changed" => Self::KeyboardLayoutsChanged,
+            "keyboard-layout-switched" => Self::KeyboardLayoutSwitched,
+            "overview-opened-or-closed" => Self::OverviewOpenedOrClosed,
+            "mouse-keys-toggled" => Self::MouseKeysToggled,
+            "bind-mode-changed" => Self::BindModeChanged,
+            "app-binds-changed" => Self::AppBindsChanged,
+            "config-loaded" => Self::ConfigLoaded,
+            "screenshot-captured" => Self::ScreenshotCaptured,
+            "casts-changed" => Self::CastsChanged,
+            "cast-started-or-changed" => Self::CastStartedOrChanged,
+            "cast-stopped" => Self::CastStopped,
+            _ => return Err("unknown event"),
+        })
+    }
+}
+
+impl<S> knuffel::Decode<S> for Hook
+where
+    S: knuffel::traits::ErrorSpan,
+{
+    fn decode_node(
+        node: &knuffel::ast::SpannedNode<S>,
+        ctx: &mut knuffel::decode::Context<S>,
+    ) -> Result<Self, DecodeError<S>> {
+        if let Some(type_name) = &node.type_name {
+            ctx.emit_error(DecodeError::unexpected(
+                type_name,
+                "type name",
+                "no type name expected for this node",
+            ));
+        }
+
+        let name = &**node.node_name;
+        let event = HookEvent::from_str(name).map_err(|_| {
+            DecodeError::unexpected(
+                &node.node_name,
+                "node",
+                format!("unknown event `{}`", name.escape_default()),
+            )
+        })?;
+
+        for val in node.arguments.iter() {
+            ctx.emit_error(DecodeError::unexpected(
+                &val.literal,
+                "argument",
+                "no arguments expected for this node",
+            ));
+        }
+
+        for name in node.properties.keys() {
+            ctx.emit_error(DecodeError::unexpected(
+                name,
+                "property",
+                format!("unexpected property `{}`", name.escape_default()),
+            ));
+        }
+
+        let mut matches = Vec::new();
+        let mut excludes = Vec::new();
+        let mut command = None;
+        for child in node.children() {
+            let child_name = &**child.node_name;
+            match child_name {
+                "match" | "exclude" => {
+                    if !event.is_window_event() {
+                        ctx.emit_error(DecodeError::unexpected(
+                            child,
+                            "node",
+                            format!("`{name}` events don't refer to a window to match"),
+                        ));
+                        continue;
+                    }
+
+                    let m = HookMatch::decode_node(child, ctx)?;
+                    if child_name == "match" {
+                        matches.push(m);
+                    } else {
+                        excludes.push(m);
+                    }
+                }
+                _ => {
+                    let cmd = HookCommand::decode_node(child, ctx)?;
+                    if command.is_some() {
+                        ctx.emit_error(DecodeError::unexpected(
+                            child,
+                            "node",
+                            "only one command is allowed per hook",
+                        ));
+                    } else {
+                        command = Some(cmd);
+                    }
+                }
+            }
+        }
+
+        let command = command.ok_or_else(|| {
+            DecodeError::missing(
+                node,
+                "expected a `spawn` or `spawn-sh` command for the hook",
+            )
+        })?;
+
+        Ok(Self {
+            event,
+            matches,
+            excludes,
+            command,
+        })
+    }
+}
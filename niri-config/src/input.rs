@@ -1,11 +1,13 @@
 use std::str::FromStr;
 
+use knuffel::errors::DecodeError;
 use miette::miette;
 use smithay::input::keyboard::XkbConfig;
 use smithay::reexports::input;
 
 use crate::binds::Modifiers;
-use crate::utils::{Flag, MergeWith, Percent};
+use crate::gestures::{decode_action_child, GestureAction};
+use crate::utils::{expect_only_children, Flag, MergeWith, Percent, EVDEV_KEYS};
 use crate::FloatOrInt;
 
 #[derive(Debug, Default, PartialEq)]
@@ -23,6 +25,9 @@ pub struct Input {
     pub workspace_auto_back_and_forth: bool,
     pub mod_key: Option<ModKey>,
     pub mod_key_nested: Option<ModKey>,
+    pub remap: Remap,
+    pub mouse_keys: MouseKeys,
+    pub devices: Vec<InputDevice>,
 }
 
 #[derive(knuffel::Decode, Debug, Default, PartialEq)]
@@ -53,6 +58,12 @@ pub struct InputPart {
     pub mod_key: Option<ModKey>,
     #[knuffel(child, unwrap(argument, str))]
     pub mod_key_nested: Option<ModKey>,
+    #[knuffel(child)]
+    pub remap: Option<Remap>,
+    #[knuffel(child)]
+    pub mouse_keys: Option<MouseKeysPart>,
+    #[knuffel(children(name = "device"))]
+    pub devices: Vec<InputDevice>,
 }
 
 impl MergeWith<InputPart> for Input {
@@ -62,6 +73,7 @@ impl MergeWith<InputPart> for Input {
             keyboard,
             disable_power_key_handling,
             workspace_auto_back_and_forth,
+            mouse_keys,
         );
 
         merge_clone!(
@@ -81,6 +93,26 @@ impl MergeWith<InputPart> for Input {
             mod_key,
             mod_key_nested,
         );
+
+        if let Some(remap) = &part.remap {
+            self.remap.merge_with(remap);
+        }
+
+        self.devices.extend(part.devices.iter().cloned());
+    }
+}
+
+impl Input {
+    /// Returns the device sections matching a device, later ones taking precedence.
+    pub fn devices_matching<'a>(
+        &'a self,
+        name: &'a str,
+        vendor: u32,
+        product: u32,
+    ) -> impl Iterator<Item = &'a InputDevice> + 'a {
+        self.devices
+            .iter()
+            .filter(move |device| device.matches(name, vendor, product))
     }
 }
 
@@ -91,6 +123,9 @@ pub struct Keyboard {
     pub repeat_rate: u8,
     pub track_layout: TrackLayout,
     pub numlock: bool,
+    pub sticky_keys: bool,
+    pub slow_keys: KeyDelay,
+    pub bounce_keys: KeyDelay,
 }
 
 impl Default for Keyboard {
@@ -102,6 +137,9 @@ impl Default for Keyboard {
             repeat_rate: 25,
             track_layout: Default::default(),
             numlock: Default::default(),
+            sticky_keys: false,
+            slow_keys: Default::default(),
+            bounce_keys: Default::default(),
         }
     }
 }
@@ -118,12 +156,51 @@ pub struct KeyboardPart {
     pub track_layout: Option<TrackLayout>,
     #[knuffel(child)]
     pub numlock: Option<Flag>,
+    #[knuffel(child)]
+    pub sticky_keys: Option<Flag>,
+    #[knuffel(child)]
+    pub slow_keys: Option<KeyDelayPart>,
+    #[knuffel(child)]
+    pub bounce_keys: Option<KeyDelayPart>,
 }
 
 impl MergeWith<KeyboardPart> for Keyboard {
     fn merge_with(&mut self, part: &KeyboardPart) {
         merge_clone!((self, part), xkb, repeat_delay, repeat_rate, track_layout);
-        merge!((self, part), numlock);
+        merge!((self, part), numlock, sticky_keys, slow_keys, bounce_keys);
+    }
+}
+
+/// Accessibility key filter that acts after a delay, like slow keys and bounce keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyDelay {
+    pub on: bool,
+    pub delay_ms: u16,
+}
+
+impl Default for KeyDelay {
+    fn default() -> Self {
+        Self {
+            on: false,
+            // Matches the GNOME default.
+            delay_ms: 300,
+        }
+    }
+}
+
+#[derive(knuffel::Decode, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyDelayPart {
+    #[knuffel(child)]
+    pub off: bool,
+    #[knuffel(child, unwrap(argument))]
+    pub delay_ms: Option<u16>,
+}
+
+impl MergeWith<KeyDelayPart> for KeyDelay {
+    fn merge_with(&mut self, part: &KeyDelayPart) {
+        // The section being present turns the filter on, unless it has an explicit off.
+        self.on = !part.off;
+        merge_clone!((self, part), delay_ms);
     }
 }
 
@@ -379,6 +456,342 @@ pub struct Touch {
     pub calibration_matrix: Option<Vec<f32>>,
     #[knuffel(child, unwrap(argument))]
     pub map_to_output: Option<String>,
+    #[knuffel(child)]
+    pub edge_swipes: Option<EdgeSwipes>,
+}
+
+#[derive(knuffel::Decode, Debug, Default, Clone, PartialEq)]
+pub struct EdgeSwipes {
+    #[knuffel(child, unwrap(argument))]
+    pub edge_width: Option<FloatOrInt<0, 65535>>,
+    #[knuffel(child)]
+    pub left: Option<EdgeSwipe>,
+    #[knuffel(child)]
+    pub right: Option<EdgeSwipe>,
+    #[knuffel(child)]
+    pub top: Option<EdgeSwipe>,
+    #[knuffel(child)]
+    pub bottom: Option<EdgeSwipe>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EdgeSwipe {
+    pub action: GestureAction,
+}
+
+impl EdgeSwipes {
+    /// Width of the area along the edges where a touch starts an edge swipe.
+    pub fn edge_width(&self) -> f64 {
+        self.edge_width.map_or(20., |w| w.0)
+    }
+}
+
+impl<S> knuffel::Decode<S> for EdgeSwipe
+where
+    S: knuffel::traits::ErrorSpan,
+{
+    fn decode_node(
+        node: &knuffel::ast::SpannedNode<S>,
+        ctx: &mut knuffel::decode::Context<S>,
+    ) -> Result<Self, DecodeError<S>> {
+        expect_only_children(node, ctx);
+        let action = decode_action_child(node, ctx)?;
+        Ok(Self { action })
+    }
+}
+
+impl<S> knuffel::Decode<S> for KeyRemap
+where
+    S: knuffel::traits::ErrorSpan,
+{
+    fn decode_node(
+        node: &knuffel::ast::SpannedNode<S>,
+        ctx: &mut knuffel::decode::Context<S>,
+    ) -> Result<Self, DecodeError<S>> {
+        if let Some(type_name) = &node.type_name {
+            ctx.emit_error(DecodeError::unexpected(
+                type_name,
+                "type name",
+                "no type name expected for this node",
+            ));
+        }
+
+        for child in node.children() {
+            ctx.emit_error(DecodeError::unexpected(
+                child,
+                "node",
+                "no children expected for this node",
+            ));
+        }
+
+        let from = EvdevKey::from_str(&node.node_name)
+            .map_err(|e| DecodeError::conversion(&node.node_name, e))?;
+
+        let mut args = node.arguments.iter();
+        let key = args
+            .next()
+            .map(|val| decode_evdev_key(val, ctx))
+            .transpose()?;
+        for val in args {
+            ctx.emit_error(DecodeError::unexpected(
+                &val.literal,
+                "argument",
+                "unexpected argument",
+            ));
+        }
+
+        let mut tap = None;
+        let mut hold = None;
+        let mut tapping_term_ms = None;
+        for (name, val) in &node.properties {
+            match &***name {
+                "tap" => tap = Some(decode_evdev_key(val, ctx)?),
+                "hold" => hold = Some(decode_evdev_key(val, ctx)?),
+                "tapping-term-ms" => {
+                    tapping_term_ms = Some(knuffel::traits::DecodeScalar::decode(val, ctx)?);
+                }
+                name_str => {
+                    ctx.emit_error(DecodeError::unexpected(
+                        name,
+                        "property",
+                        format!("unexpected property `{}`", name_str.escape_default()),
+                    ));
+                }
+            }
+        }
+
+        let to = match (key, tap, hold, tapping_term_ms) {
+            (Some(key), None, None, None) => RemapTarget::Key(key),
+            (None, Some(tap), Some(hold), tapping_term_ms) => RemapTarget::DualRole {
+                tap,
+                hold,
+                tapping_term_ms: tapping_term_ms.unwrap_or(200),
+            },
+            _ => {
+                return Err(DecodeError::missing(
+                    node,
+                    "expected either a key argument, or both `tap` and `hold` properties",
+                ));
+            }
+        };
+
+        Ok(Self { from, to })
+    }
+}
+
+fn decode_evdev_key<S: knuffel::traits::ErrorSpan>(
+    val: &knuffel::ast::Value<S>,
+    ctx: &mut knuffel::decode::Context<S>,
+) -> Result<EvdevKey, DecodeError<S>> {
+    let name: String = knuffel::traits::DecodeScalar::decode(val, ctx)?;
+    EvdevKey::from_str(&name).map_err(|e| DecodeError::conversion(&val.literal, e))
+}
+
+/// Settings for specific devices, overriding the settings of their device type.
+#[derive(knuffel::Decode, Debug, Default, Clone, PartialEq)]
+pub struct InputDevice {
+    #[knuffel(argument)]
+    pub name: Option<String>,
+    #[knuffel(property)]
+    pub vendor: Option<u32>,
+    #[knuffel(property)]
+    pub product: Option<u32>,
+    #[knuffel(child)]
+    pub natural_scroll: Option<Flag>,
+    #[knuffel(child, unwrap(argument))]
+    pub accel_speed: Option<FloatOrInt<-1, 1>>,
+    #[knuffel(child, unwrap(argument, str))]
+    pub accel_profile: Option<AccelProfile>,
+    #[knuffel(child, unwrap(argument, str))]
+    pub scroll_method: Option<ScrollMethod>,
+    #[knuffel(child, unwrap(argument))]
+    pub scroll_button: Option<u32>,
+    #[knuffel(child)]
+    pub left_handed: Option<Flag>,
+    #[knuffel(child)]
+    pub edge_swipes: Option<EdgeSwipes>,
+    #[knuffel(child)]
+    pub xkb: Option<Xkb>,
+    #[knuffel(child)]
+    pub remap: Option<Remap>,
+}
+
+impl InputDevice {
+    /// Returns whether the device matches every criterion set in this section.
+    pub fn matches(&self, name: &str, vendor: u32, product: u32) -> bool {
+        if self.name.is_none() && self.vendor.is_none() && self.product.is_none() {
+            return false;
+        }
+
+        self.name.as_deref().is_none_or(|n| n == name)
+            && self.vendor.is_none_or(|v| v == vendor)
+            && self.product.is_none_or(|p| p == product)
+    }
+}
+
+/// Keys rewritten before they reach XKB, the binds, and the clients.
+#[derive(knuffel::Decode, Debug, Default, Clone, PartialEq, Eq)]
+pub struct Remap(#[knuffel(children)] pub Vec<KeyRemap>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyRemap {
+    pub from: EvdevKey,
+    pub to: RemapTarget,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemapTarget {
+    Key(EvdevKey),
+    /// Acts as `tap` when pressed and released alone, and as `hold` when held with other keys or
+    /// for longer than the tapping term.
+    DualRole {
+        tap: EvdevKey,
+        hold: EvdevKey,
+        tapping_term_ms: u16,
+    },
+}
+
+/// Linux evdev key code, as in `linux/input-event-codes.h`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EvdevKey(pub u32);
+
+impl MergeWith<Remap> for Remap {
+    fn merge_with(&mut self, part: &Remap) {
+        // Later remaps of the same key replace earlier ones.
+        self.0
+            .retain(|remap| !part.0.iter().any(|new| new.from == remap.from));
+        self.0.extend(part.0.iter().copied());
+    }
+}
+
+impl Remap {
+    pub fn find(&self, key: EvdevKey) -> Option<RemapTarget> {
+        self.0
+            .iter()
+            .find(|remap| remap.from == key)
+            .map(|remap| remap.to)
+    }
+}
+
+/// Moving the pointer and clicking with keys, by default on the numeric keypad.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MouseKeys {
+    pub on: bool,
+    /// Pointer speed in logical pixels per second when it starts moving.
+    pub speed: u16,
+    /// Pointer speed in logical pixels per second after accelerating.
+    pub max_speed: u16,
+    /// Time to go from `speed` to `max_speed`.
+    pub acceleration_ms: u16,
+    pub up: EvdevKey,
+    pub down: EvdevKey,
+    pub left: EvdevKey,
+    pub right: EvdevKey,
+    pub up_left: EvdevKey,
+    pub up_right: EvdevKey,
+    pub down_left: EvdevKey,
+    pub down_right: EvdevKey,
+    /// Presses the selected button while held.
+    pub click: EvdevKey,
+    /// Presses the selected button until `release`, for dragging.
+    pub press: EvdevKey,
+    pub release: EvdevKey,
+    pub left_button: EvdevKey,
+    pub middle_button: EvdevKey,
+    pub right_button: EvdevKey,
+}
+
+impl Default for MouseKeys {
+    fn default() -> Self {
+        // The keypad layout of the X server mouse keys.
+        Self {
+            on: false,
+            speed: 200,
+            max_speed: 1200,
+            acceleration_ms: 1000,
+            up: EvdevKey(72),            // KP8
+            down: EvdevKey(80),          // KP2
+            left: EvdevKey(75),          // KP4
+            right: EvdevKey(77),         // KP6
+            up_left: EvdevKey(71),       // KP7
+            up_right: EvdevKey(73),      // KP9
+            down_left: EvdevKey(79),     // KP1
+            down_right: EvdevKey(81),    // KP3
+            click: EvdevKey(76),         // KP5
+            press: EvdevKey(82),         // KP0
+            release: EvdevKey(83),       // KPDOT
+            left_button: EvdevKey(98),   // KPSLASH
+            middle_button: EvdevKey(55), // KPASTERISK
+            right_button: EvdevKey(74),  // KPMINUS
+        }
+    }
+}
+
+#[derive(knuffel::Decode, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MouseKeysPart {
+    #[knuffel(child)]
+    pub off: bool,
+    #[knuffel(child, unwrap(argument))]
+    pub speed: Option<u16>,
+    #[knuffel(child, unwrap(argument))]
+    pub max_speed: Option<u16>,
+    #[knuffel(child, unwrap(argument))]
+    pub acceleration_ms: Option<u16>,
+    #[knuffel(child, unwrap(argument, str))]
+    pub up: Option<EvdevKey>,
+    #[knuffel(child, unwrap(argument, str))]
+    pub down: Option<EvdevKey>,
+    #[knuffel(child, unwrap(argument, str))]
+    pub left: Option<EvdevKey>,
+    #[knuffel(child, unwrap(argument, str))]
+    pub right: Option<EvdevKey>,
+    #[knuffel(child, unwrap(argument, str))]
+    pub up_left: Option<EvdevKey>,
+    #[knuffel(child, unwrap(argument, str))]
+    pub up_right: Option<EvdevKey>,
+    #[knuffel(child, unwrap(argument, str))]
+    pub down_left: Option<EvdevKey>,
+    #[knuffel(child, unwrap(argument, str))]
+    pub down_right: Option<EvdevKey>,
+    #[knuffel(child, unwrap(argument, str))]
+    pub click: Option<EvdevKey>,
+    #[knuffel(child, unwrap(argument, str))]
+    pub press: Option<EvdevKey>,
+    #[knuffel(child, unwrap(argument, str))]
+    pub release: Option<EvdevKey>,
+    #[knuffel(child, unwrap(argument, str))]
+    pub left_button: Option<EvdevKey>,
+    #[knuffel(child, unwrap(argument, str))]
+    pub middle_button: Option<EvdevKey>,
+    #[knuffel(child, unwrap(argument, str))]
+    pub right_button: Option<EvdevKey>,
+}
+
+impl MergeWith<MouseKeysPart> for MouseKeys {
+    fn merge_with(&mut self, part: &MouseKeysPart) {
+        // Like the key filters, the section being present turns mouse keys on.
+        self.on = !part.off;
+        merge_clone!(
+            (self, part),
+            speed,
+            max_speed,
+            acceleration_ms,
+            up,
+            down,
+            left,
+            right,
+            up_left,
+            up_right,
+            down_left,
+            down_right,
+            click,
+            press,
+            release,
+            left_button,
+            middle_button,
+            right_button,
+        );
+    }
 }
 
 #[derive(knuffel::Decode, Debug, Clone, Copy, PartialEq)]
@@ -510,6 +923,28 @@ impl FromStr for TapButtonMap {
     }
 }
 
+impl FromStr for EvdevKey {
+    type Err = miette::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(code) = s.parse::<u32>() {
+            return Ok(Self(code));
+        }
+
+        let lower = s.to_ascii_lowercase();
+        let name = lower.strip_prefix("key_").unwrap_or(&lower);
+
+        let Some((_, code)) = EVDEV_KEYS.iter().find(|(n, _)| *n == name) else {
+            return Err(miette!(
+                "invalid key {s:?}, expected a name from linux/input-event-codes.h \
+                 like \"capslock\" or \"KEY_CAPSLOCK\", or a key code"
+            ));
+        };
+
+        Ok(Self(*code))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use insta::assert_debug_snapshot;
@@ -746,4 +1181,163 @@ mod tests {
         )
         ");
     }
+
+    #[test]
+    fn parse_devices() {
+        let parsed = do_parse(
+            r#"
+            device "Logitech MX Master 3" {
+                accel-speed 0.3
+                natural-scroll false
+            }
+            device vendor=0x046d product=0x4082 {
+                scroll-method "on-button-down"
+                scroll-button 274
+                left-handed
+            }
+            device "Keychron K3" {
+                xkb {
+                    layout "us,de"
+                    options "grp:win_space_toggle"
+                }
+            }
+            "#,
+        );
+
+        let matching: Vec<_> = parsed
+            .devices_matching("Logitech MX Master 3", 0x046d, 0x4082)
+            .collect();
+        assert_eq!(matching.len(), 2);
+        assert_eq!(matching[0].accel_speed, Some(FloatOrInt(0.3)));
+        assert_eq!(matching[0].natural_scroll, Some(Flag(false)));
+        assert_eq!(matching[1].scroll_method, Some(ScrollMethod::OnButtonDown));
+        assert_eq!(matching[1].left_handed, Some(Flag(true)));
+
+        // Every criterion must match.
+        let matching = parsed.devices_matching("Logitech MX Master 3", 0x046d, 0xb034);
+        assert_eq!(matching.count(), 1);
+        let matching = parsed.devices_matching("Some Touchpad", 0x06cb, 0x0001);
+        assert_eq!(matching.count(), 0);
+
+        let keychron = parsed
+            .devices_matching("Keychron K3", 0x05ac, 0x024f)
+            .next();
+        let xkb = keychron.unwrap().xkb.as_ref().unwrap();
+        assert_eq!(xkb.layout, "us,de");
+        assert_eq!(xkb.options.as_deref(), Some("grp:win_space_toggle"));
+    }
+
+    #[test]
+    fn parse_remap() {
+        let parsed = do_parse(
+            r#"
+            remap {
+                capslock tap="esc" hold="leftctrl"
+                tab tap="tab" hold="leftmeta" tapping-term-ms=300
+                KEY_RIGHTALT "leftmeta"
+                "102nd" "58"
+            }
+            device "Keychron K3" {
+                remap {
+                    capslock "esc"
+                }
+            }
+            "#,
+        );
+
+        assert_eq!(
+            parsed.remap.find(EvdevKey(58)),
+            Some(RemapTarget::DualRole {
+                tap: EvdevKey(1),
+                hold: EvdevKey(29),
+                tapping_term_ms: 200,
+            })
+        );
+        assert_eq!(
+            parsed.remap.find(EvdevKey(15)),
+            Some(RemapTarget::DualRole {
+                tap: EvdevKey(15),
+                hold: EvdevKey(125),
+                tapping_term_ms: 300,
+            })
+        );
+        assert_eq!(
+            parsed.remap.find(EvdevKey(100)),
+            Some(RemapTarget::Key(EvdevKey(125)))
+        );
+        assert_eq!(
+            parsed.remap.find(EvdevKey(86)),
+            Some(RemapTarget::Key(EvdevKey(58)))
+        );
+        assert_eq!(parsed.remap.find(EvdevKey(30)), None);
+
+        let keychron = parsed.devices_matching("Keychron K3", 0, 0).next().unwrap();
+        assert_eq!(
+            keychron.remap.as_ref().unwrap().find(EvdevKey(58)),
+            Some(RemapTarget::Key(EvdevKey(1)))
+        );
+
+        assert!("KEY_NOPE".parse::<EvdevKey>().is_err());
+        assert_eq!("f24".parse::<EvdevKey>().unwrap(), EvdevKey(194));
+        assert_eq!("KEY_PLAYPAUSE".parse::<EvdevKey>().unwrap(), EvdevKey(164));
+        assert_eq!("micmute".parse::<EvdevKey>().unwrap(), EvdevKey(248));
+    }
+
+    #[test]
+    fn parse_key_filters() {
+        let parsed = do_parse(
+            r#"
+            keyboard {
+                sticky-keys
+                slow-keys
+                bounce-keys {
+                    off
+                    delay-ms 500
+                }
+            }
+            "#,
+        );
+
+        let keyboard = &parsed.keyboard;
+        assert!(keyboard.sticky_keys);
+        assert_eq!(
+            keyboard.slow_keys,
+            KeyDelay {
+                on: true,
+                delay_ms: 300,
+            }
+        );
+        assert_eq!(
+            keyboard.bounce_keys,
+            KeyDelay {
+                on: false,
+                delay_ms: 500,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_mouse_keys() {
+        let parsed = do_parse(
+            r#"
+            mouse-keys {
+                off
+                max-speed 2000
+                up "k"
+                down "j"
+                click "KEY_SPACE"
+            }
+            "#,
+        );
+
+        let mouse_keys = &parsed.mouse_keys;
+        assert!(!mouse_keys.on);
+        assert_eq!(mouse_keys.speed, 200);
+        assert_eq!(mouse_keys.max_speed, 2000);
+        assert_eq!(mouse_keys.up, EvdevKey(37));
+        assert_eq!(mouse_keys.down, EvdevKey(36));
+        assert_eq!(mouse_keys.click, EvdevKey(57));
+        // Keys that aren't set keep the keypad defaults.
+        assert_eq!(mouse_keys.left, EvdevKey(75));
+    }
 }
@@ -1,5 +1,5 @@
 use knuffel::errors::DecodeError;
-use niri_ipc::{ColumnDisplay, SizeChange};
+use niri_ipc::{ColumnDisplay, LayoutMode, SizeChange};
 
 use crate::appearance::{
     Border, FocusRing, InsertHint, Shadow, TabIndicator, DEFAULT_BACKGROUND_COLOR,
@@ -14,13 +14,18 @@ pub struct Layout {
     pub shadow: Shadow,
     pub tab_indicator: TabIndicator,
     pub insert_hint: InsertHint,
+    pub floating_snap: FloatingSnap,
+    pub snap_zones: SnapZones,
     pub preset_column_widths: Vec<PresetSize>,
     pub default_column_width: Option<PresetSize>,
     pub preset_window_heights: Vec<PresetSize>,
     pub center_focused_column: CenterFocusedColumn,
     pub always_center_single_column: bool,
     pub empty_workspace_above_first: bool,
+    pub show_window_marks: bool,
     pub default_column_display: ColumnDisplay,
+    pub layout_mode: LayoutMode,
+    pub orientation: Orientation,
     pub gaps: f64,
     pub struts: Struts,
     pub background_color: Color,
@@ -34,6 +39,8 @@ impl Default for Layout {
             shadow: Shadow::default(),
             tab_indicator: TabIndicator::default(),
             insert_hint: InsertHint::default(),
+            floating_snap: FloatingSnap::default(),
+            snap_zones: SnapZones::default(),
             preset_column_widths: vec![
                 PresetSize::Proportion(1. / 3.),
                 PresetSize::Proportion(0.5),
@@ -43,7 +50,10 @@ impl Default for Layout {
             center_focused_column: CenterFocusedColumn::Never,
             always_center_single_column: false,
             empty_workspace_above_first: false,
+            show_window_marks: false,
             default_column_display: ColumnDisplay::Normal,
+            layout_mode: LayoutMode::Scrolling,
+            orientation: Orientation::Horizontal,
             gaps: 16.,
             struts: Struts::default(),
             preset_window_heights: vec![
@@ -65,8 +75,11 @@ impl MergeWith<LayoutPart> for Layout {
             shadow,
             tab_indicator,
             insert_hint,
+            floating_snap,
+            snap_zones,
             always_center_single_column,
             empty_workspace_above_first,
+            show_window_marks,
             gaps,
         );
 
@@ -76,6 +89,8 @@ impl MergeWith<LayoutPart> for Layout {
             preset_window_heights,
             center_focused_column,
             default_column_display,
+            layout_mode,
+            orientation,
             struts,
             background_color,
         );
@@ -106,6 +121,10 @@ pub struct LayoutPart {
     pub tab_indicator: Option<TabIndicatorPart>,
     #[knuffel(child)]
     pub insert_hint: Option<InsertHintPart>,
+    #[knuffel(child)]
+    pub floating_snap: Option<FloatingSnapPart>,
+    #[knuffel(child)]
+    pub snap_zones: Option<SnapZonesPart>,
     #[knuffel(child, unwrap(children))]
     pub preset_column_widths: Option<Vec<PresetSize>>,
     #[knuffel(child)]
@@ -118,8 +137,14 @@ pub struct LayoutPart {
     pub always_center_single_column: Option<Flag>,
     #[knuffel(child)]
     pub empty_workspace_above_first: Option<Flag>,
+    #[knuffel(child)]
+    pub show_window_marks: Option<Flag>,
     #[knuffel(child, unwrap(argument, str))]
     pub default_column_display: Option<ColumnDisplay>,
+    #[knuffel(child, unwrap(argument, str))]
+    pub layout_mode: Option<LayoutMode>,
+    #[knuffel(child, unwrap(argument))]
+    pub orientation: Option<Orientation>,
     #[knuffel(child, unwrap(argument))]
     pub gaps: Option<FloatOrInt<0, 65535>>,
     #[knuffel(child)]
@@ -128,6 +153,96 @@ pub struct LayoutPart {
     pub background_color: Option<Color>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FloatingSnap {
+    pub off: bool,
+    pub distance: f64,
+    pub color: Color,
+}
+
+impl Default for FloatingSnap {
+    fn default() -> Self {
+        Self {
+            off: false,
+            distance: 12.,
+            color: Color::from_rgba8_unpremul(127, 200, 255, 255),
+        }
+    }
+}
+
+impl MergeWith<FloatingSnapPart> for FloatingSnap {
+    fn merge_with(&mut self, part: &FloatingSnapPart) {
+        self.off |= part.off;
+        if part.on {
+            self.off = false;
+        }
+
+        if let Some(x) = part.distance {
+            self.distance = x.0;
+        }
+        merge_clone!((self, part), color);
+    }
+}
+
+#[derive(knuffel::Decode, Debug, Default, Clone, Copy, PartialEq)]
+pub struct FloatingSnapPart {
+    #[knuffel(child)]
+    pub off: bool,
+    #[knuffel(child)]
+    pub on: bool,
+    #[knuffel(child, unwrap(argument))]
+    pub distance: Option<FloatOrInt<0, 65535>>,
+    #[knuffel(child)]
+    pub color: Option<Color>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SnapZones {
+    pub off: bool,
+    /// How close the pointer has to get to an output edge to activate a zone.
+    pub distance: f64,
+    /// Size of the corner areas of the edges that activate a quarter zone.
+    pub corner_size: f64,
+}
+
+impl Default for SnapZones {
+    fn default() -> Self {
+        Self {
+            off: false,
+            distance: 8.,
+            corner_size: 64.,
+        }
+    }
+}
+
+impl MergeWith<SnapZonesPart> for SnapZones {
+    fn merge_with(&mut self, part: &SnapZonesPart) {
+        self.off |= part.off;
+        if part.on {
+            self.off = false;
+        }
+
+        if let Some(x) = part.distance {
+            self.distance = x.0;
+        }
+        if let Some(x) = part.corner_size {
+            self.corner_size = x.0;
+        }
+    }
+}
+
+#[derive(knuffel::Decode, Debug, Default, Clone, Copy, PartialEq)]
+pub struct SnapZonesPart {
+    #[knuffel(child)]
+    pub off: bool,
+    #[knuffel(child)]
+    pub on: bool,
+    #[knuffel(child, unwrap(argument))]
+    pub distance: Option<FloatOrInt<0, 65535>>,
+    #[knuffel(child, unwrap(argument))]
+    pub corner_size: Option<FloatOrInt<0, 65535>>,
+}
+
 #[derive(knuffel::Decode, Debug, Clone, Copy, PartialEq)]
 pub enum PresetSize {
     Proportion(#[knuffel(argument)] f64),
@@ -170,6 +285,17 @@ pub enum CenterFocusedColumn {
     OnOverflow,
 }
 
+#[derive(knuffel::DecodeScalar, Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum Orientation {
+    /// Columns go left-to-right, and the view scrolls horizontally.
+    #[default]
+    Horizontal,
+    /// Columns go top-to-bottom, and the view scrolls vertically.
+    ///
+    /// Windows inside a column are laid out left-to-right.
+    Vertical,
+}
+
 impl<S> knuffel::Decode<S> for DefaultPresetSize
 where
     S: knuffel::traits::ErrorSpan,
@@ -34,6 +34,7 @@ pub mod binds;
 pub mod debug;
 pub mod error;
 pub mod gestures;
+pub mod hooks;
 pub mod input;
 pub mod layer_rule;
 pub mod layout;
@@ -50,6 +51,7 @@ pub use crate::binds::*;
 pub use crate::debug::Debug;
 pub use crate::error::{ConfigIncludeError, ConfigParseResult};
 pub use crate::gestures::Gestures;
+pub use crate::hooks::Hooks;
 pub use crate::input::{Input, ModKey, ScrollMethod, TrackLayout, WarpMouseToFocusMode, Xkb};
 pub use crate::layer_rule::LayerRule;
 pub use crate::layout::*;
@@ -60,7 +62,8 @@ pub use crate::recent_windows::{MruDirection, MruFilter, MruPreviews, MruScope,
 pub use crate::utils::FloatOrInt;
 use crate::utils::{Flag, MergeWith as _};
 pub use crate::window_rule::{
-    FloatingPosition, OnXdgActivate, PopupsRule, RelativeTo, ResolvedPopupsRules, WindowRule,
+    FloatingPosition, OnXdgActivate, PopupsRule, RelativeTo, ResolvedPopupsRules, SwallowParent,
+    WindowRule,
 };
 pub use crate::workspace::{Workspace, WorkspaceLayoutPart};
 
@@ -74,6 +77,7 @@ pub struct Config {
     pub spawn_sh_at_startup: Vec<SpawnShAtStartup>,
     pub layout: Layout,
     pub prefer_no_csd: bool,
+    pub restore_layout: bool,
     pub cursor: Cursor,
     pub screenshot_path: ScreenshotPath,
     pub clipboard: Clipboard,
@@ -83,12 +87,16 @@ pub struct Config {
     pub blur: Blur,
     pub gestures: Gestures,
     pub overview: Overview,
+    pub zoom: Zoom,
     pub environment: Environment,
     pub xwayland_satellite: XwaylandSatellite,
     pub window_rules: Vec<WindowRule>,
     pub layer_rules: Vec<LayerRule>,
     pub binds: Binds,
+    pub bind_modes: Vec<BindMode>,
+    pub app_binds: Vec<AppBinds>,
     pub switch_events: SwitchBinds,
+    pub hooks: Hooks,
     pub debug: Debug,
     pub workspaces: Vec<Workspace>,
     pub recent_windows: RecentWindows,
@@ -156,7 +164,8 @@ where
             let name = &**node.node_name;
 
             // Within one config file, splitting sections into multiple parts is not allowed to
-            // reduce confusion. The exceptions here aren't multipart; they all add new values.
+            // reduce confusion. The exceptions here add new values, except for `bind-mode` and
+            // `app-binds` sections with the same name, which are merged into one.
             if !matches!(
                 name,
                 "output"
@@ -165,6 +174,8 @@ where
                     | "window-rule"
                     | "layer-rule"
                     | "workspace"
+                    | "bind-mode"
+                    | "app-binds"
                     | "include"
             ) && !seen.insert(name)
             {
@@ -200,6 +211,7 @@ where
                 "blur" => m_merge!(blur),
                 "gestures" => m_merge!(gestures),
                 "overview" => m_merge!(overview),
+                "zoom" => m_merge!(zoom),
                 "xwayland-satellite" => m_merge!(xwayland_satellite),
                 "switch-events" => m_merge!(switch_events),
                 "debug" => m_merge!(debug),
@@ -214,6 +226,45 @@ where
                 "window-rule" => m_push!(window_rules),
                 "layer-rule" => m_push!(layer_rules),
                 "workspace" => m_push!(workspaces),
+                "bind-mode" => {
+                    let part = BindMode::decode_node(node, ctx)?;
+
+                    // Like with the binds section, later parts of the same mode replace
+                    // conflicting binds.
+                    let mut config = config.borrow_mut();
+                    if let Some(mode) = config.bind_modes.iter_mut().find(|m| m.name == part.name) {
+                        let binds = &mut mode.binds.0;
+                        binds.retain(|bind| {
+                            !part.binds.0.iter().any(|new| new.keys().eq(bind.keys()))
+                        });
+                        binds.extend(part.binds.0);
+
+                        if part.hotkey_overlay_title.is_some() {
+                            mode.hotkey_overlay_title = part.hotkey_overlay_title;
+                        }
+                    } else {
+                        config.bind_modes.push(part);
+                    }
+                }
+                "app-binds" => {
+                    let part = AppBinds::decode_node(node, ctx)?;
+
+                    // Later parts of the same app binds add matchers and replace conflicting
+                    // binds.
+                    let mut config = config.borrow_mut();
+                    if let Some(app) = config.app_binds.iter_mut().find(|a| a.name == part.name) {
+                        app.matches.extend(part.matches);
+                        app.excludes.extend(part.excludes);
+
+                        let binds = &mut app.binds.0;
+                        binds.retain(|bind| {
+                            !part.binds.0.iter().any(|new| new.keys().eq(bind.keys()))
+                        });
+                        binds.extend(part.binds.0);
+                    } else {
+                        config.app_binds.push(part);
+                    }
+                }
 
                 // Single-part sections.
                 "binds" => {
@@ -225,7 +276,7 @@ where
                     let mut config = config.borrow_mut();
                     let binds = &mut config.binds.0;
                     // Remove existing binds matching any new bind.
-                    binds.retain(|bind| !part.0.iter().any(|new| new.key == bind.key));
+                    binds.retain(|bind| !part.0.iter().any(|new| new.keys().eq(bind.keys())));
                     // Add all new binds.
                     binds.extend(part.0);
                 }
@@ -233,11 +284,19 @@ where
                     let part = Environment::decode_node(node, ctx)?;
                     config.borrow_mut().environment.0.extend(part.0);
                 }
+                "hooks" => {
+                    let part = Hooks::decode_node(node, ctx)?;
+                    config.borrow_mut().hooks.0.extend(part.0);
+                }
 
                 "prefer-no-csd" => {
                     config.borrow_mut().prefer_no_csd = Flag::decode_node(node, ctx)?.0
                 }
 
+                "restore-layout" => {
+                    config.borrow_mut().restore_layout = Flag::decode_node(node, ctx)?.0
+                }
+
                 "screenshot-path" => {
                     let part = knuffel::Decode::decode_node(node, ctx)?;
                     config.borrow_mut().screenshot_path = part;
@@ -534,6 +593,14 @@ impl Config {
     pub fn parse_mem(text: &str) -> Result<Self, ConfigIncludeError> {
         Self::parse(Path::new("config.kdl"), text).config
     }
+
+    pub fn bind_mode(&self, name: &str) -> Option<&BindMode> {
+        self.bind_modes.iter().find(|mode| mode.name == name)
+    }
+
+    pub fn app_binds(&self, name: &str) -> Option<&AppBinds> {
+        self.app_binds.iter().find(|app| app.name == name)
+    }
 }
 
 impl ConfigPath {
@@ -679,6 +746,375 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_bind_modes() {
+        let parsed = do_parse(
+            r#"
+            bind-mode "resize" hotkey-overlay-title="Resize" {
+                H { set-column-width "-10%"; }
+                L { set-column-width "+10%"; }
+            }
+
+            bind-mode "launch" {
+                T { spawn "alacritty"; }
+            }
+
+            bind-mode "resize" {
+                L { set-column-width "+5%"; }
+                Return { exit-bind-mode; }
+            }
+            "#,
+        );
+
+        let names: Vec<_> = parsed.bind_modes.iter().map(|m| &*m.name).collect();
+        assert_eq!(names, ["resize", "launch"]);
+
+        let resize = parsed.bind_mode("resize").unwrap();
+        assert_eq!(resize.hotkey_overlay_title.as_deref(), Some("Resize"));
+        let actions: Vec<_> = resize.binds.0.iter().map(|b| &b.action).collect();
+        assert_eq!(
+            actions,
+            [
+                &Action::SetColumnWidth(niri_ipc::SizeChange::AdjustProportion(-10.)),
+                &Action::SetColumnWidth(niri_ipc::SizeChange::AdjustProportion(5.)),
+                &Action::ExitBindMode,
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_app_binds() {
+        let parsed = do_parse(
+            r#"
+            app-binds "tool" {
+                match app-id="^internal-tool$"
+                exclude title="Settings"
+                Mod+W { spawn "tool-close-tab"; }
+            }
+
+            app-binds "tool" {
+                match app-id="^internal-tool-beta$"
+                Mod+W { spawn "tool-beta-close-tab"; }
+                Mod+T { spawn "tool-new-tab"; }
+            }
+            "#,
+        );
+
+        assert_eq!(parsed.app_binds.len(), 1);
+        let tool = parsed.app_binds("tool").unwrap();
+        assert_eq!(tool.matches.len(), 2);
+        assert_eq!(tool.excludes.len(), 1);
+
+        let actions: Vec<_> = tool.binds.0.iter().map(|b| &b.action).collect();
+        assert_eq!(
+            actions,
+            [
+                &Action::Spawn(vec![String::from("tool-beta-close-tab")]),
+                &Action::Spawn(vec![String::from("tool-new-tab")]),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_gesture_binds() {
+        use crate::gestures::{GestureAction, GestureTrigger, PinchDirection, SwipeDirection};
+
+        let parsed = do_parse(
+            r#"
+            gestures {
+                swipe fingers=3 direction="left" { view-scroll-gesture; }
+                swipe fingers=4 direction="up" { toggle-overview; }
+                pinch fingers=2 direction="in" { close-window; }
+                hold fingers=3 { spawn "menu"; }
+            }
+
+            gestures {
+                swipe fingers=4 direction="up" { overview-gesture; }
+            }
+            "#,
+        );
+
+        let binds: Vec<_> = parsed
+            .gestures
+            .binds
+            .iter()
+            .map(|b| (b.trigger, &b.action))
+            .collect();
+        assert_eq!(
+            binds,
+            [
+                (
+                    GestureTrigger::Swipe {
+                        fingers: 3,
+                        direction: SwipeDirection::Left
+                    },
+                    &GestureAction::ViewScroll
+                ),
+                (
+                    GestureTrigger::Pinch {
+                        fingers: 2,
+                        direction: PinchDirection::In
+                    },
+                    &GestureAction::Action(Action::CloseWindow)
+                ),
+                (
+                    GestureTrigger::Hold { fingers: 3 },
+                    &GestureAction::Action(Action::Spawn(vec![String::from("menu")]))
+                ),
+                (
+                    GestureTrigger::Swipe {
+                        fingers: 4,
+                        direction: SwipeDirection::Up
+                    },
+                    &GestureAction::Overview
+                ),
+            ]
+        );
+
+        // Interactive gestures need a swipe to follow.
+        let res = Config::parse_mem(
+            r#"
+            gestures {
+                pinch fingers=2 direction="out" { overview-gesture; }
+            }
+            "#,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn parse_touch_edge_swipes() {
+        use crate::gestures::GestureAction;
+
+        let parsed = do_parse(
+            r#"
+            input {
+                touch {
+                    edge-swipes {
+                        edge-width 30
+                        left { view-scroll-gesture; }
+                        bottom { spawn "keyboard"; }
+                    }
+                }
+            }
+            "#,
+        );
+
+        let edge_swipes = parsed.input.touch.edge_swipes.unwrap();
+        assert_eq!(edge_swipes.edge_width(), 30.);
+        assert_eq!(
+            edge_swipes.left.map(|swipe| swipe.action),
+            Some(GestureAction::ViewScroll)
+        );
+        assert_eq!(edge_swipes.right, None);
+        assert_eq!(
+            edge_swipes.bottom.map(|swipe| swipe.action),
+            Some(GestureAction::Action(Action::Spawn(vec![String::from(
+                "keyboard"
+            )])))
+        );
+    }
+
+    #[test]
+    fn parse_action_sequences() {
+        let parsed = do_parse(
+            r#"
+            binds {
+                Mod+C {
+                    focus-workspace "chat";
+                    focus-column-first;
+                    maximize-column;
+                }
+                Mod+T { spawn "alacritty"; }
+            }
+            "#,
+        );
+
+        assert_eq!(
+            parsed.binds.0[0].action,
+            Action::Sequence(vec![
+                Action::FocusWorkspace(WorkspaceReference::Name(String::from("chat"))),
+                Action::FocusColumnFirst,
+                Action::MaximizeColumn,
+            ])
+        );
+        assert_eq!(
+            parsed.binds.0[1].action,
+            Action::Spawn(vec![String::from("alacritty")])
+        );
+
+        // Every action must be a spawn to run while locked.
+        let res = Config::parse_mem(
+            r#"
+            binds {
+                Mod+C allow-when-locked=true {
+                    spawn "a";
+                    close-window;
+                }
+            }
+            "#,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn parse_hooks() {
+        use crate::hooks::{HookCommand, HookEvent};
+
+        let parsed = do_parse(
+            r#"
+            hooks {
+                window-opened-or-changed {
+                    match app-id="^firefox$"
+                    exclude title="Private"
+                    spawn "notify-send" "Firefox"
+                }
+                workspace-activated {
+                    spawn-sh "echo $NIRI_EVENT >>~/events"
+                }
+            }
+
+            hooks {
+                config-loaded { spawn "reload-bar"; }
+            }
+            "#,
+        );
+
+        let hooks = &parsed.hooks.0;
+        assert_eq!(hooks.len(), 3);
+        assert_eq!(hooks[0].event, HookEvent::WindowOpenedOrChanged);
+        assert_eq!(hooks[0].matches.len(), 1);
+        assert_eq!(hooks[0].excludes.len(), 1);
+        assert_eq!(
+            hooks[0].command,
+            HookCommand::Spawn(vec![String::from("notify-send"), String::from("Firefox")])
+        );
+        assert_eq!(hooks[1].event, HookEvent::WorkspaceActivated);
+        assert_eq!(
+            hooks[1].command,
+            HookCommand::SpawnSh(String::from("echo $NIRI_EVENT >>~/events"))
+        );
+        assert_eq!(hooks[2].event, HookEvent::ConfigLoaded);
+
+        let event = niri_ipc::Event::WindowClosed { id: 1 };
+        assert_eq!(HookEvent::from(&event), HookEvent::WindowClosed);
+
+        // Matchers only make sense for window events.
+        let res = Config::parse_mem(
+            r#"
+            hooks {
+                workspace-activated {
+                    match app-id="firefox"
+                    spawn "a"
+                }
+            }
+            "#,
+        );
+        assert!(res.is_err());
+
+        let res = Config::parse_mem(
+            r#"
+            hooks {
+                window-teleported { spawn "a"; }
+            }
+            "#,
+        );
+        assert!(res.is_err());
+
+        let res = Config::parse_mem(
+            r#"
+            hooks {
+                window-closed {}
+            }
+            "#,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn parse_bind_sequences() {
+        let parsed = do_parse(
+            r#"
+            binds {
+                Mod+Space W H { focus-column-left; }
+                Mod + Space  W L { focus-column-right; }
+                Mod+T { spawn "alacritty"; }
+            }
+            "#,
+        );
+
+        let key = |s: &str| s.parse::<Key>().unwrap();
+        let keys: Vec<Vec<_>> = parsed
+            .binds
+            .0
+            .iter()
+            .map(|bind| bind.keys().copied().collect())
+            .collect();
+        assert_eq!(
+            keys,
+            [
+                vec![key("Mod+Space"), key("W"), key("H")],
+                vec![key("Mod+Space"), key("W"), key("L")],
+                vec![key("Mod+T")],
+            ]
+        );
+
+        // A sequence can't continue past another bind.
+        let res = Config::parse_mem(
+            r#"
+            binds {
+                Mod+Space W { focus-column-left; }
+                Mod+Space W H { focus-column-right; }
+            }
+            "#,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn parse_release_binds() {
+        let parsed = do_parse(
+            r#"
+            binds {
+                Super_L tap=true { toggle-overview; }
+                Mod+T on-release=true { spawn "alacritty"; }
+                Mod+Q { close-window; }
+            }
+            "#,
+        );
+
+        let triggers: Vec<_> = parsed
+            .binds
+            .0
+            .iter()
+            .map(|bind| (bind.on_release, bind.tap))
+            .collect();
+        assert_eq!(triggers, [(false, true), (true, false), (false, false)]);
+    }
+
+    #[test]
+    fn parse_zoom() {
+        let parsed = do_parse(
+            r#"
+            zoom {
+                factor 3
+                step 0.25
+                follow "focus"
+            }
+            "#,
+        );
+
+        assert_eq!(
+            parsed.zoom,
+            Zoom {
+                factor: 3.,
+                step: 0.25,
+                follow: ZoomFollow::Focus,
+                smoothing_ms: 150,
+            }
+        );
+    }
+
     #[test]
     fn parse() {
         let parsed = do_parse(
@@ -843,6 +1279,14 @@ mod tests {
                     color "rgb(255, 200, 127)"
                     gradient from="rgba(10, 20, 30, 1.0)" to="#0080ffff" relative-to="workspace-view"
                 }
+
+                floating-snap {
+                    distance 20
+                }
+
+                snap-zones {
+                    corner-size 100
+                }
             }
 
             spawn-at-startup "alacritty" "-e" "fish"
@@ -850,6 +1294,8 @@ mod tests {
 
             prefer-no-csd
 
+            restore-layout
+
             cursor {
                 xcursor-theme "breeze_cursors"
                 xcursor-size 16
@@ -1011,6 +1457,15 @@ mod tests {
                     repeat_rate: 25,
                     track_layout: Window,
                     numlock: false,
+                    sticky_keys: false,
+                    slow_keys: KeyDelay {
+                        on: false,
+                        delay_ms: 300,
+                    },
+                    bounce_keys: KeyDelay {
+                        on: false,
+                        delay_ms: 300,
+                    },
                 },
                 touchpad: Touchpad {
                     off: false,
@@ -1149,6 +1604,7 @@ mod tests {
                     map_to_output: Some(
                         "eDP-1",
                     ),
+                    edge_swipes: None,
                 },
                 disable_power_key_handling: true,
                 warp_mouse_to_focus: Some(
@@ -1168,6 +1624,58 @@ mod tests {
                 mod_key_nested: Some(
                     Super,
                 ),
+                remap: Remap(
+                    [],
+                ),
+                mouse_keys: MouseKeys {
+                    on: false,
+                    speed: 200,
+                    max_speed: 1200,
+                    acceleration_ms: 1000,
+                    up: EvdevKey(
+                        72,
+                    ),
+                    down: EvdevKey(
+                        80,
+                    ),
+                    left: EvdevKey(
+                        75,
+                    ),
+                    right: EvdevKey(
+                        77,
+                    ),
+                    up_left: EvdevKey(
+                        71,
+                    ),
+                    up_right: EvdevKey(
+                        73,
+                    ),
+                    down_left: EvdevKey(
+                        79,
+                    ),
+                    down_right: EvdevKey(
+                        81,
+                    ),
+                    click: EvdevKey(
+                        76,
+                    ),
+                    press: EvdevKey(
+                        82,
+                    ),
+                    release: EvdevKey(
+                        83,
+                    ),
+                    left_button: EvdevKey(
+                        98,
+                    ),
+                    middle_button: EvdevKey(
+                        55,
+                    ),
+                    right_button: EvdevKey(
+                        74,
+                    ),
+                },
+                devices: [],
             },
             outputs: Outputs(
                 [
@@ -1448,6 +1956,21 @@ mod tests {
                         },
                     ),
                 },
+                floating_snap: FloatingSnap {
+                    off: false,
+                    distance: 20.0,
+                    color: Color {
+                        r: 0.49803922,
+                        g: 0.78431374,
+                        b: 1.0,
+                        a: 1.0,
+                    },
+                },
+                snap_zones: SnapZones {
+                    off: false,
+                    distance: 8.0,
+                    corner_size: 100.0,
+                },
                 preset_column_widths: [
                     Proportion(
                         0.25,
@@ -1484,7 +2007,10 @@ mod tests {
                 center_focused_column: OnOverflow,
                 always_center_single_column: false,
                 empty_workspace_above_first: false,
+                show_window_marks: false,
                 default_column_display: Tabbed,
+                layout_mode: Scrolling,
+                orientation: Horizontal,
                 gaps: 8.0,
                 struts: Struts {
                     left: FloatOrInt(
@@ -1508,6 +2034,7 @@ mod tests {
                 },
             },
             prefer_no_csd: true,
+            restore_layout: true,
             cursor: Cursor {
                 xcursor_theme: "breeze_cursors",
                 xcursor_size: 16,
@@ -1696,6 +2223,7 @@ mod tests {
                     bottom_left: false,
                     bottom_right: false,
                 },
+                binds: [],
             },
             overview: Overview {
                 zoom: 0.5,
@@ -1725,6 +2253,12 @@ mod tests {
                     },
                 },
             },
+            zoom: Zoom {
+                factor: 2.0,
+                step: 0.5,
+                follow: Pointer,
+                smoothing_ms: 150,
+            },
             environment: Environment(
                 [
                     EnvironmentVariable {
@@ -1825,6 +2359,9 @@ mod tests {
                     open_focused: Some(
                         true,
                     ),
+                    open_in_scratchpad: None,
+                    open_sticky: None,
+                    swallow_parent: None,
                     on_xdg_activate: Some(
                         Ignore,
                     ),
@@ -1990,8 +2527,11 @@ mod tests {
                                 COMPOSITOR,
                             ),
                         },
+                        sequence: [],
                         action: ToggleKeyboardShortcutsInhibit,
                         repeat: true,
+                        on_release: false,
+                        tap: false,
                         cooldown: None,
                         allow_when_locked: false,
                         allow_inhibiting: false,
@@ -2010,8 +2550,11 @@ mod tests {
                                 SHIFT | COMPOSITOR,
                             ),
                         },
+                        sequence: [],
                         action: ToggleKeyboardShortcutsInhibit,
                         repeat: true,
+                        on_release: false,
+                        tap: false,
                         cooldown: None,
                         allow_when_locked: false,
                         allow_inhibiting: false,
@@ -2026,12 +2569,15 @@ mod tests {
                                 COMPOSITOR,
                             ),
                         },
+                        sequence: [],
                         action: Spawn(
                             [
                                 "alacritty",
                             ],
                         ),
                         repeat: true,
+                        on_release: false,
+                        tap: false,
                         cooldown: None,
                         allow_when_locked: true,
                         allow_inhibiting: true,
@@ -2046,8 +2592,11 @@ mod tests {
                                 COMPOSITOR,
                             ),
                         },
+                        sequence: [],
                         action: CloseWindow,
                         repeat: true,
+                        on_release: false,
+                        tap: false,
                         cooldown: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
@@ -2064,8 +2613,11 @@ mod tests {
                                 SHIFT | COMPOSITOR,
                             ),
                         },
+                        sequence: [],
                         action: FocusMonitorLeft,
                         repeat: true,
+                        on_release: false,
+                        tap: false,
                         cooldown: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
@@ -2080,10 +2632,13 @@ mod tests {
                                 SHIFT | COMPOSITOR,
                             ),
                         },
+                        sequence: [],
                         action: FocusMonitor(
                             "eDP-1",
                         ),
                         repeat: true,
+                        on_release: false,
+                        tap: false,
                         cooldown: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
@@ -2098,8 +2653,11 @@ mod tests {
                                 CTRL | SHIFT | COMPOSITOR,
                             ),
                         },
+                        sequence: [],
                         action: MoveWindowToMonitorRight,
                         repeat: true,
+                        on_release: false,
+                        tap: false,
                         cooldown: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
@@ -2114,10 +2672,13 @@ mod tests {
                                 CTRL | ALT | COMPOSITOR,
                             ),
                         },
+                        sequence: [],
                         action: MoveWindowToMonitor(
                             "eDP-1",
                         ),
                         repeat: true,
+                        on_release: false,
+                        tap: false,
                         cooldown: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
@@ -2132,10 +2693,13 @@ mod tests {
                                 CTRL | ALT | COMPOSITOR,
                             ),
                         },
+                        sequence: [],
                         action: MoveColumnToMonitor(
                             "DP-1",
                         ),
                         repeat: true,
+                        on_release: false,
+                        tap: false,
                         cooldown: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
@@ -2150,8 +2714,11 @@ mod tests {
                                 COMPOSITOR,
                             ),
                         },
+                        sequence: [],
                         action: ConsumeWindowIntoColumn,
                         repeat: true,
+                        on_release: false,
+                        tap: false,
                         cooldown: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
@@ -2166,12 +2733,15 @@ mod tests {
                                 COMPOSITOR,
                             ),
                         },
+                        sequence: [],
                         action: FocusWorkspace(
                             Index(
                                 1,
                             ),
                         ),
                         repeat: true,
+                        on_release: false,
+                        tap: false,
                         cooldown: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
@@ -2186,12 +2756,15 @@ mod tests {
                                 SHIFT | COMPOSITOR,
                             ),
                         },
+                        sequence: [],
                         action: FocusWorkspace(
                             Name(
                                 "workspace-1",
                             ),
                         ),
                         repeat: true,
+                        on_release: false,
+                        tap: false,
                         cooldown: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
@@ -2206,10 +2779,13 @@ mod tests {
                                 SHIFT | COMPOSITOR,
                             ),
                         },
+                        sequence: [],
                         action: Quit(
                             true,
                         ),
                         repeat: true,
+                        on_release: false,
+                        tap: false,
                         cooldown: None,
                         allow_when_locked: false,
                         allow_inhibiting: false,
@@ -2222,8 +2798,11 @@ mod tests {
                                 COMPOSITOR,
                             ),
                         },
+                        sequence: [],
                         action: FocusWorkspaceDown,
                         repeat: true,
+                        on_release: false,
+                        tap: false,
                         cooldown: Some(
                             150ms,
                         ),
@@ -2240,10 +2819,13 @@ mod tests {
                                 ALT | SUPER,
                             ),
                         },
+                        sequence: [],
                         action: SpawnSh(
                             "pkill orca || exec orca",
                         ),
                         repeat: true,
+                        on_release: false,
+                        tap: false,
                         cooldown: None,
                         allow_when_locked: true,
                         allow_inhibiting: true,
@@ -2251,6 +2833,8 @@ mod tests {
                     },
                 ],
             ),
+            bind_modes: [],
+            app_binds: [],
             switch_events: SwitchBinds {
                 lid_open: None,
                 lid_close: None,
@@ -2273,6 +2857,9 @@ mod tests {
                     },
                 ),
             },
+            hooks: Hooks(
+                [],
+            ),
             debug: Debug {
                 preview_render: None,
                 dbus_interfaces_in_non_session_instances: false,
@@ -2360,6 +2947,7 @@ mod tests {
                                 ALT,
                             ),
                         },
+                        sequence: [],
                         action: MruAdvance {
                             direction: Forward,
                             scope: None,
@@ -2368,6 +2956,8 @@ mod tests {
                             ),
                         },
                         repeat: true,
+                        on_release: false,
+                        tap: false,
                         cooldown: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
@@ -2382,6 +2972,7 @@ mod tests {
                                 ALT,
                             ),
                         },
+                        sequence: [],
                         action: MruAdvance {
                             direction: Forward,
                             scope: None,
@@ -2390,6 +2981,8 @@ mod tests {
                             ),
                         },
                         repeat: true,
+                        on_release: false,
+                        tap: false,
                         cooldown: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
@@ -2404,6 +2997,7 @@ mod tests {
                                 SUPER,
                             ),
                         },
+                        sequence: [],
                         action: MruAdvance {
                             direction: Forward,
                             scope: Some(
@@ -2414,6 +3008,8 @@ mod tests {
                             ),
                         },
                         repeat: true,
+                        on_release: false,
+                        tap: false,
                         cooldown: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
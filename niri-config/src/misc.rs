@@ -152,6 +152,52 @@ impl MergeWith<OverviewPart> for Overview {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Zoom {
+    pub factor: f64,
+    pub step: f64,
+    pub follow: ZoomFollow,
+    pub smoothing_ms: u16,
+}
+
+impl Default for Zoom {
+    fn default() -> Self {
+        Self {
+            factor: 2.,
+            step: 0.5,
+            follow: ZoomFollow::Pointer,
+            smoothing_ms: 150,
+        }
+    }
+}
+
+#[derive(knuffel::DecodeScalar, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZoomFollow {
+    /// Keep the pointer in view, moving the zoomed area as it approaches the edges.
+    Pointer,
+    /// Keep the focused window in view, centering it where possible.
+    Focus,
+}
+
+#[derive(knuffel::Decode, Debug, Clone, Copy, PartialEq)]
+pub struct ZoomPart {
+    #[knuffel(child, unwrap(argument))]
+    pub factor: Option<FloatOrInt<1, 32>>,
+    #[knuffel(child, unwrap(argument))]
+    pub step: Option<FloatOrInt<0, 32>>,
+    #[knuffel(child, unwrap(argument))]
+    pub follow: Option<ZoomFollow>,
+    #[knuffel(child, unwrap(argument))]
+    pub smoothing_ms: Option<u16>,
+}
+
+impl MergeWith<ZoomPart> for Zoom {
+    fn merge_with(&mut self, part: &ZoomPart) {
+        merge!((self, part), factor, step);
+        merge_clone!((self, part), follow, smoothing_ms);
+    }
+}
+
 #[derive(knuffel::Decode, Debug, Default, Clone, PartialEq, Eq)]
 pub struct Environment(#[knuffel(children)] pub Vec<EnvironmentVariable>);
 
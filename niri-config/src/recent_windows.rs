@@ -148,8 +148,11 @@ impl From<MruBind> for Bind {
     fn from(x: MruBind) -> Self {
         Self {
             key: x.key,
+            sequence: Vec::new(),
             action: Action::from(x.action),
             repeat: true,
+            on_release: false,
+            tap: false,
             cooldown: None,
             allow_when_locked: false,
             allow_inhibiting: x.allow_inhibiting,
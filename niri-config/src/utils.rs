@@ -4,7 +4,9 @@ use knuffel::errors::DecodeError;
 use miette::miette;
 use regex::Regex;
 
+mod evdev_keys;
 mod merge_with;
+pub(crate) use evdev_keys::EVDEV_KEYS;
 pub use merge_with::*;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -35,6 +35,12 @@ pub struct WindowRule {
     #[knuffel(child, unwrap(argument))]
     pub open_focused: Option<bool>,
     #[knuffel(child, unwrap(argument))]
+    pub open_in_scratchpad: Option<bool>,
+    #[knuffel(child, unwrap(argument))]
+    pub open_sticky: Option<bool>,
+    #[knuffel(child)]
+    pub swallow_parent: Option<SwallowParent>,
+    #[knuffel(child, unwrap(argument))]
     pub on_xdg_activate: Option<OnXdgActivate>,
 
     // Rules applied dynamically.
@@ -141,6 +147,15 @@ pub struct Match {
     pub at_startup: Option<bool>,
 }
 
+/// Which parent windows a new window can swallow.
+///
+/// Without any matchers, the window swallows any window of a parent process.
+#[derive(knuffel::Decode, Debug, Default, Clone, PartialEq)]
+pub struct SwallowParent {
+    #[knuffel(children(name = "match"))]
+    pub matches: Vec<Match>,
+}
+
 #[derive(knuffel::Decode, Debug, Clone, Copy, PartialEq)]
 pub struct FloatingPosition {
     #[knuffel(property)]
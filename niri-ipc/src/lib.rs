@@ -87,6 +87,11 @@ pub enum Request {
     PickColor,
     /// Perform an action.
     Action(Action),
+    /// Perform several actions in order.
+    ///
+    /// The actions run back to back before niri draws the next frame, so only their end result
+    /// shows up on screen.
+    Actions(Vec<Action>),
     /// Change output configuration temporarily.
     ///
     /// The configuration is changed temporarily and not saved into the config file. If the output
@@ -117,6 +122,8 @@ pub enum Request {
     ReturnError,
     /// Request information about the overview.
     OverviewState,
+    /// Request information about mouse keys.
+    MouseKeysState,
     /// Request information about screencasts.
     Casts,
 }
@@ -163,6 +170,8 @@ pub enum Response {
     OutputConfigChanged(OutputConfigChanged),
     /// Information about the overview.
     OverviewState(Overview),
+    /// Information about mouse keys.
+    MouseKeysState(MouseKeys),
     /// Information about screencasts.
     Casts(Vec<Cast>),
 }
@@ -175,6 +184,14 @@ pub struct Overview {
     pub is_open: bool,
 }
 
+/// Mouse keys information.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct MouseKeys {
+    /// Whether mouse keys are currently on.
+    pub is_on: bool,
+}
+
 /// Color picked from the screen.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
@@ -285,6 +302,20 @@ pub enum Action {
     },
     /// Enable or disable the keyboard shortcuts inhibitor (if any) for the focused surface.
     ToggleKeyboardShortcutsInhibit {},
+    /// Toggle sticky keys, which latch modifiers pressed on their own.
+    ToggleStickyKeys {},
+    /// Toggle slow keys, which accept key presses only after holding the key for a while.
+    ToggleSlowKeys {},
+    /// Toggle bounce keys, which ignore quickly repeated presses of the same key.
+    ToggleBounceKeys {},
+    /// Toggle mouse keys, which move the pointer and click with the numeric keypad.
+    ToggleMouseKeys {},
+    /// Zoom in the accessibility magnifier, turning it on if needed.
+    ZoomIn {},
+    /// Zoom out the accessibility magnifier, turning it off at the normal size.
+    ZoomOut {},
+    /// Toggle the accessibility magnifier.
+    ToggleZoom {},
     /// Close a window.
     #[cfg_attr(feature = "clap", clap(about = "Close the focused window"))]
     CloseWindow {
@@ -454,6 +485,12 @@ pub enum Action {
         #[cfg_attr(feature = "clap", arg())]
         display: ColumnDisplay,
     },
+    /// Set the layout mode of the focused workspace.
+    SetLayoutMode {
+        /// Layout mode to set.
+        #[cfg_attr(feature = "clap", arg())]
+        mode: LayoutMode,
+    },
     /// Center the focused column on the screen.
     CenterColumn {},
     /// Center a window on the screen.
@@ -831,6 +868,95 @@ pub enum Action {
         #[cfg_attr(feature = "clap", arg(long))]
         id: Option<u64>,
     },
+    /// Toggle whether the focused window is sticky, following the active workspace.
+    ///
+    /// Making a window sticky moves it to the floating layout.
+    ToggleWindowSticky {
+        /// Id of the window to toggle.
+        ///
+        /// If `None`, uses the focused window.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: Option<u64>,
+    },
+    /// Hide the focused scratchpad window, or show a window from the scratchpad.
+    ToggleScratchpad {},
+    /// Hide a window in the scratchpad.
+    MoveWindowToScratchpad {
+        /// Id of the window to hide.
+        ///
+        /// If `None`, uses the focused window.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: Option<u64>,
+    },
+    /// Minimize a window.
+    MinimizeWindow {
+        /// Id of the window to minimize.
+        ///
+        /// If `None`, uses the focused window.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: Option<u64>,
+    },
+    /// Restore a minimized window.
+    RestoreMinimizedWindow {
+        /// Id of the window to restore.
+        ///
+        /// If `None`, restores the most recently minimized window on the active workspace.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: Option<u64>,
+    },
+    /// Undo the last change to the window layout.
+    ///
+    /// Covers moving windows between columns, workspaces and monitors, and changing column
+    /// widths.
+    UndoLayoutChange {},
+    /// Redo the last undone change to the window layout.
+    RedoLayoutChange {},
+    /// Set a mark on a window.
+    ///
+    /// A mark is set on at most one window at a time, so it moves from any window that had it.
+    SetWindowMark {
+        /// Mark to set.
+        #[cfg_attr(feature = "clap", arg())]
+        mark: String,
+
+        /// Id of the window to mark.
+        ///
+        /// If `None`, uses the focused window.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: Option<u64>,
+    },
+    /// Focus the window with a mark.
+    FocusWindowByMark {
+        /// Mark of the window to focus.
+        #[cfg_attr(feature = "clap", arg())]
+        mark: String,
+    },
+    /// Swap the focused window with the window with a mark.
+    SwapWithMark {
+        /// Mark of the window to swap with.
+        #[cfg_attr(feature = "clap", arg())]
+        mark: String,
+    },
+    /// Move and resize a floating window to fill a part of the working area.
+    SnapFloatingWindow {
+        /// Zone to snap the window to.
+        #[cfg_attr(feature = "clap", arg())]
+        zone: SnapZone,
+
+        /// Id of the window to snap.
+        ///
+        /// If `None`, uses the focused window.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: Option<u64>,
+    },
+    /// Switch to a bind mode, replacing the regular binds with the binds of the mode.
+    EnterBindMode {
+        /// Name of the bind mode.
+        #[cfg_attr(feature = "clap", arg())]
+        name: String,
+    },
+    /// Leave the active bind mode and go back to the regular binds.
+    ExitBindMode {},
     /// Switches focus to the floating layout.
     FocusFloating {},
     /// Switches focus to the tiling layout.
@@ -1007,6 +1133,40 @@ pub enum ColumnDisplay {
     Tabbed,
 }
 
+/// How windows are arranged in the tiling layout of a workspace.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub enum LayoutMode {
+    /// Windows are arranged in columns on an infinite horizontal strip.
+    Scrolling,
+    /// One master window on the left, the rest stacked vertically on the right.
+    MasterStack,
+    /// Every new window splits the area of the previous one in half, alternating directions.
+    Dwindle,
+}
+
+/// Part of the working area that a floating window can snap to.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub enum SnapZone {
+    /// Left half.
+    Left,
+    /// Right half.
+    Right,
+    /// Top half.
+    Top,
+    /// Bottom half.
+    Bottom,
+    /// Top left quarter.
+    TopLeft,
+    /// Top right quarter.
+    TopRight,
+    /// Bottom left quarter.
+    BottomLeft,
+    /// Bottom right quarter.
+    BottomRight,
+}
+
 /// Output actions that niri can perform.
 // Variants in this enum should match the spelling of the ones in niri-config. Most thigs from
 // niri-config should be present here.
@@ -1356,8 +1516,18 @@ pub struct Window {
     ///
     /// If the window isn't floating then it is in the tiling layout.
     pub is_floating: bool,
+    /// Whether this window is sticky.
+    ///
+    /// Sticky windows are floating and follow the active workspace of their monitor.
+    pub is_sticky: bool,
+    /// Whether this window is minimized.
+    ///
+    /// Minimized windows are hidden from their workspace until restored.
+    pub is_minimized: bool,
     /// Whether this window requests your attention.
     pub is_urgent: bool,
+    /// Mark set on this window, if any.
+    pub mark: Option<String>,
     /// Position- and size-related properties of the window.
     pub layout: WindowLayout,
     /// Timestamp when the window was most recently focused.
@@ -1404,6 +1574,9 @@ pub struct WindowLayout {
     /// The indices are 1-based, i.e. the leftmost column is at index 1 and the topmost tile in a
     /// column is at index 1. This is consistent with [`Action::FocusColumn`] and
     /// [`Action::FocusWindowInColumn`].
+    ///
+    /// With the vertical layout orientation, columns are stacked top-to-bottom, so the column
+    /// index counts from the topmost column, and the tile index counts from the leftmost tile.
     pub pos_in_scrolling_layout: Option<(usize, usize)>,
     /// Size of the tile this window is in, including decorations like borders.
     pub tile_size: (f64, f64),
@@ -1485,6 +1658,10 @@ pub struct KeyboardLayouts {
     pub names: Vec<String>,
     /// Index of the currently active layout in `names`.
     pub current_idx: u8,
+    /// Name of the keyboard device whose own layouts these are.
+    ///
+    /// `None` when these are the layouts shared by all keyboards without their own xkb settings.
+    pub device: Option<String>,
 }
 
 /// A layer-shell layer.
@@ -1703,6 +1880,21 @@ pub enum Event {
         /// The new state of the overview.
         is_open: bool,
     },
+    /// Mouse keys were turned on or off.
+    MouseKeysToggled {
+        /// The new state of mouse keys.
+        is_on: bool,
+    },
+    /// The active bind mode changed.
+    BindModeChanged {
+        /// Name of the new bind mode, or `None` if the regular binds are active.
+        name: Option<String>,
+    },
+    /// The app binds active for the focused window changed.
+    AppBindsChanged {
+        /// Name of the new app binds, or `None` if no app binds match the focused window.
+        name: Option<String>,
+    },
     /// The configuration was reloaded.
     ///
     /// You will always receive this event when connecting to the event stream, indicating the last
@@ -1881,6 +2073,40 @@ impl FromStr for ColumnDisplay {
     }
 }
 
+impl FromStr for LayoutMode {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "scrolling" => Ok(Self::Scrolling),
+            "master-stack" => Ok(Self::MasterStack),
+            "dwindle" => Ok(Self::Dwindle),
+            _ => Err(r#"invalid layout mode, can be "scrolling", "master-stack" or "dwindle""#),
+        }
+    }
+}
+
+impl FromStr for SnapZone {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "left" => Ok(Self::Left),
+            "right" => Ok(Self::Right),
+            "top" => Ok(Self::Top),
+            "bottom" => Ok(Self::Bottom),
+            "top-left" => Ok(Self::TopLeft),
+            "top-right" => Ok(Self::TopRight),
+            "bottom-left" => Ok(Self::BottomLeft),
+            "bottom-right" => Ok(Self::BottomRight),
+            _ => Err(concat!(
+                r#"invalid snap zone, can be "left", "right", "top", "bottom", "top-left", "#,
+                r#""top-right", "bottom-left" or "bottom-right""#
+            )),
+        }
+    }
+}
+
 impl FromStr for Transform {
     type Err = &'static str;
 
@@ -44,6 +44,15 @@ pub struct EventStreamState {
     /// State of the overview.
     pub overview: OverviewState,
 
+    /// State of mouse keys.
+    pub mouse_keys: MouseKeysState,
+
+    /// State of the bind mode.
+    pub bind_mode: BindModeState,
+
+    /// State of the app binds.
+    pub app_binds: AppBindsState,
+
     /// State of the config.
     pub config: ConfigState,
 
@@ -79,6 +88,27 @@ pub struct OverviewState {
     pub is_open: bool,
 }
 
+/// The mouse keys state communicated over the event stream.
+#[derive(Debug, Default)]
+pub struct MouseKeysState {
+    /// Whether mouse keys are currently on.
+    pub is_on: bool,
+}
+
+/// The bind mode state communicated over the event stream.
+#[derive(Debug, Default)]
+pub struct BindModeState {
+    /// Name of the active bind mode, or `None` if the regular binds are active.
+    pub name: Option<String>,
+}
+
+/// The app binds state communicated over the event stream.
+#[derive(Debug, Default)]
+pub struct AppBindsState {
+    /// Name of the app binds active for the focused window, if any.
+    pub name: Option<String>,
+}
+
 /// The config state communicated over the event stream.
 #[derive(Debug, Default)]
 pub struct ConfigState {
@@ -100,6 +130,9 @@ impl EventStreamStatePart for EventStreamState {
         events.extend(self.windows.replicate());
         events.extend(self.keyboard_layouts.replicate());
         events.extend(self.overview.replicate());
+        events.extend(self.mouse_keys.replicate());
+        events.extend(self.bind_mode.replicate());
+        events.extend(self.app_binds.replicate());
         events.extend(self.config.replicate());
         events.extend(self.casts.replicate());
         events
@@ -110,6 +143,9 @@ impl EventStreamStatePart for EventStreamState {
         let event = self.windows.apply(event)?;
         let event = self.keyboard_layouts.apply(event)?;
         let event = self.overview.apply(event)?;
+        let event = self.mouse_keys.apply(event)?;
+        let event = self.bind_mode.apply(event)?;
+        let event = self.app_binds.apply(event)?;
         let event = self.config.apply(event)?;
         let event = self.casts.apply(event)?;
         Some(event)
@@ -280,6 +316,58 @@ impl EventStreamStatePart for OverviewState {
     }
 }
 
+impl EventStreamStatePart for MouseKeysState {
+    fn replicate(&self) -> Vec<Event> {
+        vec![Event::MouseKeysToggled { is_on: self.is_on }]
+    }
+
+    fn apply(&mut self, event: Event) -> Option<Event> {
+        match event {
+            Event::MouseKeysToggled { is_on } => {
+                self.is_on = is_on;
+            }
+            event => return Some(event),
+        }
+        None
+    }
+}
+
+impl EventStreamStatePart for BindModeState {
+    fn replicate(&self) -> Vec<Event> {
+        vec![Event::BindModeChanged {
+            name: self.name.clone(),
+        }]
+    }
+
+    fn apply(&mut self, event: Event) -> Option<Event> {
+        match event {
+            Event::BindModeChanged { name } => {
+                self.name = name;
+            }
+            event => return Some(event),
+        }
+        None
+    }
+}
+
+impl EventStreamStatePart for AppBindsState {
+    fn replicate(&self) -> Vec<Event> {
+        vec![Event::AppBindsChanged {
+            name: self.name.clone(),
+        }]
+    }
+
+    fn apply(&mut self, event: Event) -> Option<Event> {
+        match event {
+            Event::AppBindsChanged { name } => {
+                self.name = name;
+            }
+            event => return Some(event),
+        }
+        None
+    }
+}
+
 impl EventStreamStatePart for ConfigState {
     fn replicate(&self) -> Vec<Event> {
         vec![Event::ConfigLoaded {
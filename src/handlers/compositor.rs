@@ -148,6 +148,8 @@ impl CompositorHandler for State {
                     // before mapping, so we need to compute open_floating at the last possible
                     // moment, that is here.
                     let is_floating = rules.compute_open_floating(toplevel);
+                    let open_in_scratchpad = rules.open_in_scratchpad == Some(true);
+                    let swallow_rule = rules.swallow_parent.clone();
 
                     // Figure out if we should activate the window.
                     let activate = rules.open_focused.map(|focus| {
@@ -189,6 +191,10 @@ impl CompositorHandler for State {
                                 || output.is_none()
                                 || output.as_ref() == *parent_output
                         })
+                        // Windows hidden in the scratchpad have nowhere to open next to.
+                        .filter(|(mapped, _)| {
+                            !self.niri.layout.scratchpad_has_window(&mapped.window)
+                        })
                         .map(|(mapped, _)| mapped.window.clone());
 
                     // The mapped pre-commit hook deals with dma-bufs on its own.
@@ -198,8 +204,22 @@ impl CompositorHandler for State {
                         let config = self.niri.config.borrow();
                         Mapped::new(window, rules, hook, &config)
                     };
+
+                    // Windows opening in the scratchpad stay hidden until summoned.
+                    if open_in_scratchpad {
+                        self.niri.layout.add_window_to_scratchpad(mapped);
+                        return;
+                    }
+
                     let window = mapped.window.clone();
 
+                    // Windows started from a terminal can take over its tile. Dialogs and floating
+                    // windows open as usual.
+                    let swallow_parent = swallow_rule
+                        .filter(|_| parent.is_none() && !is_floating)
+                        .zip(mapped.credentials().map(|creds| creds.pid))
+                        .and_then(|(rule, pid)| self.niri.find_swallow_parent(&rule, pid));
+
                     let target = if let Some(p) = &parent {
                         // Open dialogs next to their parent window.
                         AddWindowTarget::NextTo(p)
@@ -210,15 +230,44 @@ impl CompositorHandler for State {
                     } else {
                         AddWindowTarget::Auto
                     };
-                    let output = self.niri.layout.add_window(
-                        mapped,
-                        target,
-                        width,
-                        height,
-                        is_full_width,
-                        is_floating,
-                        activate,
-                    );
+
+                    // Put windows without a specific target back where they were before a
+                    // restart.
+                    let restore_slot =
+                        if swallow_parent.is_none() && matches!(target, AddWindowTarget::Auto) {
+                            let hints = mapped.window_hints();
+                            self.niri.layout.find_restore_slot(&hints)
+                        } else {
+                            None
+                        };
+
+                    // If the swallow fails, the window comes back to be added as usual.
+                    let swallowed = if let Some(swallow_parent) = &swallow_parent {
+                        self.niri
+                            .layout
+                            .swallow_window(swallow_parent, mapped, activate)
+                    } else {
+                        Err(mapped)
+                    };
+
+                    let output = match swallowed {
+                        Ok(output) => output,
+                        Err(mapped) => {
+                            if let Some(slot) = restore_slot {
+                                self.niri.layout.add_window_restored(mapped, slot, activate)
+                            } else {
+                                self.niri.layout.add_window(
+                                    mapped,
+                                    target,
+                                    width,
+                                    height,
+                                    is_full_width,
+                                    is_floating,
+                                    activate,
+                                )
+                            }
+                        }
+                    };
                     let output = output.cloned();
 
                     // The window state cannot contain Fullscreen and Maximized at once. Therefore,
@@ -294,6 +343,7 @@ impl CompositorHandler for State {
                         .stop_casts_for_target(CastTarget::Window { id: id.get() });
 
                     self.niri.window_mru_ui.remove_window(id);
+                    self.niri.layout.unswallow_window(&window);
                     self.niri.layout.remove_window(&window, transaction.clone());
                     self.add_default_dmabuf_pre_commit_hook(surface);
 
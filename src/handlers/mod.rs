@@ -593,6 +593,22 @@ impl ForeignToplevelHandler for State {
             self.niri.layout.set_maximized(&window, false);
         }
     }
+
+    fn set_minimized(&mut self, wl_surface: WlSurface) {
+        if let Some((mapped, _)) = self.niri.layout.find_window_and_output(&wl_surface) {
+            let window = mapped.window.clone();
+            self.niri.layout.minimize_window(Some(&window));
+            self.niri.queue_redraw_all();
+        }
+    }
+
+    fn unset_minimized(&mut self, wl_surface: WlSurface) {
+        if let Some((mapped, _)) = self.niri.layout.find_window_and_output(&wl_surface) {
+            let window = mapped.window.clone();
+            self.niri.layout.restore_minimized_window(Some(&window));
+            self.niri.queue_redraw_all();
+        }
+    }
 }
 
 impl ExtWorkspaceHandler for State {
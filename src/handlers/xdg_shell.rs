@@ -665,6 +665,21 @@ impl XdgShellHandler for State {
         }
     }
 
+    fn minimize_request(&mut self, toplevel: ToplevelSurface) {
+        let Some((mapped, _)) = self
+            .niri
+            .layout
+            .find_window_and_output(toplevel.wl_surface())
+        else {
+            // Unmapped windows have nothing to minimize yet.
+            return;
+        };
+
+        let window = mapped.window.clone();
+        self.niri.layout.minimize_window(Some(&window));
+        self.niri.queue_redraw_all();
+    }
+
     fn fullscreen_request(
         &mut self,
         toplevel: ToplevelSurface,
@@ -918,6 +933,7 @@ impl XdgShellHandler for State {
         let was_active = active_window == Some(&window);
 
         self.niri.window_mru_ui.remove_window(id);
+        self.niri.layout.unswallow_window(&window);
         self.niri.layout.remove_window(&window, transaction.clone());
 
         let surface = surface.wl_surface();
@@ -3,6 +3,7 @@ use smithay::backend::input;
 use smithay::backend::winit::WinitVirtualDevice;
 use smithay::output::Output;
 
+use crate::input::mouse_keys::MouseKeysDevice;
 use crate::niri::State;
 use crate::protocols::virtual_pointer::VirtualPointer;
 
@@ -49,3 +50,9 @@ impl NiriInputDevice for VirtualPointer {
         self.output().cloned()
     }
 }
+
+impl NiriInputDevice for MouseKeysDevice {
+    fn output(&self, _: &State) -> Option<Output> {
+        None
+    }
+}
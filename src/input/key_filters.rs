@@ -0,0 +1,216 @@
+use std::collections::{HashMap, HashSet};
+
+use niri_config::input::Keyboard;
+use smithay::backend::input::{KeyState, Keycode};
+use smithay::reexports::calloop::RegistrationToken;
+
+/// Accessibility keyboard filters: sticky keys, slow keys, and bounce keys.
+///
+/// They act on keys before they reach the binds or the clients.
+#[derive(Debug, Default)]
+pub struct KeyFilters {
+    pub sticky_keys: bool,
+    pub slow_keys: bool,
+    pub bounce_keys: bool,
+    /// Time of the last release of each key, for bounce keys.
+    last_release: HashMap<Keycode, u32>,
+    /// Keys whose press bounce keys dropped, so that their release is dropped too.
+    bounced: HashSet<Keycode>,
+    /// Key presses waiting for slow keys to accept them.
+    pub slow_pending: HashMap<Keycode, RegistrationToken>,
+    /// Modifiers physically held down.
+    held_mods: HashMap<Keycode, ModPress>,
+    /// Modifiers that sticky keys keeps pressed after their physical release.
+    sticky_mods: HashMap<Keycode, StickyMod>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ModPress {
+    /// Regular press, chorded once another key was pressed while holding it.
+    Normal { chorded: bool },
+    /// Press of a latched modifier, which locks it.
+    Locking,
+    /// Press of a locked modifier, which unlocks it on release.
+    Unlocking,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StickyMod {
+    /// Released with the next key.
+    Latched,
+    /// Kept until pressed again.
+    Locked,
+}
+
+impl KeyFilters {
+    pub fn new(config: &Keyboard) -> Self {
+        Self {
+            sticky_keys: config.sticky_keys,
+            slow_keys: config.slow_keys.on,
+            bounce_keys: config.bounce_keys.on,
+            ..Self::default()
+        }
+    }
+
+    /// Returns whether the key event gets past bounce keys.
+    pub fn bounce(&mut self, key: Keycode, pressed: bool, time: u32, delay_ms: u16) -> bool {
+        if !pressed {
+            self.last_release.insert(key, time);
+            return !self.bounced.remove(&key);
+        }
+
+        let bounced = self.bounce_keys
+            && self
+                .last_release
+                .get(&key)
+                .is_some_and(|last| time.wrapping_sub(*last) < u32::from(delay_ms));
+        if bounced {
+            self.bounced.insert(key);
+        }
+
+        !bounced
+    }
+
+    /// Returns the key events to deliver in place of this one with sticky keys.
+    ///
+    /// `is_modifier` is only checked for key presses.
+    pub fn sticky(
+        &mut self,
+        key: Keycode,
+        pressed: bool,
+        is_modifier: bool,
+    ) -> Vec<(Keycode, KeyState)> {
+        let mut events = Vec::new();
+
+        if pressed {
+            if !is_modifier {
+                for press in self.held_mods.values_mut() {
+                    if let ModPress::Normal { chorded } = press {
+                        *chorded = true;
+                    }
+                }
+
+                events.push((key, KeyState::Pressed));
+                return events;
+            }
+
+            let press = match self.sticky_mods.get(&key) {
+                Some(StickyMod::Latched) => {
+                    self.sticky_mods.insert(key, StickyMod::Locked);
+                    ModPress::Locking
+                }
+                Some(StickyMod::Locked) => {
+                    self.sticky_mods.remove(&key);
+                    ModPress::Unlocking
+                }
+                None => {
+                    events.push((key, KeyState::Pressed));
+                    ModPress::Normal { chorded: false }
+                }
+            };
+            self.held_mods.insert(key, press);
+
+            return events;
+        }
+
+        match self.held_mods.remove(&key) {
+            // A modifier tapped on its own latches.
+            Some(ModPress::Normal { chorded: false }) if self.sticky_keys => {
+                self.sticky_mods.insert(key, StickyMod::Latched);
+            }
+            Some(ModPress::Locking) => (),
+            Some(ModPress::Normal { .. } | ModPress::Unlocking) => {
+                events.push((key, KeyState::Released));
+            }
+            None => {
+                events.push((key, KeyState::Released));
+
+                // Latched modifiers apply to the next key only.
+                self.sticky_mods.retain(|mod_key, sticky| {
+                    if *sticky == StickyMod::Latched {
+                        events.push((*mod_key, KeyState::Released));
+                        false
+                    } else {
+                        true
+                    }
+                });
+            }
+        }
+
+        events
+    }
+
+    /// Turns sticky keys on or off, returning the modifiers to release.
+    pub fn set_sticky_keys(&mut self, on: bool) -> Vec<Keycode> {
+        self.sticky_keys = on;
+        if on {
+            return Vec::new();
+        }
+
+        // Modifiers still held down get released normally.
+        for press in self.held_mods.values_mut() {
+            *press = ModPress::Normal { chorded: true };
+        }
+
+        let held_mods = &self.held_mods;
+        self.sticky_mods
+            .drain()
+            .map(|(key, _)| key)
+            .filter(|key| !held_mods.contains_key(key))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sticky_and_bounce_keys() {
+        let shift = Keycode::from(42 + 8);
+        let a = Keycode::from(30 + 8);
+        let pressed = KeyState::Pressed;
+        let released = KeyState::Released;
+
+        let mut filters = KeyFilters::default();
+        filters.sticky_keys = true;
+        filters.bounce_keys = true;
+
+        // Tapping a modifier latches it until the next key is released.
+        assert_eq!(filters.sticky(shift, true, true), [(shift, pressed)]);
+        assert!(filters.sticky(shift, false, true).is_empty());
+        assert_eq!(filters.sticky(a, true, false), [(a, pressed)]);
+        assert_eq!(
+            filters.sticky(a, false, false),
+            [(a, released), (shift, released)]
+        );
+
+        // Tapping it twice locks it until it is tapped again.
+        assert_eq!(filters.sticky(shift, true, true), [(shift, pressed)]);
+        assert!(filters.sticky(shift, false, true).is_empty());
+        assert!(filters.sticky(shift, true, true).is_empty());
+        assert!(filters.sticky(shift, false, true).is_empty());
+        assert_eq!(filters.sticky(a, true, false), [(a, pressed)]);
+        assert_eq!(filters.sticky(a, false, false), [(a, released)]);
+        assert!(filters.sticky(shift, true, true).is_empty());
+        assert_eq!(filters.sticky(shift, false, true), [(shift, released)]);
+
+        // Modifiers held in a key combination work as usual.
+        assert_eq!(filters.sticky(shift, true, true), [(shift, pressed)]);
+        assert_eq!(filters.sticky(a, true, false), [(a, pressed)]);
+        assert_eq!(filters.sticky(a, false, false), [(a, released)]);
+        assert_eq!(filters.sticky(shift, false, true), [(shift, released)]);
+
+        // Turning sticky keys off releases the latched modifiers.
+        filters.sticky(shift, true, true);
+        filters.sticky(shift, false, true);
+        assert_eq!(filters.set_sticky_keys(false), [shift]);
+
+        // Bounce keys drops presses right after a release, along with their release.
+        assert!(filters.bounce(a, true, 1000, 300));
+        assert!(filters.bounce(a, false, 1050, 300));
+        assert!(!filters.bounce(a, true, 1100, 300));
+        assert!(!filters.bounce(a, false, 1150, 300));
+        assert!(filters.bounce(a, true, 1500, 300));
+    }
+}
@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use niri_config::input::{EvdevKey, RemapTarget};
+use smithay::reexports::calloop::RegistrationToken;
+
+/// Rewrites physical key events according to the configured remaps.
+///
+/// Keys remember what they were remapped to when pressed, so that their release matches even if
+/// the config changes in between.
+#[derive(Debug, Default)]
+pub struct KeyRemapper {
+    /// Physical keys currently held down, in the order they were pressed.
+    pressed: Vec<(EvdevKey, PressedKey)>,
+    /// Timers turning dual-role keys into their hold keys once the tapping term runs out.
+    pub hold_timers: HashMap<EvdevKey, RegistrationToken>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum PressedKey {
+    Key(EvdevKey),
+    DualRole {
+        tap: EvdevKey,
+        hold: EvdevKey,
+        tapping_term_ms: u16,
+        /// Whether this key acts as `hold`, because another key was pressed meanwhile or it was
+        /// held for longer than the tapping term.
+        holding: bool,
+    },
+}
+
+impl KeyRemapper {
+    /// Returns the key events to process in place of a physical key event.
+    ///
+    /// `target` looks up the remap of a key, and is only called for key presses.
+    pub fn process(
+        &mut self,
+        key: EvdevKey,
+        pressed: bool,
+        target: impl FnOnce(EvdevKey) -> Option<RemapTarget>,
+    ) -> Vec<(EvdevKey, bool)> {
+        let mut events = Vec::new();
+
+        if !pressed {
+            let Some(idx) = self.pressed.iter().position(|(k, _)| *k == key) else {
+                // The key was pressed before we started tracking it.
+                events.push((key, false));
+                return events;
+            };
+
+            match self.pressed.remove(idx).1 {
+                PressedKey::Key(to) => events.push((to, false)),
+                PressedKey::DualRole {
+                    hold,
+                    holding: true,
+                    ..
+                } => events.push((hold, false)),
+                PressedKey::DualRole {
+                    tap,
+                    holding: false,
+                    ..
+                } => {
+                    events.push((tap, true));
+                    events.push((tap, false));
+                }
+            }
+
+            return events;
+        }
+
+        // Pressing another key turns pending dual-role keys into their hold keys.
+        events.extend(self.hold(None));
+
+        // Drop a stale press of the same key, if any, so that its state doesn't get stuck.
+        self.pressed.retain(|(k, _)| *k != key);
+
+        let pressed_key = match target(key) {
+            None => PressedKey::Key(key),
+            Some(RemapTarget::Key(to)) => PressedKey::Key(to),
+            Some(RemapTarget::DualRole {
+                tap,
+                hold,
+                tapping_term_ms,
+            }) => PressedKey::DualRole {
+                tap,
+                hold,
+                tapping_term_ms,
+                holding: false,
+            },
+        };
+
+        if let PressedKey::Key(to) = pressed_key {
+            events.push((to, true));
+        }
+
+        self.pressed.push((key, pressed_key));
+        events
+    }
+
+    /// Turns pending dual-role keys into their hold keys, returning the key events to process.
+    ///
+    /// With `key` set, only that physical key is affected.
+    pub fn hold(&mut self, key: Option<EvdevKey>) -> Vec<(EvdevKey, bool)> {
+        let mut events = Vec::new();
+        for (k, pressed_key) in &mut self.pressed {
+            if key.is_some_and(|key| key != *k) {
+                continue;
+            }
+
+            if let PressedKey::DualRole { hold, holding, .. } = pressed_key {
+                if !*holding {
+                    *holding = true;
+                    events.push((*hold, true));
+                }
+            }
+        }
+        events
+    }
+
+    /// Returns how long a pending dual-role key can be held before it acts as its hold key.
+    pub fn tapping_term(&self, key: EvdevKey) -> Option<Duration> {
+        self.pressed
+            .iter()
+            .find_map(|(k, pressed_key)| match pressed_key {
+                PressedKey::DualRole {
+                    tapping_term_ms,
+                    holding: false,
+                    ..
+                } if *k == key => Some(Duration::from_millis(u64::from(*tapping_term_ms))),
+                _ => None,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_remapping() {
+        let caps = EvdevKey(58);
+        let esc = EvdevKey(1);
+        let ctrl = EvdevKey(29);
+        let a = EvdevKey(30);
+        let b = EvdevKey(48);
+
+        let target = |key| match key {
+            EvdevKey(58) => Some(RemapTarget::DualRole {
+                tap: esc,
+                hold: ctrl,
+                tapping_term_ms: 200,
+            }),
+            EvdevKey(30) => Some(RemapTarget::Key(b)),
+            _ => None,
+        };
+
+        let mut remapper = KeyRemapper::default();
+
+        // One-to-one remap.
+        assert_eq!(remapper.process(a, true, target), [(b, true)]);
+        assert_eq!(remapper.process(a, false, target), [(b, false)]);
+
+        // Tapping the dual-role key alone gives the tap key.
+        assert!(remapper.process(caps, true, target).is_empty());
+        assert_eq!(
+            remapper.process(caps, false, target),
+            [(esc, true), (esc, false)]
+        );
+
+        // Holding it with another key gives the hold key.
+        assert!(remapper.process(caps, true, target).is_empty());
+        assert_eq!(remapper.process(b, true, target), [(ctrl, true), (b, true)]);
+        assert_eq!(remapper.process(b, false, target), [(b, false)]);
+        assert_eq!(remapper.process(caps, false, target), [(ctrl, false)]);
+
+        // Holding it past the tapping term, or clicking, gives the hold key too.
+        assert!(remapper.process(caps, true, target).is_empty());
+        assert_eq!(
+            remapper.tapping_term(caps),
+            Some(Duration::from_millis(200))
+        );
+        assert!(remapper.hold(Some(a)).is_empty());
+        assert_eq!(remapper.hold(Some(caps)), [(ctrl, true)]);
+        assert_eq!(remapper.tapping_term(caps), None);
+        assert!(remapper.hold(None).is_empty());
+        assert_eq!(remapper.process(caps, false, target), [(ctrl, false)]);
+
+        // Releases match the press even if the remap changed in between.
+        assert_eq!(remapper.process(a, true, target), [(b, true)]);
+        assert_eq!(remapper.process(a, false, |_| None), [(b, false)]);
+
+        // Keys pressed before tracking started are released as is.
+        assert_eq!(remapper.process(esc, false, target), [(esc, false)]);
+    }
+}
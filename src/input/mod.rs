@@ -1,10 +1,13 @@
 use std::any::Any;
 use std::collections::hash_map::Entry;
 use std::collections::HashSet;
+use std::mem;
 use std::time::Duration;
 
 use calloop::timer::{TimeoutAction, Timer};
 use input::event::gesture::GestureEventCoordinates as _;
+use niri_config::gestures::{GestureAction, GestureTrigger, PinchDirection, SwipeDirection};
+use niri_config::input::EvdevKey;
 use niri_config::{
     Action, Bind, Binds, Config, Key, ModKey, Modifiers, MruDirection, SwitchBinds, Trigger,
 };
@@ -38,6 +41,7 @@ use smithay::reexports::wayland_server::protocol::wl_surface::WlSurface;
 use smithay::utils::{Logical, Point, Rectangle, Transform, SERIAL_COUNTER};
 use smithay::wayland::keyboard_shortcuts_inhibit::KeyboardShortcutsInhibitor;
 use smithay::wayland::pointer_constraints::{with_pointer_constraint, PointerConstraint};
+use touch_edge_swipe_grab::{ScreenEdge, TouchEdgeSwipeGrab};
 use touch_overview_grab::TouchOverviewGrab;
 
 use self::move_grab::MoveGrab;
@@ -57,6 +61,9 @@ use crate::utils::{center, get_monotonic_time, CastSessionId, ResizeEdge};
 
 pub mod backend_ext;
 pub mod click_grab;
+pub mod key_filters;
+pub mod key_remap;
+pub mod mouse_keys;
 pub mod move_grab;
 pub mod pick_color_grab;
 pub mod pick_window_grab;
@@ -65,12 +72,38 @@ pub mod scroll_swipe_gesture;
 pub mod scroll_tracker;
 pub mod spatial_movement_grab;
 pub mod swipe_tracker;
+pub mod touch_edge_swipe_grab;
 pub mod touch_overview_grab;
 
 use backend_ext::{NiriInputBackend as InputBackend, NiriInputDevice as _};
 
 pub const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(400);
 
+/// Time to press the next key of a bind sequence before it is cancelled.
+const BIND_SEQUENCE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// How far a pinch has to scale before it triggers a gesture bind.
+const GESTURE_PINCH_THRESHOLD: f64 = 0.2;
+
+/// How often mouse keys move the pointer while a direction key is held.
+const MOUSE_KEYS_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Touchpad gesture handled by the gesture binds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GestureBindProgress {
+    /// Swipe that didn't move far enough yet to know its direction.
+    Swipe {
+        fingers: u32,
+        cumulative: (f64, f64),
+    },
+    /// Pinch that didn't scale far enough yet to know its direction.
+    Pinch { fingers: u32 },
+    /// Hold that triggers if the fingers lift without moving.
+    Hold { fingers: u32 },
+    /// Gesture that already triggered its bind, ignored until it ends.
+    Done,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct TabletData {
     pub aspect_ratio: f64,
@@ -394,7 +427,15 @@ impl State {
 
         pos.x = pos.x.clamp(0.0, target_geo.size.w - px);
         pos.y = pos.y.clamp(0.0, target_geo.size.h - px);
-        Some(pos + target_geo.loc)
+        let pos = pos + target_geo.loc;
+
+        // When mapped to a window, the tablet doesn't correspond to what's on screen, so there's
+        // no zoom to undo.
+        if window_target.is_some() {
+            Some(pos)
+        } else {
+            Some(self.niri.unzoom_position(pos))
+        }
     }
 
     fn is_inhibiting_shortcuts(&self) -> bool {
@@ -409,16 +450,262 @@ impl State {
             .is_some_and(KeyboardShortcutsInhibitor::is_active)
     }
 
-    fn on_keyboard<I: InputBackend>(
+    fn on_keyboard<I: InputBackend + 'static>(
         &mut self,
         event: I::KeyboardKeyEvent,
         consumed_by_a11y: &mut bool,
+    ) where
+        I::Device: 'static,
+    {
+        let time = Event::time_msec(&event);
+        let pressed = event.state() == KeyState::Pressed;
+
+        let device = event.device();
+        let device = (&device as &dyn Any).downcast_ref::<input::Device>();
+
+        // Switch to the keymap of the keyboard that's being typed on.
+        if pressed {
+            self.switch_device_xkb(device);
+        }
+
+        // Remapped keys go through the regular handling as if they had been typed.
+        let evdev_key = EvdevKey(event.key_code().raw() - 8);
+        let keys = {
+            let config = self.niri.config.borrow();
+            let input = &config.input;
+            self.niri.key_remapper.process(evdev_key, pressed, |key| {
+                device
+                    .and_then(|device| {
+                        input
+                            .devices_matching(
+                                device.name(),
+                                device.id_vendor(),
+                                device.id_product(),
+                            )
+                            .filter_map(|c| c.remap.as_ref()?.find(key))
+                            .last()
+                    })
+                    .or_else(|| input.remap.find(key))
+            })
+        };
+
+        let remapper = &mut self.niri.key_remapper;
+        if let Some(token) = remapper.hold_timers.remove(&evdev_key) {
+            self.niri.event_loop.remove(token);
+        }
+
+        // A dual-role key held for long enough acts as its hold key even on its own.
+        if let Some(tapping_term) = remapper.tapping_term(evdev_key) {
+            let token = self
+                .niri
+                .event_loop
+                .insert_source(Timer::from_duration(tapping_term), move |_, _, state| {
+                    state.niri.key_remapper.hold_timers.remove(&evdev_key);
+                    let keys = state.niri.key_remapper.hold(Some(evdev_key));
+                    let time = time.wrapping_add(tapping_term.as_millis() as u32);
+                    state.process_remapped_keys(keys, time, &mut false);
+                    TimeoutAction::Drop
+                })
+                .unwrap();
+            self.niri.key_remapper.hold_timers.insert(evdev_key, token);
+        }
+
+        self.process_remapped_keys(keys, time, consumed_by_a11y);
+    }
+
+    fn process_remapped_keys(
+        &mut self,
+        keys: Vec<(EvdevKey, bool)>,
+        time: u32,
+        consumed_by_a11y: &mut bool,
+    ) {
+        for (key, pressed) in keys {
+            let state = if pressed {
+                KeyState::Pressed
+            } else {
+                KeyState::Released
+            };
+            self.filter_key(Keycode::from(key.0 + 8), state, time, consumed_by_a11y);
+        }
+    }
+
+    /// Turns dual-role keys held down into their hold keys, like when clicking with Ctrl held.
+    fn hold_dual_role_keys(&mut self, time: u32) {
+        let timers = mem::take(&mut self.niri.key_remapper.hold_timers);
+        for token in timers.into_values() {
+            self.niri.event_loop.remove(token);
+        }
+
+        let keys = self.niri.key_remapper.hold(None);
+        self.process_remapped_keys(keys, time, &mut false);
+    }
+
+    /// Runs the key through the accessibility key filters before handling it.
+    fn filter_key(
+        &mut self,
+        key_code: Keycode,
+        state: KeyState,
+        time: u32,
+        consumed_by_a11y: &mut bool,
+    ) {
+        let pressed = state == KeyState::Pressed;
+        let (bounce_delay, slow_delay) = {
+            let config = self.niri.config.borrow();
+            let keyboard = &config.input.keyboard;
+            (keyboard.bounce_keys.delay_ms, keyboard.slow_keys.delay_ms)
+        };
+
+        let filters = &mut self.niri.key_filters;
+        if !filters.bounce(key_code, pressed, time, bounce_delay) {
+            return;
+        }
+
+        if pressed && filters.slow_keys {
+            // Accept the press only once the key has been held down long enough.
+            let timer = Timer::from_duration(Duration::from_millis(u64::from(slow_delay)));
+            let token = self
+                .niri
+                .event_loop
+                .insert_source(timer, move |_, _, state| {
+                    state.niri.key_filters.slow_pending.remove(&key_code);
+                    let time = time.wrapping_add(u32::from(slow_delay));
+                    state.on_sticky_key(key_code, KeyState::Pressed, time, &mut false);
+                    TimeoutAction::Drop
+                })
+                .unwrap();
+            if let Some(token) = self.niri.key_filters.slow_pending.insert(key_code, token) {
+                self.niri.event_loop.remove(token);
+            }
+            return;
+        }
+
+        if !pressed {
+            // A key released before slow keys accepted it never gets pressed.
+            if let Some(token) = self.niri.key_filters.slow_pending.remove(&key_code) {
+                self.niri.event_loop.remove(token);
+                return;
+            }
+        }
+
+        self.on_sticky_key(key_code, state, time, consumed_by_a11y);
+    }
+
+    fn on_sticky_key(
+        &mut self,
+        key_code: Keycode,
+        state: KeyState,
+        time: u32,
+        consumed_by_a11y: &mut bool,
+    ) {
+        let pressed = state == KeyState::Pressed;
+        let is_modifier =
+            pressed && self.niri.key_filters.sticky_keys && self.is_modifier_key(key_code);
+        let keys = self.niri.key_filters.sticky(key_code, pressed, is_modifier);
+        for (key_code, state) in keys {
+            if self.on_mouse_key(key_code, state) {
+                continue;
+            }
+
+            self.on_key(key_code, state, time, consumed_by_a11y);
+        }
+    }
+
+    /// Handles the key with mouse keys, returning whether mouse keys took it over.
+    fn on_mouse_key(&mut self, key_code: Keycode, state: KeyState) -> bool {
+        // Use the same clock as the motion timer.
+        let time = get_monotonic_time().as_millis() as u32;
+        let events = {
+            let config = self.niri.config.borrow();
+            let pressed = state == KeyState::Pressed;
+            let mouse_keys = &mut self.niri.mouse_keys;
+            mouse_keys.process(&config.input.mouse_keys, key_code, pressed, time)
+        };
+        let Some(events) = events else {
+            return false;
+        };
+
+        for event in events {
+            self.process_input_event(event.into_input_event(time));
+        }
+
+        if self.niri.mouse_keys.is_moving() && self.niri.mouse_keys.timer.is_none() {
+            let timer = Timer::from_duration(MOUSE_KEYS_INTERVAL);
+            let token = self
+                .niri
+                .event_loop
+                .insert_source(timer, |_, _, state| {
+                    let time = get_monotonic_time().as_millis() as u32;
+                    let event = {
+                        let config = state.niri.config.borrow();
+                        let mouse_keys = &mut state.niri.mouse_keys;
+                        mouse_keys.motion(&config.input.mouse_keys, time)
+                    };
+                    if let Some(event) = event {
+                        state.process_input_event(event.into_input_event(time));
+                    }
+
+                    if !state.niri.mouse_keys.is_moving() {
+                        state.niri.mouse_keys.timer = None;
+                        return TimeoutAction::Drop;
+                    }
+
+                    TimeoutAction::ToDuration(MOUSE_KEYS_INTERVAL)
+                })
+                .unwrap();
+            self.niri.mouse_keys.timer = Some(token);
+        }
+
+        true
+    }
+
+    pub fn set_mouse_keys(&mut self, on: bool) {
+        let time = get_monotonic_time().as_millis() as u32;
+        for event in self.niri.mouse_keys.set_on(on) {
+            self.process_input_event(event.into_input_event(time));
+        }
+    }
+
+    fn is_modifier_key(&mut self, key_code: Keycode) -> bool {
+        let keyboard = self.niri.seat.get_keyboard().unwrap();
+        keyboard.with_xkb_state(self, |context| {
+            let xkb = context.xkb().lock().unwrap();
+            // SAFETY: we're not changing the ref count.
+            let state = unsafe { xkb.state() };
+            state.key_get_one_sym(key_code).is_modifier_key()
+        })
+    }
+
+    pub fn set_sticky_keys(&mut self, on: bool) {
+        let released = self.niri.key_filters.set_sticky_keys(on);
+        if released.is_empty() {
+            return;
+        }
+
+        let time = get_monotonic_time().as_millis() as u32;
+        for key_code in released {
+            self.on_key(key_code, KeyState::Released, time, &mut false);
+        }
+    }
+
+    fn announce_key_filter(&mut self, name: &str, on: bool) {
+        #[cfg(feature = "dbus")]
+        self.niri
+            .a11y_announce(format!("{name} {}", if on { "on" } else { "off" }));
+        #[cfg(not(feature = "dbus"))]
+        let _ = (name, on);
+    }
+
+    fn on_key(
+        &mut self,
+        key_code: Keycode,
+        state: KeyState,
+        time: u32,
+        consumed_by_a11y: &mut bool,
     ) {
         let mod_key = self.backend.mod_key(&self.niri.config.borrow());
 
         let serial = SERIAL_COUNTER.next_serial();
-        let time = Event::time_msec(&event);
-        let pressed = event.state() == KeyState::Pressed;
+        let pressed = state == KeyState::Pressed;
 
         // Stop bind key repeat on any release. This won't work 100% correctly in cases like:
         // 1. Press Mod
@@ -446,11 +733,8 @@ impl State {
         // other changes.
         #[cfg(feature = "dbus")]
         let block = {
-            let block = self.a11y_process_key(
-                Duration::from_millis(u64::from(time)),
-                event.key_code(),
-                event.state(),
-            );
+            let block =
+                self.a11y_process_key(Duration::from_millis(u64::from(time)), key_code, state);
             if block != KbMonBlock::Pass {
                 *consumed_by_a11y = true;
             }
@@ -464,18 +748,26 @@ impl State {
         #[cfg(not(feature = "dbus"))]
         let _ = consumed_by_a11y;
 
-        let Some(Some(bind)) = self.niri.seat.get_keyboard().unwrap().input(
+        let mut released_bind = None;
+        let res = self.niri.seat.get_keyboard().unwrap().input(
             self,
-            event.key_code(),
-            event.state(),
+            key_code,
+            state,
             serial,
             time,
             |this, mods, keysym| {
-                let key_code = event.key_code();
                 let modified = keysym.modified_sym();
                 let raw = keysym.raw_latin_sym_or_raw_current_sym();
                 let modifiers = modifiers_from_state(*mods);
 
+                if pressed {
+                    this.cancel_tap_binds();
+                } else {
+                    // The release still goes through the regular handling below, so that it
+                    // reaches the focused window if and only if the press did.
+                    released_bind = this.niri.release_binds.remove(&key_code);
+                }
+
                 // After updating XKB state from accessibility-grabbed keys, return right away and
                 // don't handle them.
                 #[cfg(feature = "dbus")]
@@ -554,10 +846,60 @@ impl State {
                     this.niri.screenshot_ui.set_space_down(pressed);
                 }
 
+                // Continue the bind sequence in progress.
+                if pressed && !this.niri.bind_sequence.is_empty() {
+                    // Modifiers are held down for the next key, so they don't end the sequence.
+                    if modified.is_modifier_key() {
+                        return FilterResult::Forward;
+                    }
+
+                    let res = raw.map(|raw| {
+                        let config = this.niri.config.borrow();
+                        let bindings = make_binds_iter(
+                            &config,
+                            this.niri.bind_mode.as_deref(),
+                            this.niri.app_binds.as_deref(),
+                            &mut this.niri.window_mru_ui,
+                            modifiers,
+                        );
+                        match_bind_sequence(
+                            bindings,
+                            mod_key,
+                            &this.niri.bind_sequence,
+                            Trigger::Keysym(raw),
+                            *mods,
+                        )
+                    });
+
+                    // Keys that don't continue any sequence cancel it and are swallowed too.
+                    this.niri.suppressed_keys.insert(key_code);
+                    return match res {
+                        Some(SequenceMatch::Complete(bind)) => {
+                            this.set_bind_sequence(Vec::new());
+                            this.press_bind(key_code, modified, bind)
+                        }
+                        Some(SequenceMatch::Prefix(key)) => {
+                            let mut sequence = this.niri.bind_sequence.clone();
+                            sequence.push(key);
+                            this.set_bind_sequence(sequence);
+                            FilterResult::Intercept(None)
+                        }
+                        Some(SequenceMatch::None) | None => {
+                            this.set_bind_sequence(Vec::new());
+                            FilterResult::Intercept(None)
+                        }
+                    };
+                }
+
                 let res = {
                     let config = this.niri.config.borrow();
-                    let bindings =
-                        make_binds_iter(&config, &mut this.niri.window_mru_ui, modifiers);
+                    let bindings = make_binds_iter(
+                        &config,
+                        this.niri.bind_mode.as_deref(),
+                        this.niri.app_binds.as_deref(),
+                        &mut this.niri.window_mru_ui,
+                        modifiers,
+                    );
 
                     should_intercept_key(
                         &mut this.niri.suppressed_keys,
@@ -574,7 +916,44 @@ impl State {
                     )
                 };
 
+                let res = match res {
+                    FilterResult::Intercept(Some(bind)) if pressed => {
+                        this.press_bind(key_code, modified, bind)
+                    }
+                    res => res,
+                };
+
                 if matches!(res, FilterResult::Forward) {
+                    // Start a bind sequence if the key begins one.
+                    let can_start_sequence =
+                        pressed && !is_inhibiting_shortcuts && !this.niri.screenshot_ui.is_open();
+                    if let Some(raw) = raw.filter(|_| can_start_sequence) {
+                        let res = {
+                            let config = this.niri.config.borrow();
+                            let bindings = make_binds_iter(
+                                &config,
+                                this.niri.bind_mode.as_deref(),
+                                this.niri.app_binds.as_deref(),
+                                &mut this.niri.window_mru_ui,
+                                modifiers,
+                            );
+                            match_bind_sequence(bindings, mod_key, &[], Trigger::Keysym(raw), *mods)
+                        };
+
+                        if let SequenceMatch::Prefix(key) = res {
+                            this.niri.suppressed_keys.insert(key_code);
+                            this.set_bind_sequence(vec![key]);
+                            return FilterResult::Intercept(None);
+                        }
+                    }
+
+                    // Escape leaves the bind mode, unless the mode binds it to something else.
+                    if pressed && raw == Some(Keysym::Escape) && this.niri.bind_mode.is_some() {
+                        this.niri.suppressed_keys.insert(key_code);
+                        this.set_bind_mode(None);
+                        return FilterResult::Intercept(None);
+                    }
+
                     // If we didn't find any bind, try other hardcoded keys.
                     if this.niri.keyboard_focus.is_overview() && pressed {
                         if let Some(bind) = raw.and_then(|raw| hardcoded_overview_bind(raw, *mods))
@@ -591,7 +970,14 @@ impl State {
 
                 res
             },
-        ) else {
+        );
+
+        if let Some(bind) = released_bind {
+            self.handle_bind(bind);
+            return;
+        }
+
+        let Some(Some(bind)) = res else {
             return;
         };
 
@@ -604,6 +990,64 @@ impl State {
         self.start_key_repeat(bind);
     }
 
+    /// Returns how to handle the press of a key triggering a bind.
+    ///
+    /// Binds triggered on release are held back until their key is released.
+    fn press_bind(
+        &mut self,
+        key_code: Keycode,
+        modified: Keysym,
+        bind: Bind,
+    ) -> FilterResult<Option<Bind>> {
+        if !bind.on_release && !bind.tap {
+            return FilterResult::Intercept(Some(bind));
+        }
+
+        // Let modifier presses through, so that the modifier keeps working in key combinations.
+        let forward = bind.tap && modified.is_modifier_key();
+        self.niri.release_binds.insert(key_code, bind);
+
+        if forward {
+            self.niri.suppressed_keys.remove(&key_code);
+            FilterResult::Forward
+        } else {
+            self.niri.suppressed_keys.insert(key_code);
+            FilterResult::Intercept(None)
+        }
+    }
+
+    /// Keeps pending tap binds from triggering, since their key wasn't tapped on its own.
+    fn cancel_tap_binds(&mut self) {
+        self.niri.release_binds.retain(|_, bind| !bind.tap);
+    }
+
+    /// Sets the keys pressed so far of a bind sequence, or cancels it when empty.
+    pub fn set_bind_sequence(&mut self, sequence: Vec<Key>) {
+        if let Some(token) = self.niri.bind_sequence_timer.take() {
+            self.niri.event_loop.remove(token);
+        }
+
+        if !sequence.is_empty() {
+            let timer = Timer::from_duration(BIND_SEQUENCE_TIMEOUT);
+            let token = self
+                .niri
+                .event_loop
+                .insert_source(timer, |_, _, state| {
+                    state.niri.bind_sequence_timer = None;
+                    state.set_bind_sequence(Vec::new());
+                    TimeoutAction::Drop
+                })
+                .unwrap();
+            self.niri.bind_sequence_timer = Some(token);
+        }
+
+        if self.niri.hotkey_overlay.set_bind_sequence(sequence.clone()) {
+            self.niri.queue_redraw_all();
+        }
+
+        self.niri.bind_sequence = sequence;
+    }
+
     fn start_key_repeat(&mut self, bind: Bind) {
         if !bind.repeat {
             return;
@@ -712,6 +1156,17 @@ impl State {
                 info!("quitting as requested");
                 self.niri.stop_signal.stop()
             }
+            Action::Sequence(actions) => {
+                // Running them all before the next redraw shows and animates only the end state:
+                // the animation clock stands still until then, so every animation they start
+                // begins from what is on screen now.
+                for action in actions {
+                    self.do_action(action, allow_when_locked);
+                }
+
+                // Windows resized by different actions change size in the same frame.
+                self.niri.layout.share_pending_transactions();
+            }
             Action::ChangeVt(vt) => {
                 self.backend.change_vt(vt);
                 // Changing VT may not deliver the key releases, so clear the state.
@@ -831,6 +1286,42 @@ impl State {
                     });
                 }
             }
+            Action::ToggleStickyKeys => {
+                let on = !self.niri.key_filters.sticky_keys;
+                self.set_sticky_keys(on);
+                self.announce_key_filter("Sticky keys", on);
+            }
+            Action::ToggleSlowKeys => {
+                let on = !self.niri.key_filters.slow_keys;
+                self.niri.key_filters.slow_keys = on;
+                self.announce_key_filter("Slow keys", on);
+            }
+            Action::ToggleBounceKeys => {
+                let on = !self.niri.key_filters.bounce_keys;
+                self.niri.key_filters.bounce_keys = on;
+                self.announce_key_filter("Bounce keys", on);
+            }
+            Action::ToggleMouseKeys => {
+                let on = !self.niri.mouse_keys.is_on;
+                self.set_mouse_keys(on);
+                self.announce_key_filter("Mouse keys", on);
+            }
+            Action::ZoomIn => {
+                let step = self.niri.config.borrow().zoom.step;
+                self.niri.set_zoom_factor(self.niri.zoom_factor + step);
+            }
+            Action::ZoomOut => {
+                let step = self.niri.config.borrow().zoom.step;
+                self.niri.set_zoom_factor(self.niri.zoom_factor - step);
+            }
+            Action::ToggleZoom => {
+                let factor = if self.niri.zoom_factor > 1. {
+                    1.
+                } else {
+                    self.niri.config.borrow().zoom.factor
+                };
+                self.niri.set_zoom_factor(factor);
+            }
             Action::ToggleKeyboardShortcutsInhibit => {
                 if let Some(inhibitor) = self.niri.keyboard_focus.surface().and_then(|surface| {
                     self.niri
@@ -1617,6 +2108,12 @@ impl State {
                 // FIXME: granular
                 self.niri.queue_redraw_all();
             }
+            Action::SetLayoutMode(mode) => {
+                self.niri.layout.set_layout_mode(mode);
+                self.maybe_warp_cursor_to_focus();
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
             Action::SwitchPresetColumnWidth => {
                 self.niri.layout.toggle_width(true);
             }
@@ -2195,6 +2692,143 @@ impl State {
                     self.niri.queue_redraw_all();
                 }
             }
+            Action::ToggleWindowSticky => {
+                self.niri.layout.toggle_window_sticky(None);
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
+            Action::ToggleWindowStickyById(id) => {
+                let window = self.niri.layout.windows().find(|(_, m)| m.id().get() == id);
+                let window = window.map(|(_, m)| m.window.clone());
+                if let Some(window) = window {
+                    self.niri.layout.toggle_window_sticky(Some(&window));
+                    // FIXME: granular
+                    self.niri.queue_redraw_all();
+                }
+            }
+            Action::ToggleScratchpad => {
+                self.niri.layout.toggle_scratchpad();
+                self.maybe_warp_cursor_to_focus();
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
+            Action::MoveWindowToScratchpad => {
+                self.niri.layout.move_window_to_scratchpad(None);
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
+            Action::MoveWindowToScratchpadById(id) => {
+                let window = self.niri.layout.windows().find(|(_, m)| m.id().get() == id);
+                let window = window.map(|(_, m)| m.window.clone());
+                if let Some(window) = window {
+                    self.niri.layout.move_window_to_scratchpad(Some(&window));
+                    // FIXME: granular
+                    self.niri.queue_redraw_all();
+                }
+            }
+            Action::MinimizeWindow => {
+                self.niri.layout.minimize_window(None);
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
+            Action::MinimizeWindowById(id) => {
+                let window = self.niri.layout.windows().find(|(_, m)| m.id().get() == id);
+                let window = window.map(|(_, m)| m.window.clone());
+                if let Some(window) = window {
+                    self.niri.layout.minimize_window(Some(&window));
+                    // FIXME: granular
+                    self.niri.queue_redraw_all();
+                }
+            }
+            Action::RestoreMinimizedWindow => {
+                self.niri.layout.restore_minimized_window(None);
+                self.maybe_warp_cursor_to_focus();
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
+            Action::RestoreMinimizedWindowById(id) => {
+                let window = self
+                    .niri
+                    .layout
+                    .minimized_windows()
+                    .find(|m| m.id().get() == id);
+                let window = window.map(|m| m.window.clone());
+                if let Some(window) = window {
+                    self.niri.layout.restore_minimized_window(Some(&window));
+                    self.maybe_warp_cursor_to_focus();
+                    // FIXME: granular
+                    self.niri.queue_redraw_all();
+                }
+            }
+            Action::UndoLayoutChange => {
+                self.niri.layout.undo_layout_change();
+                self.maybe_warp_cursor_to_focus();
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
+            Action::RedoLayoutChange => {
+                self.niri.layout.redo_layout_change();
+                self.maybe_warp_cursor_to_focus();
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
+            Action::SetWindowMark(mark) => {
+                let window = self.niri.layout.focus().map(|m| m.window.clone());
+                if let Some(window) = window {
+                    self.niri.set_window_mark(&window, &mark);
+                    // FIXME: granular
+                    self.niri.queue_redraw_all();
+                }
+            }
+            Action::SetWindowMarkById { id, mark } => {
+                let window = self.niri.layout.windows().find(|(_, m)| m.id().get() == id);
+                let window = window.map(|(_, m)| m.window.clone());
+                if let Some(window) = window {
+                    self.niri.set_window_mark(&window, &mark);
+                    // FIXME: granular
+                    self.niri.queue_redraw_all();
+                }
+            }
+            Action::FocusWindowByMark(mark) => {
+                if let Some(window) = self.niri.find_window_by_mark(&mark) {
+                    self.focus_window(&window);
+                }
+            }
+            Action::SwapWithMark(mark) => {
+                if let Some(window) = self.niri.find_window_by_mark(&mark) {
+                    self.niri.layout.swap_windows(None, &window);
+                    self.maybe_warp_cursor_to_focus();
+                    // FIXME: granular
+                    self.niri.queue_redraw_all();
+                }
+            }
+            Action::SnapFloatingWindow(zone) => {
+                self.niri.layout.snap_floating_window(None, zone, true);
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
+            Action::SnapFloatingWindowById { id, zone } => {
+                let window = self.niri.layout.windows().find(|(_, m)| m.id().get() == id);
+                let window = window.map(|(_, m)| m.window.clone());
+                if let Some(window) = window {
+                    self.niri
+                        .layout
+                        .snap_floating_window(Some(&window), zone, true);
+                    // FIXME: granular
+                    self.niri.queue_redraw_all();
+                }
+            }
+            Action::EnterBindMode(name) => {
+                let exists = self.niri.config.borrow().bind_mode(&name).is_some();
+                if exists {
+                    self.set_bind_mode(Some(name));
+                } else {
+                    warn!("bind mode {name:?} does not exist");
+                }
+            }
+            Action::ExitBindMode => {
+                self.set_bind_mode(None);
+            }
             Action::FocusFloating => {
                 self.niri.layout.focus_floating();
                 self.maybe_warp_cursor_to_focus();
@@ -2524,7 +3158,9 @@ impl State {
             }
             None
         });
-        if let Some((output, horizontal)) = spatial_grab.flatten() {
+        if let Some((output, is_view_offset)) = spatial_grab.flatten() {
+            let horizontal =
+                is_view_offset != self.niri.layout.is_view_offset_gesture_vertical(&output);
             if let Some(geo) = self.niri.global_space.output_geometry(&output) {
                 let geo = geo.to_f64();
                 if horizontal {
@@ -2689,7 +3325,8 @@ impl State {
 
         let Some(pos) = self.compute_absolute_location(&event, None).or_else(|| {
             self.global_bounding_rectangle().map(|output_geo| {
-                event.position_transformed(output_geo.size) + output_geo.loc.to_f64()
+                let pos = event.position_transformed(output_geo.size) + output_geo.loc.to_f64();
+                self.niri.unzoom_position(pos)
             })
         }) else {
             return;
@@ -2788,6 +3425,11 @@ impl State {
 
         let mod_key = self.backend.mod_key(&self.niri.config.borrow());
 
+        // Clicking while holding a dual-role key uses it as its hold key, e.g. for Ctrl+click.
+        if button_state == ButtonState::Pressed {
+            self.hold_dual_role_keys(event.time_msec());
+        }
+
         // Ignore release events for mouse clicks that triggered a bind.
         if self.niri.suppressed_buttons.remove(&button_code) {
             return;
@@ -2798,6 +3440,8 @@ impl State {
         let mod_down = modifiers.contains(mod_key.to_modifiers());
 
         if ButtonState::Pressed == button_state {
+            self.cancel_tap_binds();
+
             let mut is_mru_open = false;
             if let Some(mru_output) = self.niri.window_mru_ui.output() {
                 is_mru_open = true;
@@ -2831,8 +3475,13 @@ impl State {
                 }
                 .and_then(|trigger| {
                     let config = self.niri.config.borrow();
-                    let bindings =
-                        make_binds_iter(&config, &mut self.niri.window_mru_ui, modifiers);
+                    let bindings = make_binds_iter(
+                        &config,
+                        self.niri.bind_mode.as_deref(),
+                        self.niri.app_binds.as_deref(),
+                        &mut self.niri.window_mru_ui,
+                        modifiers,
+                    );
                     find_configured_bind(bindings, mod_key, trigger, mods)
                 })
                 .filter(|bind| {
@@ -3107,6 +3756,8 @@ impl State {
 
         let mod_key = self.backend.mod_key(&self.niri.config.borrow());
 
+        self.cancel_tap_binds();
+
         // We received an event for the regular pointer, so show it now. This is also needed for
         // update_pointer_contents() below to return the real contents, necessary for the pointer
         // axis event to reach the window.
@@ -3162,8 +3813,11 @@ impl State {
                                     trigger: Trigger::WheelScrollLeft,
                                     modifiers: Modifiers::empty(),
                                 },
+                                sequence: Vec::new(),
                                 action: Action::FocusColumnLeftUnderMouse,
                                 repeat: true,
+                                on_release: false,
+                                tap: false,
                                 cooldown: None,
                                 allow_when_locked: false,
                                 allow_inhibiting: false,
@@ -3174,8 +3828,11 @@ impl State {
                                     trigger: Trigger::WheelScrollRight,
                                     modifiers: Modifiers::empty(),
                                 },
+                                sequence: Vec::new(),
                                 action: Action::FocusColumnRightUnderMouse,
                                 repeat: true,
+                                on_release: false,
+                                tap: false,
                                 cooldown: None,
                                 allow_when_locked: false,
                                 allow_inhibiting: false,
@@ -3184,8 +3841,13 @@ impl State {
                             (bind_left, bind_right)
                         } else {
                             let config = self.niri.config.borrow();
-                            let bindings =
-                                make_binds_iter(&config, &mut self.niri.window_mru_ui, modifiers);
+                            let bindings = make_binds_iter(
+                                &config,
+                                self.niri.bind_mode.as_deref(),
+                                self.niri.app_binds.as_deref(),
+                                &mut self.niri.window_mru_ui,
+                                modifiers,
+                            );
                             let bind_left = find_configured_bind(
                                 bindings.clone(),
                                 mod_key,
@@ -3231,8 +3893,11 @@ impl State {
                                 trigger: Trigger::WheelScrollUp,
                                 modifiers: Modifiers::empty(),
                             },
+                            sequence: Vec::new(),
                             action: Action::FocusWorkspaceUpUnderMouse,
                             repeat: true,
+                            on_release: false,
+                            tap: false,
                             cooldown: Some(Duration::from_millis(50)),
                             allow_when_locked: false,
                             allow_inhibiting: false,
@@ -3243,8 +3908,11 @@ impl State {
                                 trigger: Trigger::WheelScrollDown,
                                 modifiers: Modifiers::empty(),
                             },
+                            sequence: Vec::new(),
                             action: Action::FocusWorkspaceDownUnderMouse,
                             repeat: true,
+                            on_release: false,
+                            tap: false,
                             cooldown: Some(Duration::from_millis(50)),
                             allow_when_locked: false,
                             allow_inhibiting: false,
@@ -3257,8 +3925,11 @@ impl State {
                                 trigger: Trigger::WheelScrollUp,
                                 modifiers: Modifiers::empty(),
                             },
+                            sequence: Vec::new(),
                             action: Action::FocusColumnLeftUnderMouse,
                             repeat: true,
+                            on_release: false,
+                            tap: false,
                             cooldown: Some(Duration::from_millis(50)),
                             allow_when_locked: false,
                             allow_inhibiting: false,
@@ -3269,8 +3940,11 @@ impl State {
                                 trigger: Trigger::WheelScrollDown,
                                 modifiers: Modifiers::empty(),
                             },
+                            sequence: Vec::new(),
                             action: Action::FocusColumnRightUnderMouse,
                             repeat: true,
+                            on_release: false,
+                            tap: false,
                             cooldown: Some(Duration::from_millis(50)),
                             allow_when_locked: false,
                             allow_inhibiting: false,
@@ -3279,8 +3953,13 @@ impl State {
                         (bind_up, bind_down)
                     } else {
                         let config = self.niri.config.borrow();
-                        let bindings =
-                            make_binds_iter(&config, &mut self.niri.window_mru_ui, modifiers);
+                        let bindings = make_binds_iter(
+                            &config,
+                            self.niri.bind_mode.as_deref(),
+                            self.niri.app_binds.as_deref(),
+                            &mut self.niri.window_mru_ui,
+                            modifiers,
+                        );
                         let bind_up = find_configured_bind(
                             bindings.clone(),
                             mod_key,
@@ -3432,8 +4111,13 @@ impl State {
                     .accumulate(horizontal);
                 if ticks != 0 {
                     let config = self.niri.config.borrow();
-                    let bindings =
-                        make_binds_iter(&config, &mut self.niri.window_mru_ui, modifiers);
+                    let bindings = make_binds_iter(
+                        &config,
+                        self.niri.bind_mode.as_deref(),
+                        self.niri.app_binds.as_deref(),
+                        &mut self.niri.window_mru_ui,
+                        modifiers,
+                    );
                     let bind_left = find_configured_bind(
                         bindings.clone(),
                         mod_key,
@@ -3470,8 +4154,13 @@ impl State {
                     .accumulate(vertical);
                 if ticks != 0 {
                     let config = self.niri.config.borrow();
-                    let bindings =
-                        make_binds_iter(&config, &mut self.niri.window_mru_ui, modifiers);
+                    let bindings = make_binds_iter(
+                        &config,
+                        self.niri.bind_mode.as_deref(),
+                        self.niri.app_binds.as_deref(),
+                        &mut self.niri.window_mru_ui,
+                        modifiers,
+                    );
                     let bind_up = find_configured_bind(
                         bindings.clone(),
                         mod_key,
@@ -3935,6 +4624,17 @@ impl State {
             return;
         }
 
+        let fingers = event.fingers();
+        if self.niri.config.borrow().gestures.has_swipe_binds(fingers) {
+            self.niri.gesture_bind = Some(GestureBindProgress::Swipe {
+                fingers,
+                cumulative: (0., 0.),
+            });
+
+            // We handled this event.
+            return;
+        }
+
         if event.fingers() == 3 {
             self.niri.gesture_swipe_3f_cumulative = Some((0., 0.));
 
@@ -3981,6 +4681,7 @@ impl State {
             delta_y = libinput_event.dy_unaccelerated();
         }
 
+        let uninverted_delta_x = delta_x;
         let uninverted_delta_y = delta_y;
 
         let device = event.device();
@@ -3991,7 +4692,33 @@ impl State {
             }
         }
 
-        let is_overview_open = self.niri.layout.is_overview_open();
+        if let Some(GestureBindProgress::Swipe {
+            fingers,
+            cumulative: (cx, cy),
+        }) = &mut self.niri.gesture_bind
+        {
+            // The direction follows the fingers regardless of natural scrolling.
+            *cx += uninverted_delta_x;
+            *cy += uninverted_delta_y;
+
+            let (fingers, cx, cy) = (*fingers, *cx, *cy);
+            if cx * cx + cy * cy >= 16. * 16. {
+                self.niri.gesture_bind = Some(GestureBindProgress::Done);
+
+                let direction = if cx.abs() > cy.abs() {
+                    if cx > 0. {
+                        SwipeDirection::Right
+                    } else {
+                        SwipeDirection::Left
+                    }
+                } else if cy > 0. {
+                    SwipeDirection::Down
+                } else {
+                    SwipeDirection::Up
+                };
+                self.trigger_gesture_bind(GestureTrigger::Swipe { fingers, direction });
+            }
+        }
 
         if let Some((cx, cy)) = &mut self.niri.gesture_swipe_3f_cumulative {
             *cx += delta_x;
@@ -4003,24 +4730,15 @@ impl State {
                 self.niri.gesture_swipe_3f_cumulative = None;
 
                 if let Some(output) = self.niri.output_under_cursor() {
-                    if cx.abs() > cy.abs() {
-                        let output_ws = if is_overview_open {
-                            self.niri.workspace_under_cursor(true)
+                    let is_view_offset =
+                        if self.niri.layout.is_view_offset_gesture_vertical(&output) {
+                            cy.abs() > cx.abs()
                         } else {
-                            // We don't want to accidentally "catch" the wrong workspace during
-                            // animations.
-                            self.niri.output_under_cursor().and_then(|output| {
-                                let mon = self.niri.layout.monitor_for_output(&output)?;
-                                Some((output, mon.active_workspace_ref()))
-                            })
+                            cx.abs() > cy.abs()
                         };
 
-                        if let Some((output, ws)) = output_ws {
-                            let ws_idx = self.niri.layout.find_workspace_by_id(ws.id()).unwrap().0;
-                            self.niri
-                                .layout
-                                .view_offset_gesture_begin(&output, Some(ws_idx), true);
-                        }
+                    if is_view_offset {
+                        self.begin_view_offset_swipe();
                     } else {
                         self.niri
                             .layout
@@ -4032,11 +4750,22 @@ impl State {
 
         let timestamp = Duration::from_micros(event.time());
 
-        let mut handled = false;
-        let res = self
+        // With vertical scrolling orientation, the swipe axes are swapped.
+        let (view_delta, workspace_delta) = if self
             .niri
-            .layout
-            .workspace_switch_gesture_update(delta_y, timestamp, true);
+            .output_under_cursor()
+            .is_some_and(|output| self.niri.layout.is_view_offset_gesture_vertical(&output))
+        {
+            (delta_y, delta_x)
+        } else {
+            (delta_x, delta_y)
+        };
+
+        let mut handled = false;
+        let res =
+            self.niri
+                .layout
+                .workspace_switch_gesture_update(workspace_delta, timestamp, true);
         if let Some(output) = res {
             if let Some(output) = output {
                 self.niri.queue_redraw(&output);
@@ -4047,7 +4776,7 @@ impl State {
         let res = self
             .niri
             .layout
-            .view_offset_gesture_update(delta_x, timestamp, true);
+            .view_offset_gesture_update(view_delta, timestamp, true);
         if let Some(output) = res {
             if let Some(output) = output {
                 self.niri.queue_redraw(&output);
@@ -4066,7 +4795,7 @@ impl State {
             handled = true;
         }
 
-        if handled {
+        if handled || self.niri.gesture_bind.is_some() {
             // We handled this event.
             return;
         }
@@ -4089,7 +4818,7 @@ impl State {
     fn on_gesture_swipe_end<I: InputBackend>(&mut self, event: I::GestureSwipeEndEvent) {
         self.niri.gesture_swipe_3f_cumulative = None;
 
-        let mut handled = false;
+        let mut handled = self.niri.gesture_bind.take().is_some();
         let res = self.niri.layout.workspace_switch_gesture_end(Some(true));
         if let Some(output) = res {
             self.niri.queue_redraw(&output);
@@ -4131,6 +4860,14 @@ impl State {
     }
 
     fn on_gesture_pinch_begin<I: InputBackend>(&mut self, event: I::GesturePinchBeginEvent) {
+        let fingers = event.fingers();
+        if self.niri.config.borrow().gestures.has_pinch_binds(fingers) {
+            self.niri.gesture_bind = Some(GestureBindProgress::Pinch { fingers });
+
+            // We handled this event.
+            return;
+        }
+
         let serial = SERIAL_COUNTER.next_serial();
         let pointer = self.niri.seat.get_pointer().unwrap();
 
@@ -4149,6 +4886,27 @@ impl State {
     }
 
     fn on_gesture_pinch_update<I: InputBackend>(&mut self, event: I::GesturePinchUpdateEvent) {
+        if let Some(progress) = self.niri.gesture_bind {
+            if let GestureBindProgress::Pinch { fingers } = progress {
+                let scale = event.scale();
+                let direction = if scale <= 1. - GESTURE_PINCH_THRESHOLD {
+                    Some(PinchDirection::In)
+                } else if scale >= 1. + GESTURE_PINCH_THRESHOLD {
+                    Some(PinchDirection::Out)
+                } else {
+                    None
+                };
+
+                if let Some(direction) = direction {
+                    self.niri.gesture_bind = Some(GestureBindProgress::Done);
+                    self.trigger_gesture_bind(GestureTrigger::Pinch { fingers, direction });
+                }
+            }
+
+            // We handled this event.
+            return;
+        }
+
         let pointer = self.niri.seat.get_pointer().unwrap();
 
         if self.update_pointer_contents() {
@@ -4167,6 +4925,11 @@ impl State {
     }
 
     fn on_gesture_pinch_end<I: InputBackend>(&mut self, event: I::GesturePinchEndEvent) {
+        if self.niri.gesture_bind.take().is_some() {
+            // We handled this event.
+            return;
+        }
+
         let serial = SERIAL_COUNTER.next_serial();
         let pointer = self.niri.seat.get_pointer().unwrap();
 
@@ -4185,6 +4948,14 @@ impl State {
     }
 
     fn on_gesture_hold_begin<I: InputBackend>(&mut self, event: I::GestureHoldBeginEvent) {
+        let fingers = event.fingers();
+        if self.niri.config.borrow().gestures.has_hold_binds(fingers) {
+            self.niri.gesture_bind = Some(GestureBindProgress::Hold { fingers });
+
+            // We handled this event.
+            return;
+        }
+
         let serial = SERIAL_COUNTER.next_serial();
         let pointer = self.niri.seat.get_pointer().unwrap();
 
@@ -4203,6 +4974,18 @@ impl State {
     }
 
     fn on_gesture_hold_end<I: InputBackend>(&mut self, event: I::GestureHoldEndEvent) {
+        if let Some(progress) = self.niri.gesture_bind.take() {
+            // Moving the fingers cancels the hold.
+            if let GestureBindProgress::Hold { fingers } = progress {
+                if !event.cancelled() {
+                    self.trigger_gesture_bind(GestureTrigger::Hold { fingers });
+                }
+            }
+
+            // We handled this event.
+            return;
+        }
+
         let serial = SERIAL_COUNTER.next_serial();
         let pointer = self.niri.seat.get_pointer().unwrap();
 
@@ -4220,6 +5003,51 @@ impl State {
         );
     }
 
+    fn trigger_gesture_bind(&mut self, trigger: GestureTrigger) {
+        let action = {
+            let config = self.niri.config.borrow();
+            let Some(bind) = config.gestures.find_bind(trigger) else {
+                return;
+            };
+            bind.action.clone()
+        };
+
+        match action {
+            GestureAction::ViewScroll => self.begin_view_offset_swipe(),
+            GestureAction::WorkspaceSwitch => {
+                if let Some(output) = self.niri.output_under_cursor() {
+                    self.niri
+                        .layout
+                        .workspace_switch_gesture_begin(&output, true);
+                }
+            }
+            GestureAction::Overview => {
+                self.niri.layout.overview_gesture_begin();
+                self.niri.queue_redraw_all();
+            }
+            GestureAction::Action(action) => self.do_action(action, false),
+        }
+    }
+
+    fn begin_view_offset_swipe(&mut self) {
+        let output_ws = if self.niri.layout.is_overview_open() {
+            self.niri.workspace_under_cursor(true)
+        } else {
+            // We don't want to accidentally "catch" the wrong workspace during animations.
+            self.niri.output_under_cursor().and_then(|output| {
+                let mon = self.niri.layout.monitor_for_output(&output)?;
+                Some((output, mon.active_workspace_ref()))
+            })
+        };
+
+        if let Some((output, ws)) = output_ws {
+            let ws_idx = self.niri.layout.find_workspace_by_id(ws.id()).unwrap().0;
+            self.niri
+                .layout
+                .view_offset_gesture_begin(&output, Some(ws_idx), true);
+        }
+    }
+
     fn compute_absolute_location<I: InputBackend>(
         &self,
         evt: &impl AbsolutePositionEvent<I>,
@@ -4231,10 +5059,9 @@ impl State {
         let output_geo = self.niri.global_space.output_geometry(output).unwrap();
         let transform = output.current_transform();
         let size = transform.invert().transform_size(output_geo.size);
-        Some(
-            transform.transform_point_in(evt.position_transformed(size), &size.to_f64())
-                + output_geo.loc.to_f64(),
-        )
+        let pos = transform.transform_point_in(evt.position_transformed(size), &size.to_f64())
+            + output_geo.loc.to_f64();
+        Some(self.niri.unzoom_position(pos))
     }
 
     /// Computes the cursor position for the touch event.
@@ -4247,7 +5074,10 @@ impl State {
         self.compute_absolute_location(evt, self.niri.output_for_touch())
     }
 
-    fn on_touch_down<I: InputBackend>(&mut self, evt: I::TouchDownEvent) {
+    fn on_touch_down<I: InputBackend + 'static>(&mut self, evt: I::TouchDownEvent)
+    where
+        I::Device: 'static,
+    {
         let Some(handle) = self.niri.seat.get_touch() else {
             return;
         };
@@ -4301,7 +5131,17 @@ impl State {
                 }
             }
         } else if !handle.is_grabbed() {
-            if self.niri.layout.is_overview_open()
+            let device = evt.device();
+            let device = (&device as &dyn Any).downcast_ref::<input::Device>();
+            if let Some((output, edge, action)) = self.edge_swipe_at(pos, device) {
+                let start_data = TouchGrabStartData {
+                    focus: None,
+                    slot,
+                    location: pos,
+                };
+                let grab = TouchEdgeSwipeGrab::new(start_data, output, edge, action);
+                handle.set_grab(self, grab, serial);
+            } else if self.niri.layout.is_overview_open()
                 && !mod_down
                 && under.layer.is_none()
                 && under.output.is_some()
@@ -4379,6 +5219,46 @@ impl State {
         // We're using touch, hide the pointer.
         self.niri.pointer_visibility = PointerVisibility::Disabled;
     }
+    /// Returns the edge swipe that a touch starting at `pos` begins, if any.
+    fn edge_swipe_at(
+        &self,
+        pos: Point<f64, Logical>,
+        device: Option<&input::Device>,
+    ) -> Option<(Output, ScreenEdge, GestureAction)> {
+        if self.niri.is_locked() {
+            return None;
+        }
+
+        let config = self.niri.config.borrow();
+        let device_edge_swipes = device.and_then(|device| {
+            config
+                .input
+                .devices_matching(device.name(), device.id_vendor(), device.id_product())
+                .filter_map(|c| c.edge_swipes.as_ref())
+                .last()
+        });
+        let edge_swipes = device_edge_swipes.or(config.input.touch.edge_swipes.as_ref())?;
+
+        // Touch positions are mapped onto this output.
+        let output = self.niri.output_for_touch()?;
+        let geom = self.niri.global_space.output_geometry(output)?;
+        let pos_within_output = pos - geom.loc.to_f64();
+        let edge = touch_edge_swipe_grab::edge_at(
+            pos_within_output,
+            geom.size.to_f64(),
+            edge_swipes.edge_width(),
+        )?;
+
+        let swipe = match edge {
+            ScreenEdge::Left => &edge_swipes.left,
+            ScreenEdge::Right => &edge_swipes.right,
+            ScreenEdge::Top => &edge_swipes.top,
+            ScreenEdge::Bottom => &edge_swipes.bottom,
+        };
+        let action = swipe.as_ref()?.action.clone();
+        Some((output.clone(), edge, action))
+    }
+
     fn on_touch_up<I: InputBackend>(&mut self, evt: I::TouchUpEvent) {
         let Some(handle) = self.niri.seat.get_touch() else {
             return;
@@ -4544,8 +5424,11 @@ fn should_intercept_key<'a>(
                         // Not entirely correct but it doesn't matter in how we currently use it.
                         modifiers: Modifiers::empty(),
                     },
+                    sequence: Vec::new(),
                     action,
                     repeat: true,
+                    on_release: false,
+                    tap: false,
                     cooldown: None,
                     allow_when_locked: false,
                     // The screenshot UI owns the focus anyway, so this doesn't really matter.
@@ -4608,8 +5491,11 @@ fn find_bind<'a>(
                 trigger: Trigger::Keysym(modified),
                 modifiers: Modifiers::empty(),
             },
+            sequence: Vec::new(),
             action,
             repeat: true,
+            on_release: false,
+            tap: false,
             cooldown: None,
             allow_when_locked: false,
             // In a worst-case scenario, the user has no way to unlock the compositor and a
@@ -4632,32 +5518,98 @@ fn find_configured_bind<'a>(
     trigger: Trigger,
     mods: ModifiersState,
 ) -> Option<Bind> {
-    // Handle configured binds.
-    let mut modifiers = modifiers_from_state(mods);
+    // Handle configured binds. Sequences are handled separately.
+    bindings
+        .into_iter()
+        .find(|bind| {
+            let on_release = bind.on_release || bind.tap;
+            bind.sequence.is_empty() && key_matches(&bind.key, mod_key, trigger, mods, on_release)
+        })
+        .cloned()
+}
 
-    let mod_down = modifiers_from_state(mods).contains(mod_key.to_modifiers());
-    if mod_down {
-        modifiers |= Modifiers::COMPOSITOR;
-    }
+/// Result of pressing a key for bind sequences.
+enum SequenceMatch {
+    /// The key completes the sequence of this bind.
+    Complete(Bind),
+    /// The key continues one or more sequences, as this key of their binds.
+    Prefix(Key),
+    None,
+}
+
+fn match_bind_sequence<'a>(
+    bindings: impl IntoIterator<Item = &'a Bind>,
+    mod_key: ModKey,
+    pressed: &[Key],
+    trigger: Trigger,
+    mods: ModifiersState,
+) -> SequenceMatch {
+    let mut rv = SequenceMatch::None;
 
     for bind in bindings {
-        if bind.key.trigger != trigger {
+        if bind.sequence.is_empty() || bind.sequence.len() < pressed.len() {
+            continue;
+        }
+
+        let mut keys = bind.keys();
+        if !keys.by_ref().take(pressed.len()).eq(pressed) {
             continue;
         }
 
-        let mut bind_modifiers = bind.key.modifiers;
-        if bind_modifiers.contains(Modifiers::COMPOSITOR) {
-            bind_modifiers |= mod_key.to_modifiers();
-        } else if bind_modifiers.contains(mod_key.to_modifiers()) {
-            bind_modifiers |= Modifiers::COMPOSITOR;
+        let key = keys.next().unwrap();
+        let is_last = pressed.len() == bind.sequence.len();
+        let on_release = is_last && (bind.on_release || bind.tap);
+        if !key_matches(key, mod_key, trigger, mods, on_release) {
+            continue;
         }
 
-        if bind_modifiers == modifiers {
-            return Some(bind.clone());
+        if keys.next().is_none() {
+            return SequenceMatch::Complete(bind.clone());
         }
+
+        rv = SequenceMatch::Prefix(*key);
+    }
+
+    rv
+}
+
+/// Returns whether the key matches the pressed trigger and modifiers.
+///
+/// A modifier key is already down by the time its own press is handled. Binds triggering on
+/// release ignore the modifier of their own key, so that `Super_L tap=true` matches a lone Super
+/// press. Other binds must include it, as in `Mod+Super_L`.
+fn key_matches(
+    key: &Key,
+    mod_key: ModKey,
+    trigger: Trigger,
+    mods: ModifiersState,
+    on_release: bool,
+) -> bool {
+    if key.trigger != trigger {
+        return false;
+    }
+
+    let mut modifiers = modifiers_from_state(mods);
+
+    if on_release {
+        if let Trigger::Keysym(keysym) = trigger {
+            modifiers.remove(keysym_modifiers(keysym));
+        }
+    }
+
+    let mod_down = modifiers.contains(mod_key.to_modifiers());
+    if mod_down {
+        modifiers |= Modifiers::COMPOSITOR;
+    }
+
+    let mut key_modifiers = key.modifiers;
+    if key_modifiers.contains(Modifiers::COMPOSITOR) {
+        key_modifiers |= mod_key.to_modifiers();
+    } else if key_modifiers.contains(mod_key.to_modifiers()) {
+        key_modifiers |= Modifiers::COMPOSITOR;
     }
 
-    None
+    key_modifiers == modifiers
 }
 
 fn find_configured_switch_action(
@@ -4700,6 +5652,19 @@ fn modifiers_from_state(mods: ModifiersState) -> Modifiers {
     modifiers
 }
 
+/// Returns the modifiers that a modifier key sets.
+fn keysym_modifiers(keysym: Keysym) -> Modifiers {
+    match keysym {
+        Keysym::Control_L | Keysym::Control_R => Modifiers::CTRL,
+        Keysym::Shift_L | Keysym::Shift_R => Modifiers::SHIFT,
+        Keysym::Alt_L | Keysym::Alt_R | Keysym::Meta_L | Keysym::Meta_R => Modifiers::ALT,
+        Keysym::Super_L | Keysym::Super_R => Modifiers::SUPER,
+        Keysym::ISO_Level3_Shift => Modifiers::ISO_LEVEL3_SHIFT,
+        Keysym::ISO_Level5_Shift => Modifiers::ISO_LEVEL5_SHIFT,
+        _ => Modifiers::empty(),
+    }
+}
+
 fn should_activate_monitors<I: InputBackend>(event: &InputEvent<I>) -> bool {
     match event {
         InputEvent::Keyboard { event } if event.state() == KeyState::Pressed => true,
@@ -4773,6 +5738,10 @@ fn should_reset_pointer_inactivity_timer<I: InputBackend>(event: &InputEvent<I>)
 }
 
 fn allowed_when_locked(action: &Action) -> bool {
+    if let Action::Sequence(actions) = action {
+        return actions.iter().all(allowed_when_locked);
+    }
+
     matches!(
         action,
         Action::Quit(_)
@@ -4782,10 +5751,21 @@ fn allowed_when_locked(action: &Action) -> bool {
             | Action::PowerOnMonitors
             | Action::SwitchLayout(_)
             | Action::ToggleKeyboardShortcutsInhibit
+            | Action::ToggleStickyKeys
+            | Action::ToggleSlowKeys
+            | Action::ToggleBounceKeys
+            | Action::ToggleMouseKeys
+            | Action::ZoomIn
+            | Action::ZoomOut
+            | Action::ToggleZoom
     )
 }
 
 fn allowed_during_screenshot(action: &Action) -> bool {
+    if let Action::Sequence(actions) = action {
+        return actions.iter().all(allowed_during_screenshot);
+    }
+
     matches!(
         action,
         Action::Quit(_)
@@ -4851,8 +5831,11 @@ fn hardcoded_overview_bind(raw: Keysym, mods: ModifiersState) -> Option<Bind> {
             trigger: Trigger::Keysym(raw),
             modifiers: Modifiers::empty(),
         },
+        sequence: Vec::new(),
         action,
         repeat,
+        on_release: false,
+        tap: false,
         cooldown: None,
         allow_when_locked: false,
         allow_inhibiting: false,
@@ -5148,6 +6131,30 @@ pub fn apply_libinput_settings(config: &niri_config::Input, device: &mut input::
                 .unwrap_or(IDENTITY_MATRIX),
         );
     }
+
+    // Device sections override the settings of the device type.
+    let name = device.name().to_owned();
+    let (vendor, product) = (device.id_vendor(), device.id_product());
+    for c in config.devices_matching(&name, vendor, product) {
+        if let Some(natural_scroll) = c.natural_scroll {
+            let _ = device.config_scroll_set_natural_scroll_enabled(natural_scroll.0);
+        }
+        if let Some(accel_speed) = c.accel_speed {
+            let _ = device.config_accel_set_speed(accel_speed.0);
+        }
+        if let Some(accel_profile) = c.accel_profile {
+            let _ = device.config_accel_set_profile(accel_profile.into());
+        }
+        if let Some(method) = c.scroll_method {
+            let _ = device.config_scroll_set_method(method.into());
+        }
+        if let Some(button) = c.scroll_button {
+            let _ = device.config_scroll_set_button(button);
+        }
+        if let Some(left_handed) = c.left_handed {
+            let _ = device.config_left_handed_set(left_handed.0);
+        }
+    }
 }
 
 pub fn mods_with_binds(mod_key: ModKey, binds: &Binds, triggers: &[Trigger]) -> HashSet<Modifiers> {
@@ -5251,11 +6258,26 @@ fn grab_allows_hot_corner(grab: &(dyn PointerGrab<State> + 'static)) -> bool {
 /// Includes dynamically populated bindings like the MRU UI.
 fn make_binds_iter<'a>(
     config: &'a Config,
+    bind_mode: Option<&str>,
+    app_binds: Option<&str>,
     mru: &'a mut WindowMruUi,
     mods: Modifiers,
 ) -> impl Iterator<Item = &'a Bind> + Clone {
+    // The binds of the active bind mode replace the general binds. Otherwise, the app binds of
+    // the focused window take precedence over the general binds.
+    let mode = bind_mode.and_then(|name| config.bind_mode(name));
+    let app_binds = app_binds
+        .filter(|_| mode.is_none())
+        .and_then(|name| config.app_binds(name));
+    let app_binds = app_binds.into_iter().flat_map(|app| &app.binds.0);
+    let general_binds = match mode {
+        Some(mode) => &mode.binds.0,
+        None => &config.binds.0,
+    };
+    let general_binds = app_binds.chain(general_binds);
+
     // Figure out the binds to use depending on whether the MRU is enabled and/or open.
-    let general_binds = (!mru.is_open()).then_some(config.binds.0.iter());
+    let general_binds = (!mru.is_open()).then_some(general_binds);
     let general_binds = general_binds.into_iter().flatten();
 
     let mru_binds =
@@ -5284,8 +6306,11 @@ mod tests {
                 trigger: Trigger::Keysym(close_keysym),
                 modifiers: Modifiers::COMPOSITOR | Modifiers::CTRL,
             },
+            sequence: Vec::new(),
             action: Action::CloseWindow,
             repeat: true,
+            on_release: false,
+            tap: false,
             cooldown: None,
             allow_when_locked: false,
             allow_inhibiting: true,
@@ -5470,8 +6495,11 @@ mod tests {
                     trigger: Trigger::Keysym(Keysym::q),
                     modifiers: Modifiers::COMPOSITOR,
                 },
+                sequence: Vec::new(),
                 action: Action::CloseWindow,
                 repeat: true,
+                on_release: false,
+                tap: false,
                 cooldown: None,
                 allow_when_locked: false,
                 allow_inhibiting: true,
@@ -5482,8 +6510,11 @@ mod tests {
                     trigger: Trigger::Keysym(Keysym::h),
                     modifiers: Modifiers::SUPER,
                 },
+                sequence: Vec::new(),
                 action: Action::FocusColumnLeft,
                 repeat: true,
+                on_release: false,
+                tap: false,
                 cooldown: None,
                 allow_when_locked: false,
                 allow_inhibiting: true,
@@ -5494,8 +6525,11 @@ mod tests {
                     trigger: Trigger::Keysym(Keysym::j),
                     modifiers: Modifiers::empty(),
                 },
+                sequence: Vec::new(),
                 action: Action::FocusWindowDown,
                 repeat: true,
+                on_release: false,
+                tap: false,
                 cooldown: None,
                 allow_when_locked: false,
                 allow_inhibiting: true,
@@ -5506,8 +6540,11 @@ mod tests {
                     trigger: Trigger::Keysym(Keysym::k),
                     modifiers: Modifiers::COMPOSITOR | Modifiers::SUPER,
                 },
+                sequence: Vec::new(),
                 action: Action::FocusWindowUp,
                 repeat: true,
+                on_release: false,
+                tap: false,
                 cooldown: None,
                 allow_when_locked: false,
                 allow_inhibiting: true,
@@ -5518,8 +6555,11 @@ mod tests {
                     trigger: Trigger::Keysym(Keysym::l),
                     modifiers: Modifiers::SUPER | Modifiers::ALT,
                 },
+                sequence: Vec::new(),
                 action: Action::FocusColumnRight,
                 repeat: true,
+                on_release: false,
+                tap: false,
                 cooldown: None,
                 allow_when_locked: false,
                 allow_inhibiting: true,
@@ -5646,4 +6686,140 @@ mod tests {
             None,
         );
     }
+
+    #[test]
+    fn bind_sequences() {
+        let config = Config::parse_mem(
+            r#"
+            binds {
+                Mod+Space W H { focus-column-left; }
+                Mod+Space W L { focus-column-right; }
+                Mod+Space T { spawn "alacritty"; }
+            }
+            "#,
+        )
+        .unwrap();
+        let bindings = &config.binds.0;
+
+        let logo = ModifiersState {
+            logo: true,
+            ..Default::default()
+        };
+        let none = ModifiersState::default();
+
+        // Sequences don't trigger as regular binds.
+        assert_eq!(
+            find_configured_bind(
+                bindings,
+                ModKey::Super,
+                Trigger::Keysym(Keysym::space),
+                logo
+            ),
+            None,
+        );
+
+        let SequenceMatch::Prefix(first) = match_bind_sequence(
+            bindings,
+            ModKey::Super,
+            &[],
+            Trigger::Keysym(Keysym::space),
+            logo,
+        ) else {
+            panic!("expected the start of a sequence");
+        };
+        assert_eq!(first, bindings[0].key);
+
+        let SequenceMatch::Prefix(second) = match_bind_sequence(
+            bindings,
+            ModKey::Super,
+            &[first],
+            Trigger::Keysym(Keysym::w),
+            none,
+        ) else {
+            panic!("expected the sequence to continue");
+        };
+
+        let SequenceMatch::Complete(bind) = match_bind_sequence(
+            bindings,
+            ModKey::Super,
+            &[first, second],
+            Trigger::Keysym(Keysym::l),
+            none,
+        ) else {
+            panic!("expected the sequence to complete");
+        };
+        assert_eq!(bind.action, Action::FocusColumnRight);
+
+        // Mod is no longer held for the continuation.
+        assert!(matches!(
+            match_bind_sequence(
+                bindings,
+                ModKey::Super,
+                &[first],
+                Trigger::Keysym(Keysym::w),
+                logo,
+            ),
+            SequenceMatch::None
+        ));
+    }
+
+    #[test]
+    fn modifier_key_binds() {
+        let config = Config::parse_mem(
+            r#"
+            binds {
+                Super_L tap=true { toggle-overview; }
+                Mod+Shift+Super_L { close-window; }
+                Super_R { spawn "fuzzel"; }
+                Mod+Super_R { spawn "alacritty"; }
+            }
+            "#,
+        )
+        .unwrap();
+        let bindings = &config.binds.0;
+
+        // Super is already down when the press of Super_L is handled. Tap binds ignore it.
+        let bind = find_configured_bind(
+            bindings,
+            ModKey::Super,
+            Trigger::Keysym(Keysym::Super_L),
+            ModifiersState {
+                logo: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(bind.as_ref(), Some(&bindings[0]));
+
+        let bind = find_configured_bind(
+            bindings,
+            ModKey::Super,
+            Trigger::Keysym(Keysym::Super_L),
+            ModifiersState {
+                logo: true,
+                shift: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(bind.as_ref(), Some(&bindings[1]));
+
+        // Regular binds on modifier keys must include the modifier.
+        let bind = find_configured_bind(
+            bindings,
+            ModKey::Super,
+            Trigger::Keysym(Keysym::Super_R),
+            ModifiersState {
+                logo: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(bind.as_ref(), Some(&bindings[3]));
+
+        let bind = find_configured_bind(
+            bindings,
+            ModKey::Super,
+            Trigger::Keysym(Keysym::Super_R),
+            ModifiersState::default(),
+        );
+        assert_eq!(bind.as_ref(), Some(&bindings[2]));
+    }
 }
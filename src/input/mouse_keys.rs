@@ -0,0 +1,460 @@
+use niri_config::input::{EvdevKey, MouseKeys as MouseKeysConfig};
+use smithay::backend::input::{
+    ButtonState, Device, DeviceCapability, Event, InputBackend, InputEvent, Keycode,
+    PointerButtonEvent, PointerMotionEvent, UnusedEvent,
+};
+use smithay::reexports::calloop::RegistrationToken;
+
+const BTN_LEFT: u32 = 0x110;
+const BTN_RIGHT: u32 = 0x111;
+const BTN_MIDDLE: u32 = 0x112;
+
+/// Mouse keys: moving the pointer and clicking with the keyboard.
+#[derive(Debug)]
+pub struct MouseKeys {
+    pub is_on: bool,
+    /// Keys taken over by mouse keys, so that their releases are taken over too.
+    held: Vec<(Keycode, HeldKey)>,
+    /// Button that the click and press keys press.
+    button: u32,
+    /// Buttons held down by mouse keys.
+    pressed_buttons: Vec<u32>,
+    /// Times in milliseconds when the pointer started moving and when it last moved.
+    motion: Option<(u32, u32)>,
+    /// Timer moving the pointer while direction keys are held.
+    pub timer: Option<RegistrationToken>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HeldKey {
+    Move {
+        dx: i8,
+        dy: i8,
+    },
+    /// Click key holding down this button.
+    Click(u32),
+    Other,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KeyAction {
+    Move { dx: i8, dy: i8 },
+    Click,
+    Press,
+    Release,
+    SelectButton(u32),
+}
+
+/// Pointer event produced by mouse keys.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MouseKeysEvent {
+    Motion { dx: f64, dy: f64 },
+    Button { button: u32, state: ButtonState },
+}
+
+impl MouseKeys {
+    pub fn new(config: &MouseKeysConfig) -> Self {
+        Self {
+            is_on: config.on,
+            held: Vec::new(),
+            button: BTN_LEFT,
+            pressed_buttons: Vec::new(),
+            motion: None,
+            timer: None,
+        }
+    }
+
+    /// Returns the pointer events for a key event, or `None` if mouse keys don't handle the key.
+    pub fn process(
+        &mut self,
+        config: &MouseKeysConfig,
+        key: Keycode,
+        pressed: bool,
+        time: u32,
+    ) -> Option<Vec<MouseKeysEvent>> {
+        let mut events = Vec::new();
+
+        if !pressed {
+            // Releases are handled even with mouse keys off, if the press was.
+            let idx = self.held.iter().position(|(k, _)| *k == key)?;
+            match self.held.remove(idx).1 {
+                HeldKey::Move { .. } => {
+                    if !self.is_moving_held() {
+                        self.motion = None;
+                    }
+                }
+                HeldKey::Click(button) => self.release_button(button, &mut events),
+                HeldKey::Other => (),
+            }
+            return Some(events);
+        }
+
+        if !self.is_on {
+            return None;
+        }
+
+        let action = find_key(config, EvdevKey(key.raw() - 8))?;
+        self.held.retain(|(k, _)| *k != key);
+
+        let held = match action {
+            KeyAction::Move { dx, dy } => {
+                if self.motion.is_none() {
+                    self.motion = Some((time, time));
+                }
+
+                // Move by a pixel right away, so that tapping the key allows precise positioning.
+                events.push(MouseKeysEvent::Motion {
+                    dx: f64::from(dx),
+                    dy: f64::from(dy),
+                });
+                HeldKey::Move { dx, dy }
+            }
+            KeyAction::Click => {
+                self.press_button(self.button, &mut events);
+                HeldKey::Click(self.button)
+            }
+            KeyAction::Press => {
+                self.press_button(self.button, &mut events);
+                HeldKey::Other
+            }
+            KeyAction::Release => {
+                for button in std::mem::take(&mut self.pressed_buttons) {
+                    events.push(MouseKeysEvent::Button {
+                        button,
+                        state: ButtonState::Released,
+                    });
+                }
+                HeldKey::Other
+            }
+            KeyAction::SelectButton(button) => {
+                self.button = button;
+                HeldKey::Other
+            }
+        };
+        self.held.push((key, held));
+
+        Some(events)
+    }
+
+    /// Returns the pointer motion since the last call.
+    ///
+    /// Returns `None` if the pointer isn't moving, or if opposite keys cancel out. Use
+    /// [`Self::is_moving()`] to tell these apart.
+    pub fn motion(&mut self, config: &MouseKeysConfig, time: u32) -> Option<MouseKeysEvent> {
+        let (start, last) = self.motion.as_mut()?;
+
+        let (mut x, mut y) = (0., 0.);
+        for (_, held) in &self.held {
+            if let HeldKey::Move { dx, dy } = held {
+                x += f64::from(*dx);
+                y += f64::from(*dy);
+            }
+        }
+
+        let elapsed = f64::from(time.wrapping_sub(*start));
+        let dt = f64::from(time.wrapping_sub(*last));
+        *last = time;
+
+        // Opposite keys cancel out.
+        let len = x.hypot(y);
+        if len == 0. {
+            return None;
+        }
+
+        let progress = if config.acceleration_ms == 0 {
+            1.
+        } else {
+            (elapsed / f64::from(config.acceleration_ms)).min(1.)
+        };
+        let speed = f64::from(config.speed);
+        let speed = speed + (f64::from(config.max_speed) - speed) * progress;
+        let distance = speed * dt / 1000.;
+
+        Some(MouseKeysEvent::Motion {
+            dx: x / len * distance,
+            dy: y / len * distance,
+        })
+    }
+
+    pub fn is_moving(&self) -> bool {
+        self.motion.is_some()
+    }
+
+    /// Turns mouse keys on or off, returning the pointer events to release held buttons.
+    pub fn set_on(&mut self, on: bool) -> Vec<MouseKeysEvent> {
+        self.is_on = on;
+        if on {
+            return Vec::new();
+        }
+
+        self.motion = None;
+
+        let mut events = Vec::new();
+        for button in std::mem::take(&mut self.pressed_buttons) {
+            events.push(MouseKeysEvent::Button {
+                button,
+                state: ButtonState::Released,
+            });
+        }
+        events
+    }
+
+    fn is_moving_held(&self) -> bool {
+        self.held
+            .iter()
+            .any(|(_, held)| matches!(held, HeldKey::Move { .. }))
+    }
+
+    fn press_button(&mut self, button: u32, events: &mut Vec<MouseKeysEvent>) {
+        if self.pressed_buttons.contains(&button) {
+            return;
+        }
+
+        self.pressed_buttons.push(button);
+        events.push(MouseKeysEvent::Button {
+            button,
+            state: ButtonState::Pressed,
+        });
+    }
+
+    fn release_button(&mut self, button: u32, events: &mut Vec<MouseKeysEvent>) {
+        let Some(idx) = self.pressed_buttons.iter().position(|b| *b == button) else {
+            return;
+        };
+
+        self.pressed_buttons.remove(idx);
+        events.push(MouseKeysEvent::Button {
+            button,
+            state: ButtonState::Released,
+        });
+    }
+}
+
+fn find_key(config: &MouseKeysConfig, key: EvdevKey) -> Option<KeyAction> {
+    let keys = [
+        (config.up, KeyAction::Move { dx: 0, dy: -1 }),
+        (config.down, KeyAction::Move { dx: 0, dy: 1 }),
+        (config.left, KeyAction::Move { dx: -1, dy: 0 }),
+        (config.right, KeyAction::Move { dx: 1, dy: 0 }),
+        (config.up_left, KeyAction::Move { dx: -1, dy: -1 }),
+        (config.up_right, KeyAction::Move { dx: 1, dy: -1 }),
+        (config.down_left, KeyAction::Move { dx: -1, dy: 1 }),
+        (config.down_right, KeyAction::Move { dx: 1, dy: 1 }),
+        (config.click, KeyAction::Click),
+        (config.press, KeyAction::Press),
+        (config.release, KeyAction::Release),
+        (config.left_button, KeyAction::SelectButton(BTN_LEFT)),
+        (config.middle_button, KeyAction::SelectButton(BTN_MIDDLE)),
+        (config.right_button, KeyAction::SelectButton(BTN_RIGHT)),
+    ];
+
+    keys.into_iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, action)| action)
+}
+
+impl MouseKeysEvent {
+    pub fn into_input_event(self, time: u32) -> InputEvent<MouseKeysInputBackend> {
+        match self {
+            Self::Motion { dx, dy } => InputEvent::PointerMotion {
+                event: MouseKeysMotionEvent { time, dx, dy },
+            },
+            Self::Button { button, state } => InputEvent::PointerButton {
+                event: MouseKeysButtonEvent {
+                    time,
+                    button,
+                    state,
+                },
+            },
+        }
+    }
+}
+
+/// Input backend for the pointer events of mouse keys, feeding them to the regular pointer
+/// handling like a virtual pointer.
+pub struct MouseKeysInputBackend;
+
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub struct MouseKeysDevice;
+
+impl Device for MouseKeysDevice {
+    fn id(&self) -> String {
+        String::from("mouse keys")
+    }
+
+    fn name(&self) -> String {
+        String::from("mouse keys")
+    }
+
+    fn has_capability(&self, capability: DeviceCapability) -> bool {
+        matches!(capability, DeviceCapability::Pointer)
+    }
+
+    fn usb_id(&self) -> Option<(u32, u32)> {
+        None
+    }
+
+    fn syspath(&self) -> Option<std::path::PathBuf> {
+        None
+    }
+}
+
+pub struct MouseKeysMotionEvent {
+    time: u32,
+    dx: f64,
+    dy: f64,
+}
+
+impl Event<MouseKeysInputBackend> for MouseKeysMotionEvent {
+    fn time(&self) -> u64 {
+        self.time as u64 * 1000 // millis to micros
+    }
+
+    fn device(&self) -> MouseKeysDevice {
+        MouseKeysDevice
+    }
+}
+
+impl PointerMotionEvent<MouseKeysInputBackend> for MouseKeysMotionEvent {
+    fn delta_x(&self) -> f64 {
+        self.dx
+    }
+
+    fn delta_y(&self) -> f64 {
+        self.dy
+    }
+
+    fn delta_x_unaccel(&self) -> f64 {
+        self.dx
+    }
+
+    fn delta_y_unaccel(&self) -> f64 {
+        self.dy
+    }
+}
+
+pub struct MouseKeysButtonEvent {
+    time: u32,
+    button: u32,
+    state: ButtonState,
+}
+
+impl Event<MouseKeysInputBackend> for MouseKeysButtonEvent {
+    fn time(&self) -> u64 {
+        self.time as u64 * 1000 // millis to micros
+    }
+
+    fn device(&self) -> MouseKeysDevice {
+        MouseKeysDevice
+    }
+}
+
+impl PointerButtonEvent<MouseKeysInputBackend> for MouseKeysButtonEvent {
+    fn button_code(&self) -> u32 {
+        self.button
+    }
+
+    fn state(&self) -> ButtonState {
+        self.state
+    }
+}
+
+impl InputBackend for MouseKeysInputBackend {
+    type Device = MouseKeysDevice;
+
+    type KeyboardKeyEvent = UnusedEvent;
+    type PointerAxisEvent = UnusedEvent;
+    type PointerButtonEvent = MouseKeysButtonEvent;
+    type PointerMotionEvent = MouseKeysMotionEvent;
+    type PointerMotionAbsoluteEvent = UnusedEvent;
+
+    type GestureSwipeBeginEvent = UnusedEvent;
+    type GestureSwipeUpdateEvent = UnusedEvent;
+    type GestureSwipeEndEvent = UnusedEvent;
+    type GesturePinchBeginEvent = UnusedEvent;
+    type GesturePinchUpdateEvent = UnusedEvent;
+    type GesturePinchEndEvent = UnusedEvent;
+    type GestureHoldBeginEvent = UnusedEvent;
+    type GestureHoldEndEvent = UnusedEvent;
+
+    type TouchDownEvent = UnusedEvent;
+    type TouchUpEvent = UnusedEvent;
+    type TouchMotionEvent = UnusedEvent;
+    type TouchCancelEvent = UnusedEvent;
+    type TouchFrameEvent = UnusedEvent;
+    type TabletToolAxisEvent = UnusedEvent;
+    type TabletToolProximityEvent = UnusedEvent;
+    type TabletToolTipEvent = UnusedEvent;
+    type TabletToolButtonEvent = UnusedEvent;
+
+    type SwitchToggleEvent = UnusedEvent;
+
+    type SpecialEvent = UnusedEvent;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mouse_keys() {
+        let config = MouseKeysConfig::default();
+        let kp4 = Keycode::from(75 + 8);
+        let kp6 = Keycode::from(77 + 8);
+        let kp5 = Keycode::from(76 + 8);
+        let kp_minus = Keycode::from(74 + 8);
+        let a = Keycode::from(30 + 8);
+
+        let button = |button, state| MouseKeysEvent::Button { button, state };
+        let motion = |dx, dy| MouseKeysEvent::Motion { dx, dy };
+
+        let mut mouse_keys = MouseKeys::new(&config);
+        assert_eq!(mouse_keys.process(&config, kp6, true, 0), None);
+
+        mouse_keys.set_on(true);
+        assert_eq!(mouse_keys.process(&config, a, true, 0), None);
+
+        // Direction keys nudge the pointer right away, then accelerate.
+        assert_eq!(
+            mouse_keys.process(&config, kp6, true, 0),
+            Some(vec![motion(1., 0.)])
+        );
+        assert_eq!(mouse_keys.motion(&config, 100), Some(motion(30., 0.)));
+        assert_eq!(mouse_keys.motion(&config, 2000), Some(motion(2280., 0.)));
+        assert_eq!(mouse_keys.process(&config, kp6, false, 2010), Some(vec![]));
+        assert_eq!(mouse_keys.motion(&config, 2020), None);
+        assert!(!mouse_keys.is_moving());
+
+        // Opposite keys cancel out without stopping the motion.
+        mouse_keys.process(&config, kp6, true, 3000);
+        mouse_keys.process(&config, kp4, true, 3000);
+        assert_eq!(mouse_keys.motion(&config, 3100), None);
+        assert!(mouse_keys.is_moving());
+        mouse_keys.process(&config, kp4, false, 3100);
+        mouse_keys.process(&config, kp6, false, 3100);
+        assert!(!mouse_keys.is_moving());
+
+        // Clicks use the selected button.
+        assert_eq!(
+            mouse_keys.process(&config, kp5, true, 0),
+            Some(vec![button(0x110, ButtonState::Pressed)])
+        );
+        assert_eq!(
+            mouse_keys.process(&config, kp5, false, 0),
+            Some(vec![button(0x110, ButtonState::Released)])
+        );
+        assert_eq!(mouse_keys.process(&config, kp_minus, true, 0), Some(vec![]));
+        assert_eq!(
+            mouse_keys.process(&config, kp5, true, 0),
+            Some(vec![button(0x111, ButtonState::Pressed)])
+        );
+
+        // Turning mouse keys off releases the buttons, and the keys held before.
+        assert_eq!(
+            mouse_keys.set_on(false),
+            [button(0x111, ButtonState::Released)]
+        );
+        assert_eq!(mouse_keys.process(&config, kp5, false, 0), Some(vec![]));
+        assert_eq!(mouse_keys.process(&config, kp5, false, 0), None);
+    }
+}
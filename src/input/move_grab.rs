@@ -201,8 +201,16 @@ impl MoveGrab {
                     })
                     .unwrap_or(false);
 
-                let is_view_offset =
-                    self.enable_view_offset && !is_floating && c.x.abs() > c.y.abs();
+                let along_view_axis = if data
+                    .niri
+                    .layout
+                    .is_view_offset_gesture_vertical(&self.start_output)
+                {
+                    c.y.abs() > c.x.abs()
+                } else {
+                    c.x.abs() > c.y.abs()
+                };
+                let is_view_offset = self.enable_view_offset && !is_floating && along_view_axis;
 
                 let started = if is_view_offset {
                     self.begin_view_offset(data)
@@ -243,11 +251,13 @@ impl MoveGrab {
                 }
             }
             GestureState::ViewOffset => {
-                let res = data.niri.layout.view_offset_gesture_update(
-                    -relative_delta.x,
-                    timestamp,
-                    false,
-                );
+                let layout = &mut data.niri.layout;
+                let delta = if layout.is_view_offset_gesture_vertical(&self.start_output) {
+                    relative_delta.y
+                } else {
+                    relative_delta.x
+                };
+                let res = layout.view_offset_gesture_update(-delta, timestamp, false);
                 if let Some(output) = res {
                     if let Some(output) = output {
                         data.niri.queue_redraw(&output);
@@ -80,18 +80,24 @@ impl SpatialMovementGrab {
         self.last_location = self.new_location;
 
         let layout = &mut data.niri.layout;
+
+        // With vertical scrolling orientation, the gesture axes are swapped.
+        let vertical = layout.is_view_offset_gesture_vertical(&self.output);
+        let orient = |p: Point<f64, Logical>| if vertical { (p.y, p.x) } else { (p.x, p.y) };
+
         let res = match self.gesture {
             GestureState::Recognizing => {
                 let c = self.new_location - self.start_data.location;
+                let (view_c, workspace_c) = orient(c);
 
                 // Check if the gesture moved far enough to decide. Threshold copied from GTK 4.
                 if c.x * c.x + c.y * c.y >= 8. * 8. {
-                    if c.x.abs() > c.y.abs() {
+                    if view_c.abs() > workspace_c.abs() {
                         self.gesture = GestureState::ViewOffset;
                         if let Some((ws_idx, ws)) = layout.find_workspace_by_id(self.workspace_id) {
                             if ws.current_output() == Some(&self.output) {
                                 layout.view_offset_gesture_begin(&self.output, Some(ws_idx), false);
-                                layout.view_offset_gesture_update(-c.x, timestamp, false)
+                                layout.view_offset_gesture_update(-view_c, timestamp, false)
                             } else {
                                 None
                             }
@@ -101,17 +107,19 @@ impl SpatialMovementGrab {
                     } else {
                         self.gesture = GestureState::WorkspaceSwitch;
                         layout.workspace_switch_gesture_begin(&self.output, false);
-                        layout.workspace_switch_gesture_update(-c.y, timestamp, false)
+                        layout.workspace_switch_gesture_update(-workspace_c, timestamp, false)
                     }
                 } else {
                     Some(None)
                 }
             }
             GestureState::ViewOffset => {
-                layout.view_offset_gesture_update(-delta.x, timestamp, false)
+                let (view_delta, _) = orient(delta);
+                layout.view_offset_gesture_update(-view_delta, timestamp, false)
             }
             GestureState::WorkspaceSwitch => {
-                layout.workspace_switch_gesture_update(-delta.y, timestamp, false)
+                let (_, workspace_delta) = orient(delta);
+                layout.workspace_switch_gesture_update(-workspace_delta, timestamp, false)
             }
         };
 
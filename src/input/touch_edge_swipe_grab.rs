@@ -0,0 +1,276 @@
+use std::time::Duration;
+
+use niri_config::gestures::GestureAction;
+use smithay::input::touch::{
+    DownEvent, GrabStartData as TouchGrabStartData, MotionEvent, OrientationEvent, ShapeEvent,
+    TouchGrab, TouchInnerHandle, UpEvent,
+};
+use smithay::input::SeatHandler;
+use smithay::output::Output;
+use smithay::utils::{Logical, Point, Size};
+
+use crate::niri::State;
+
+/// Edge of an output that a touch swipes in from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScreenEdge {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+type TouchFocus = <State as SeatHandler>::TouchFocus;
+
+pub struct TouchEdgeSwipeGrab {
+    start_data: TouchGrabStartData<State>,
+    output: Output,
+    edge: ScreenEdge,
+    action: GestureAction,
+    gesture: GestureState,
+    /// Touch down held back from the clients until it's clear that this isn't a swipe.
+    pending_down: Option<(Option<(TouchFocus, Point<f64, Logical>)>, DownEvent)>,
+    last_location: Point<f64, Logical>,
+
+    // Accumulated and applied in frame().
+    new_location: Point<f64, Logical>,
+    event_timestamp: Option<Duration>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum GestureState {
+    Recognizing,
+    Interactive,
+    /// The swipe triggered its action and is ignored until it ends.
+    Done,
+}
+
+impl TouchEdgeSwipeGrab {
+    pub fn new(
+        start_data: TouchGrabStartData<State>,
+        output: Output,
+        edge: ScreenEdge,
+        action: GestureAction,
+    ) -> Self {
+        let location = start_data.location;
+
+        Self {
+            start_data,
+            output,
+            edge,
+            action,
+            gesture: GestureState::Recognizing,
+            pending_down: None,
+            last_location: location,
+            new_location: location,
+            event_timestamp: None,
+        }
+    }
+
+    /// Returns the movement away from the edge, into the output.
+    fn inward(&self, delta: Point<f64, Logical>) -> f64 {
+        match self.edge {
+            ScreenEdge::Left => delta.x,
+            ScreenEdge::Right => -delta.x,
+            ScreenEdge::Top => delta.y,
+            ScreenEdge::Bottom => -delta.y,
+        }
+    }
+
+    fn on_frame(&mut self, data: &mut State) -> bool {
+        let Some(timestamp) = self.event_timestamp.take() else {
+            return true;
+        };
+
+        let layout = &mut data.niri.layout;
+
+        if matches!(self.gesture, GestureState::Recognizing) {
+            let c = self.new_location - self.start_data.location;
+
+            // Check if the gesture moved far enough to decide. Threshold copied from libadwaita.
+            if c.x * c.x + c.y * c.y >= 16. * 16. {
+                // The touch is a swipe, so the clients never see it.
+                self.pending_down = None;
+
+                match &self.action {
+                    GestureAction::ViewScroll => {
+                        layout.view_offset_gesture_begin(&self.output, None, false);
+                        self.gesture = GestureState::Interactive;
+                    }
+                    GestureAction::WorkspaceSwitch => {
+                        layout.workspace_switch_gesture_begin(&self.output, false);
+                        self.gesture = GestureState::Interactive;
+                    }
+                    GestureAction::Overview => {
+                        layout.overview_gesture_begin();
+                        self.gesture = GestureState::Interactive;
+                    }
+                    GestureAction::Action(action) => {
+                        // Actions cancel the touch, which can't happen from within the grab.
+                        let action = action.clone();
+                        data.niri
+                            .event_loop
+                            .insert_idle(move |state| state.do_action(action, false));
+                        self.gesture = GestureState::Done;
+                    }
+                }
+            }
+        }
+
+        if !matches!(self.gesture, GestureState::Interactive) {
+            return true;
+        }
+
+        let delta = self.new_location - self.last_location;
+        self.last_location = self.new_location;
+
+        let layout = &mut data.niri.layout;
+        let ongoing = match self.action {
+            GestureAction::ViewScroll => layout
+                .view_offset_gesture_update(-delta.x, timestamp, false)
+                .is_some(),
+            GestureAction::WorkspaceSwitch => layout
+                .workspace_switch_gesture_update(-delta.y, timestamp, false)
+                .is_some(),
+            GestureAction::Overview => layout
+                .overview_gesture_update(self.inward(delta), timestamp)
+                .is_some(),
+            GestureAction::Action(_) => unreachable!(),
+        };
+
+        if ongoing {
+            data.niri.queue_redraw_all();
+        }
+
+        ongoing
+    }
+
+    fn on_ungrab(&mut self, state: &mut State) {
+        if !matches!(self.gesture, GestureState::Interactive) {
+            return;
+        }
+
+        let layout = &mut state.niri.layout;
+        match self.action {
+            GestureAction::ViewScroll => {
+                layout.view_offset_gesture_end(Some(false));
+            }
+            GestureAction::WorkspaceSwitch => {
+                layout.workspace_switch_gesture_end(Some(false));
+            }
+            GestureAction::Overview => {
+                layout.overview_gesture_end();
+            }
+            GestureAction::Action(_) => unreachable!(),
+        }
+
+        state.niri.queue_redraw_all();
+    }
+}
+
+impl TouchGrab<State> for TouchEdgeSwipeGrab {
+    fn down(
+        &mut self,
+        data: &mut State,
+        handle: &mut TouchInnerHandle<'_, State>,
+        focus: Option<(<State as SeatHandler>::TouchFocus, Point<f64, Logical>)>,
+        event: &DownEvent,
+    ) {
+        if event.slot == self.start_data.slot {
+            self.pending_down = Some((focus, event.clone()));
+            return;
+        }
+
+        handle.down(data, None, event);
+    }
+
+    fn up(&mut self, data: &mut State, handle: &mut TouchInnerHandle<'_, State>, event: &UpEvent) {
+        if event.slot != self.start_data.slot {
+            handle.up(data, event);
+            return;
+        }
+
+        // The touch lifted without swiping, so let the clients have the tap.
+        if let Some((focus, down)) = self.pending_down.take() {
+            handle.down(data, focus, &down);
+            handle.frame(data);
+        }
+
+        handle.up(data, event);
+        handle.unset_grab(self, data);
+    }
+
+    fn motion(
+        &mut self,
+        data: &mut State,
+        handle: &mut TouchInnerHandle<'_, State>,
+        _focus: Option<(<State as SeatHandler>::TouchFocus, Point<f64, Logical>)>,
+        event: &MotionEvent,
+    ) {
+        if event.slot != self.start_data.slot {
+            handle.motion(data, None, event);
+            return;
+        }
+
+        self.new_location = event.location;
+        self.event_timestamp = Some(Duration::from_millis(u64::from(event.time)));
+    }
+
+    fn frame(&mut self, data: &mut State, handle: &mut TouchInnerHandle<'_, State>) {
+        handle.frame(data);
+
+        if !self.on_frame(data) {
+            // The gesture is no longer ongoing.
+            handle.unset_grab(self, data);
+        }
+    }
+
+    fn cancel(&mut self, data: &mut State, handle: &mut TouchInnerHandle<'_, State>) {
+        handle.cancel(data);
+        handle.unset_grab(self, data);
+    }
+
+    fn shape(
+        &mut self,
+        data: &mut State,
+        handle: &mut TouchInnerHandle<'_, State>,
+        event: &ShapeEvent,
+    ) {
+        handle.shape(data, event);
+    }
+
+    fn orientation(
+        &mut self,
+        data: &mut State,
+        handle: &mut TouchInnerHandle<'_, State>,
+        event: &OrientationEvent,
+    ) {
+        handle.orientation(data, event);
+    }
+
+    fn start_data(&self) -> &TouchGrabStartData<State> {
+        &self.start_data
+    }
+
+    fn unset(&mut self, data: &mut State) {
+        self.on_ungrab(data);
+    }
+}
+
+/// Returns the edge closest to `pos` within an output, if it is within `width` of it.
+pub fn edge_at(
+    pos: Point<f64, Logical>,
+    output_size: Size<f64, Logical>,
+    width: f64,
+) -> Option<ScreenEdge> {
+    [
+        (ScreenEdge::Left, pos.x),
+        (ScreenEdge::Right, output_size.w - pos.x),
+        (ScreenEdge::Top, pos.y),
+        (ScreenEdge::Bottom, output_size.h - pos.y),
+    ]
+    .into_iter()
+    .filter(|(_, distance)| *distance < width)
+    .min_by(|(_, a), (_, b)| a.total_cmp(b))
+    .map(|(edge, _)| edge)
+}
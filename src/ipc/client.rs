@@ -7,8 +7,8 @@ use anyhow::{anyhow, bail, Context};
 use niri_config::OutputName;
 use niri_ipc::socket::Socket;
 use niri_ipc::{
-    Action, Cast, CastKind, CastTarget, Event, KeyboardLayouts, LogicalOutput, Mode, Output,
-    OutputConfigChanged, Overview, Request, Response, Transform, Window, WindowLayout,
+    Action, Cast, CastKind, CastTarget, Event, KeyboardLayouts, LogicalOutput, Mode, MouseKeys,
+    Output, OutputConfigChanged, Overview, Request, Response, Transform, Window, WindowLayout,
 };
 use serde_json::json;
 
@@ -48,6 +48,7 @@ pub fn handle_msg(mut msg: Msg, json: bool) -> anyhow::Result<()> {
         Msg::EventStream => Request::EventStream,
         Msg::RequestError => Request::ReturnError,
         Msg::OverviewState => Request::OverviewState,
+        Msg::MouseKeysState => Request::MouseKeysState,
         Msg::Casts => Request::Casts,
     };
 
@@ -399,10 +400,18 @@ pub fn handle_msg(mut msg: Msg, json: bool) -> anyhow::Result<()> {
                 return Ok(());
             }
 
-            let KeyboardLayouts { names, current_idx } = response;
+            let KeyboardLayouts {
+                names,
+                current_idx,
+                device,
+            } = response;
             let current_idx = usize::from(current_idx);
 
-            println!("Keyboard layouts:");
+            if let Some(device) = device {
+                println!("Keyboard layouts of {device}:");
+            } else {
+                println!("Keyboard layouts:");
+            }
             for (idx, name) in names.iter().enumerate() {
                 let is_active = if idx == current_idx { " * " } else { "   " };
                 println!("{is_active}{idx} {name}");
@@ -480,6 +489,15 @@ pub fn handle_msg(mut msg: Msg, json: bool) -> anyhow::Result<()> {
                     Event::OverviewOpenedOrClosed { is_open: opened } => {
                         println!("Overview toggled: {opened}");
                     }
+                    Event::MouseKeysToggled { is_on } => {
+                        println!("Mouse keys toggled: {is_on}");
+                    }
+                    Event::BindModeChanged { name } => {
+                        println!("Bind mode changed: {name:?}");
+                    }
+                    Event::AppBindsChanged { name } => {
+                        println!("App binds changed: {name:?}");
+                    }
                     Event::ConfigLoaded { failed } => {
                         let status = if failed {
                             "with an error"
@@ -528,6 +546,25 @@ pub fn handle_msg(mut msg: Msg, json: bool) -> anyhow::Result<()> {
                 println!("Overview is closed.");
             }
         }
+        Msg::MouseKeysState => {
+            let Response::MouseKeysState(response) = response else {
+                bail!("unexpected response: expected MouseKeys, got {response:?}");
+            };
+
+            if json {
+                let response =
+                    serde_json::to_string(&response).context("error formatting response")?;
+                println!("{response}");
+                return Ok(());
+            }
+
+            let MouseKeys { is_on } = response;
+            if is_on {
+                println!("Mouse keys are on.");
+            } else {
+                println!("Mouse keys are off.");
+            }
+        }
         Msg::Casts => {
             let Response::Casts(mut casts) = response else {
                 bail!("unexpected response: expected Casts, got {response:?}");
@@ -696,6 +733,20 @@ fn print_window(window: &Window) {
         if window.is_floating { "yes" } else { "no" }
     );
 
+    println!(
+        "  Is sticky: {}",
+        if window.is_sticky { "yes" } else { "no" }
+    );
+
+    println!(
+        "  Is minimized: {}",
+        if window.is_minimized { "yes" } else { "no" }
+    );
+
+    if let Some(mark) = &window.mark {
+        println!("  Mark: \"{mark}\"");
+    }
+
     if let Some(pid) = window.pid {
         println!("  PID: {pid}");
     } else {
@@ -1,10 +1,11 @@
 use std::cell::RefCell;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::ffi::OsStr;
 use std::os::unix::net::{UnixListener, UnixStream};
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use std::{env, io, process};
 
 use anyhow::Context;
@@ -14,18 +15,20 @@ use calloop::io::Async;
 use directories::BaseDirs;
 use futures_util::io::{AsyncReadExt, BufReader};
 use futures_util::{select_biased, AsyncBufReadExt, AsyncWrite, AsyncWriteExt, FutureExt as _};
-use niri_config::OutputName;
+use niri_config::hooks::{HookCommand, HookEvent, HookMatch};
+use niri_config::{Config, OutputName};
 use niri_ipc::state::{EventStreamState, EventStreamStatePart as _};
 use niri_ipc::{
-    Action, Event, KeyboardLayouts, OutputConfigChanged, Overview, Reply, Request, Response,
-    Timestamp, WindowLayout, Workspace,
+    Action, Event, KeyboardLayouts, MouseKeys, OutputConfigChanged, Overview, Reply, Request,
+    Response, Timestamp, Window, WindowLayout, Workspace,
 };
 use smithay::desktop::layer_map_for_output;
 use smithay::input::pointer::{
     CursorIcon, CursorImageStatus, Focus, GrabStartData as PointerGrabStartData,
 };
 use smithay::reexports::calloop::generic::Generic;
-use smithay::reexports::calloop::{Interest, LoopHandle, Mode, PostAction};
+use smithay::reexports::calloop::timer::{TimeoutAction, Timer};
+use smithay::reexports::calloop::{Interest, LoopHandle, Mode, PostAction, RegistrationToken};
 use smithay::reexports::rustix::fs::unlink;
 use smithay::utils::SERIAL_COUNTER;
 use smithay::wayland::shell::wlr_layer::{KeyboardInteractivity, Layer};
@@ -34,6 +37,7 @@ use crate::backend::IpcOutputMap;
 use crate::input::pick_window_grab::PickWindowGrab;
 use crate::layout::workspace::WorkspaceId;
 use crate::niri::State;
+use crate::utils::spawning::spawn_with_env;
 use crate::utils::{version, with_toplevel_role};
 use crate::window::Mapped;
 
@@ -41,6 +45,10 @@ use crate::window::Mapped;
 // number in our buffer, we drop that event stream client.
 const EVENT_STREAM_BUFFER_SIZE: usize = 64;
 
+// Minimum time between runs of hooks for the same window, for events that can come many times a
+// second, like title changes of a terminal.
+const HOOK_THROTTLE: Duration = Duration::from_secs(1);
+
 pub struct IpcServer {
     /// Path to the IPC socket.
     ///
@@ -48,6 +56,18 @@ pub struct IpcServer {
     pub socket_path: Option<PathBuf>,
     event_streams: Rc<RefCell<Vec<EventStreamSender>>>,
     event_stream_state: Rc<RefCell<EventStreamState>>,
+    /// Config to look up the event hooks in.
+    config: Rc<RefCell<Config>>,
+    event_loop: LoopHandle<'static, State>,
+    /// Throttling state of hooks for frequent events, keyed by event kind and window id.
+    hook_throttles: RefCell<HashMap<(HookEvent, u64), HookThrottle>>,
+}
+
+struct HookThrottle {
+    last_run: Instant,
+    /// Latest event that came in too soon after the last run, to run the hooks with later.
+    pending: Option<(Event, Window)>,
+    timer: Option<RegistrationToken>,
 }
 
 struct ClientCtx {
@@ -73,6 +93,7 @@ impl IpcServer {
     pub fn start(
         event_loop: &LoopHandle<'static, State>,
         wayland_socket_name: Option<&OsStr>,
+        config: Rc<RefCell<Config>>,
     ) -> anyhow::Result<Self> {
         let _span = tracy_client::span!("Ipc::start");
 
@@ -109,10 +130,20 @@ impl IpcServer {
             socket_path,
             event_streams: Rc::new(RefCell::new(Vec::new())),
             event_stream_state: Rc::new(RefCell::new(EventStreamState::default())),
+            config,
+            event_loop: event_loop.clone(),
+            hook_throttles: RefCell::new(HashMap::new()),
         })
     }
 
     fn send_event(&self, event: Event) {
+        self.send_window_event(event, None);
+    }
+
+    /// Sends an event, giving the window it refers to for the hook matchers.
+    fn send_window_event(&self, event: Event, window: Option<&Window>) {
+        self.run_hooks(&event, window);
+
         let mut streams = self.event_streams.borrow_mut();
         let mut to_remove = Vec::new();
         for (idx, stream) in streams.iter_mut().enumerate() {
@@ -134,6 +165,168 @@ impl IpcServer {
             let _ = stream.disconnect.send_blocking(());
         }
     }
+
+    fn run_hooks(&self, event: &Event, window: Option<&Window>) {
+        if let Event::WindowClosed { id } = event {
+            self.flush_hook_throttles(*id);
+        }
+
+        let kind = HookEvent::from(event);
+        let Some(window) = window.filter(|_| kind.is_frequent()) else {
+            self.spawn_hooks(event, window);
+            return;
+        };
+
+        // Frequent events run the hooks right away, then at most once per HOOK_THROTTLE with the
+        // latest event, so that the hooks always see the final state of the window.
+        let key = (kind, window.id);
+        let now = Instant::now();
+        let mut throttles = self.hook_throttles.borrow_mut();
+        match throttles.get_mut(&key) {
+            Some(throttle) if now.saturating_duration_since(throttle.last_run) < HOOK_THROTTLE => {
+                throttle.pending = Some((event.clone(), window.clone()));
+
+                if throttle.timer.is_none() {
+                    let delay = HOOK_THROTTLE - now.saturating_duration_since(throttle.last_run);
+                    let timer = Timer::from_duration(delay);
+                    let token = self
+                        .event_loop
+                        .insert_source(timer, move |_, _, state| {
+                            if let Some(server) = &state.niri.ipc_server {
+                                server.run_pending_hooks(key);
+                            }
+                            TimeoutAction::Drop
+                        })
+                        .unwrap();
+                    throttle.timer = Some(token);
+                }
+            }
+            _ => {
+                let throttle = HookThrottle {
+                    last_run: now,
+                    pending: None,
+                    timer: None,
+                };
+                if let Some(token) = throttles.insert(key, throttle).and_then(|old| old.timer) {
+                    self.event_loop.remove(token);
+                }
+                drop(throttles);
+                self.spawn_hooks(event, Some(window));
+            }
+        }
+    }
+
+    /// Runs the hooks with the latest throttled event.
+    fn run_pending_hooks(&self, key: (HookEvent, u64)) {
+        let pending = {
+            let mut throttles = self.hook_throttles.borrow_mut();
+            let Some(throttle) = throttles.get_mut(&key) else {
+                return;
+            };
+            throttle.timer = None;
+            throttle.last_run = Instant::now();
+            throttle.pending.take()
+        };
+
+        if let Some((event, window)) = pending {
+            self.spawn_hooks(&event, Some(&window));
+        }
+    }
+
+    /// Runs the hooks with the throttled events of a closing window, and forgets about it.
+    fn flush_hook_throttles(&self, id: u64) {
+        let mut pending = Vec::new();
+        let mut throttles = self.hook_throttles.borrow_mut();
+        throttles.retain(|(_, win), throttle| {
+            if *win != id {
+                return true;
+            }
+
+            if let Some(token) = throttle.timer.take() {
+                self.event_loop.remove(token);
+            }
+            pending.extend(throttle.pending.take());
+            false
+        });
+        drop(throttles);
+
+        for (event, window) in pending {
+            self.spawn_hooks(&event, Some(&window));
+        }
+    }
+
+    fn spawn_hooks(&self, event: &Event, window: Option<&Window>) {
+        let config = self.config.borrow();
+        let mut hooks = config
+            .hooks
+            .for_event(HookEvent::from(event))
+            .filter(|hook| {
+                let matches = |m: &HookMatch| window.is_some_and(|w| hook_matches(m, w));
+                (hook.matches.is_empty() || hook.matches.iter().any(matches))
+                    && !hook.excludes.iter().any(matches)
+            })
+            .peekable();
+
+        if hooks.peek().is_none() {
+            return;
+        }
+
+        let _span = tracy_client::span!("IpcServer::spawn_hooks");
+
+        let json = match serde_json::to_string(event) {
+            Ok(json) => json,
+            Err(err) => {
+                warn!("error serializing event for hooks: {err:?}");
+                return;
+            }
+        };
+
+        for hook in hooks {
+            let env = vec![(String::from("NIRI_EVENT"), json.clone())];
+            match &hook.command {
+                HookCommand::Spawn(command) => spawn_with_env(command.clone(), None, env),
+                HookCommand::SpawnSh(command) => spawn_with_env(
+                    vec![String::from("sh"), String::from("-c"), command.clone()],
+                    None,
+                    env,
+                ),
+            }
+        }
+    }
+}
+
+fn hook_matches(m: &HookMatch, window: &Window) -> bool {
+    if let Some(app_id_re) = &m.app_id {
+        let Some(app_id) = &window.app_id else {
+            return false;
+        };
+        if !app_id_re.0.is_match(app_id) {
+            return false;
+        }
+    }
+
+    if let Some(title_re) = &m.title {
+        let Some(title) = &window.title else {
+            return false;
+        };
+        if !title_re.0.is_match(title) {
+            return false;
+        }
+    }
+
+    if m.is_focused.is_some_and(|x| x != window.is_focused) {
+        return false;
+    }
+
+    if m.is_floating.is_some_and(|x| x != window.is_floating) {
+        return false;
+    }
+
+    if m.is_urgent.is_some_and(|x| x != window.is_urgent) {
+        return false;
+    }
+
+    true
 }
 
 impl Drop for IpcServer {
@@ -381,21 +574,17 @@ async fn process(ctx: &ClientCtx, request: Request) -> Reply {
         Request::Action(action) => {
             validate_action(&action)?;
 
-            let (tx, rx) = async_channel::bounded(1);
-
             let action = niri_config::Action::from(action);
-            ctx.event_loop.insert_idle(move |state| {
-                // Make sure some logic like workspace clean-up has a chance to run before doing
-                // actions.
-                state.niri.advance_animations();
-                state.do_action(action, false);
-                let _ = tx.send_blocking(());
-            });
+            do_action(ctx, action).await;
+            Response::Handled
+        }
+        Request::Actions(actions) => {
+            for action in &actions {
+                validate_action(action)?;
+            }
 
-            // Wait until the action has been processed before returning. This is important for a
-            // few actions, for instance for DoScreenTransition this wait ensures that the screen
-            // contents were sampled into the texture.
-            let _ = rx.recv().await;
+            let actions = actions.into_iter().map(niri_config::Action::from).collect();
+            do_action(ctx, niri_config::Action::Sequence(actions)).await;
             Response::Handled
         }
         Request::Output { output, action } => {
@@ -450,6 +639,11 @@ async fn process(ctx: &ClientCtx, request: Request) -> Reply {
             let is_open = state.overview.is_open;
             Response::OverviewState(Overview { is_open })
         }
+        Request::MouseKeysState => {
+            let state = ctx.event_stream_state.borrow();
+            let is_on = state.mouse_keys.is_on;
+            Response::MouseKeysState(MouseKeys { is_on })
+        }
         Request::Casts => {
             let state = ctx.event_stream_state.borrow();
             let casts = state.casts.casts.values().cloned().collect();
@@ -460,6 +654,23 @@ async fn process(ctx: &ClientCtx, request: Request) -> Reply {
     Ok(response)
 }
 
+async fn do_action(ctx: &ClientCtx, action: niri_config::Action) {
+    let (tx, rx) = async_channel::bounded(1);
+
+    ctx.event_loop.insert_idle(move |state| {
+        // Make sure some logic like workspace clean-up has a chance to run before doing
+        // actions.
+        state.niri.advance_animations();
+        state.do_action(action, false);
+        let _ = tx.send_blocking(());
+    });
+
+    // Wait until the action has been processed before returning. This is important for a few
+    // actions, for instance for DoScreenTransition this wait ensures that the screen contents
+    // were sampled into the texture.
+    let _ = rx.recv().await;
+}
+
 fn validate_action(action: &Action) -> Result<(), String> {
     if let Action::Screenshot { path, .. }
     | Action::ScreenshotScreen { path, .. }
@@ -525,7 +736,10 @@ fn make_ipc_window(
         workspace_id: workspace_id.map(|id| id.get()),
         is_focused: mapped.is_focused(),
         is_floating: mapped.is_floating(),
+        is_sticky: mapped.is_sticky(),
+        is_minimized: mapped.is_minimized(),
         is_urgent: mapped.is_urgent(),
+        mark: mapped.mark().map(String::from),
         layout,
         focus_timestamp: mapped.get_focus_timestamp().map(Timestamp::from),
     })
@@ -534,6 +748,7 @@ fn make_ipc_window(
 impl State {
     pub fn ipc_keyboard_layouts_changed(&mut self) {
         let keyboard = self.niri.seat.get_keyboard().unwrap();
+        let device = self.niri.xkb_device.clone();
         let keyboard_layouts = keyboard.with_xkb_state(self, |context| {
             let xkb = context.xkb().lock().unwrap();
             let layouts = xkb.layouts();
@@ -542,6 +757,7 @@ impl State {
                     .map(|layout| xkb.layout_name(layout).to_owned())
                     .collect(),
                 current_idx: xkb.active_layout().0 as u8,
+                device,
             }
         });
 
@@ -718,8 +934,11 @@ impl State {
             };
 
             let workspace_id = ws_id.map(|id| id.get());
-            let mut changed =
-                ipc_win.workspace_id != workspace_id || ipc_win.is_floating != mapped.is_floating();
+            let mut changed = ipc_win.workspace_id != workspace_id
+                || ipc_win.is_floating != mapped.is_floating()
+                || ipc_win.is_sticky != mapped.is_sticky()
+                || ipc_win.is_minimized != mapped.is_minimized()
+                || ipc_win.mark.as_deref() != mapped.mark();
 
             changed |= with_toplevel_role(mapped.toplevel(), |role| {
                 ipc_win.title != role.title || ipc_win.app_id != role.app_id
@@ -783,8 +1002,25 @@ impl State {
         }
 
         for event in events {
+            // Closing removes the window, so look it up before applying the event. Other events
+            // update the window, so the matchers should see it after.
+            let closed = match &event {
+                Event::WindowClosed { id } => state.windows.get(id).cloned(),
+                _ => None,
+            };
+
             state.apply(event.clone());
-            server.send_event(event);
+
+            let window = match &event {
+                Event::WindowOpenedOrChanged { window } => Some(window.clone()),
+                Event::WindowClosed { .. } => closed,
+                Event::WindowFocusChanged { id: Some(id) }
+                | Event::WindowFocusTimestampChanged { id, .. }
+                | Event::WindowUrgencyChanged { id, .. } => state.windows.get(id).cloned(),
+                _ => None,
+            };
+
+            server.send_window_event(event, window.as_ref());
         }
     }
 
@@ -806,6 +1042,60 @@ impl State {
         server.send_event(event);
     }
 
+    pub fn ipc_refresh_mouse_keys(&mut self) {
+        let Some(server) = &self.niri.ipc_server else {
+            return;
+        };
+
+        let mut state = server.event_stream_state.borrow_mut();
+        let state = &mut state.mouse_keys;
+        let is_on = self.niri.mouse_keys.is_on;
+
+        if state.is_on == is_on {
+            return;
+        }
+
+        let event = Event::MouseKeysToggled { is_on };
+        state.apply(event.clone());
+        server.send_event(event);
+    }
+
+    pub fn ipc_refresh_bind_mode(&mut self) {
+        let Some(server) = &self.niri.ipc_server else {
+            return;
+        };
+
+        let mut state = server.event_stream_state.borrow_mut();
+        let state = &mut state.bind_mode;
+        let name = self.niri.bind_mode.clone();
+
+        if state.name == name {
+            return;
+        }
+
+        let event = Event::BindModeChanged { name };
+        state.apply(event.clone());
+        server.send_event(event);
+    }
+
+    pub fn ipc_refresh_app_binds(&mut self) {
+        let Some(server) = &self.niri.ipc_server else {
+            return;
+        };
+
+        let mut state = server.event_stream_state.borrow_mut();
+        let state = &mut state.app_binds;
+        let name = self.niri.app_binds.clone();
+
+        if state.name == name {
+            return;
+        }
+
+        let event = Event::AppBindsChanged { name };
+        state.apply(event.clone());
+        server.send_event(event);
+    }
+
     pub fn ipc_refresh_casts(&mut self) {
         let Some(server) = &self.niri.ipc_server else {
             return;
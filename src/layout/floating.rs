@@ -4,12 +4,13 @@ use std::rc::Rc;
 
 use niri_config::utils::MergeWith as _;
 use niri_config::{PresetSize, RelativeTo};
-use niri_ipc::{PositionChange, SizeChange, WindowLayout};
+use niri_ipc::{PositionChange, SizeChange, SnapZone, WindowLayout};
 use smithay::backend::renderer::gles::GlesRenderer;
 use smithay::utils::{Logical, Point, Rectangle, Scale, Serial, Size};
 
 use super::closing_window::{ClosingWindow, ClosingWindowRenderElement};
 use super::scrolling::ColumnWidth;
+use super::snap::{snap_zone_area, Snap, SnapEdges, SnapGuides};
 use super::tile::{Tile, TileRenderElement, TileRenderSnapshot};
 use super::workspace::{InteractiveResize, ResolvedSize};
 use super::{
@@ -19,6 +20,7 @@ use crate::animation::{Animation, Clock};
 use crate::layout::RenderLayer;
 use crate::niri_render_elements;
 use crate::render_helpers::renderer::NiriRenderer;
+use crate::render_helpers::solid_color::SolidColorRenderElement;
 use crate::render_helpers::xray::XrayPos;
 use crate::render_helpers::RenderCtx;
 use crate::utils::transaction::TransactionBlocker;
@@ -51,6 +53,9 @@ pub struct FloatingSpace<W: LayoutElement> {
     /// Ongoing interactive resize.
     interactive_resize: Option<InteractiveResize<W>>,
 
+    /// Guides for the edges that the interactively resized window snapped to.
+    snap_guides: SnapGuides,
+
     /// Windows in the closing animation.
     closing_windows: Vec<ClosingWindow>,
 
@@ -74,6 +79,7 @@ niri_render_elements! {
     FloatingSpaceRenderElement<R> => {
         Tile = TileRenderElement<R>,
         ClosingWindow = ClosingWindowRenderElement,
+        SnapGuide = SolidColorRenderElement,
     }
 }
 
@@ -213,6 +219,7 @@ impl<W: LayoutElement> FloatingSpace<W> {
             data: Vec::new(),
             active_window_id: None,
             interactive_resize: None,
+            snap_guides: SnapGuides::default(),
             closing_windows: Vec::new(),
             view_size,
             working_area,
@@ -286,6 +293,10 @@ impl<W: LayoutElement> FloatingSpace<W> {
             tile_view_rect.loc -= offset + tile.render_offset();
             tile.update_render_elements(is_active, tile_view_rect);
         }
+
+        let color = self.options.layout.floating_snap.color.to_array_premul();
+        self.snap_guides
+            .update_render_elements(self.view_size, color);
     }
 
     pub fn tiles(&self) -> impl Iterator<Item = &Tile<W>> + '_ {
@@ -301,6 +312,14 @@ impl<W: LayoutElement> FloatingSpace<W> {
         zip(&self.tiles, offsets)
     }
 
+    /// Returns the tiles with their positions relative to the working area.
+    pub fn tiles_with_size_frac_positions(
+        &self,
+    ) -> impl Iterator<Item = (&Tile<W>, Point<f64, SizeFrac>)> + '_ {
+        let positions = self.data.iter().map(|d| d.pos);
+        zip(&self.tiles, positions)
+    }
+
     pub fn tiles_with_offsets_mut(
         &mut self,
     ) -> impl Iterator<Item = (&mut Tile<W>, Point<f64, Logical>)> + '_ {
@@ -1076,6 +1095,11 @@ impl<W: LayoutElement> FloatingSpace<W> {
     ) {
         let scale = Scale::from(self.scale);
 
+        if layer.is_normal() && self.interactive_resize.is_some() {
+            self.snap_guides
+                .render(Point::from((0., 0.)), &mut |elem| push(elem.into()));
+        }
+
         // Draw the closing windows on top of the other windows.
         //
         // FIXME: I guess this should rather preserve the stacking order when the window is closed.
@@ -1122,6 +1146,7 @@ impl<W: LayoutElement> FloatingSpace<W> {
             data: InteractiveResizeData { edges },
         };
         self.interactive_resize = Some(resize);
+        self.snap_guides.clear();
 
         true
     }
@@ -1142,12 +1167,37 @@ impl<W: LayoutElement> FloatingSpace<W> {
         let original_window_size = resize.original_window_size;
         let edges = resize.data.edges;
 
+        let snap_config = self.options.layout.floating_snap;
+        let snap_edges = (!snap_config.off).then(|| self.snap_edges(Some(window)));
+        let idx = self.idx_of(window).unwrap();
+        let rect = Rectangle::new(self.data[idx].logical_pos, self.data[idx].size);
+        let tile = &self.tiles[idx];
+        let border_w = tile.tile_size().w - tile.window_size().w;
+        let border_h = tile.tile_size().h - tile.window_size().h;
+        let mut snap = Snap::default();
+
         if edges.intersects(ResizeEdge::LEFT_RIGHT) {
             let mut dx = delta.x;
             if edges.contains(ResizeEdge::LEFT) {
                 dx = -dx;
             };
 
+            // Snap the moving edge while the opposite edge stays in place.
+            if let Some(snap_edges) = &snap_edges {
+                let tile_w = original_window_size.w + dx + border_w;
+                let distance = snap_config.distance;
+                if edges.contains(ResizeEdge::LEFT) {
+                    let right = rect.loc.x + rect.size.w;
+                    if let Some(edge) = snap_edges.snap_x(right - tile_w, distance) {
+                        dx = right - edge - original_window_size.w - border_w;
+                        snap.x = Some(edge);
+                    }
+                } else if let Some(edge) = snap_edges.snap_x(rect.loc.x + tile_w, distance) {
+                    dx = edge - rect.loc.x - original_window_size.w - border_w;
+                    snap.x = Some(edge);
+                }
+            }
+
             let window_width = (original_window_size.w + dx).round() as i32;
             self.set_window_width(Some(window), SizeChange::SetFixed(window_width), false);
         }
@@ -1158,10 +1208,27 @@ impl<W: LayoutElement> FloatingSpace<W> {
                 dy = -dy;
             };
 
+            if let Some(snap_edges) = &snap_edges {
+                let tile_h = original_window_size.h + dy + border_h;
+                let distance = snap_config.distance;
+                if edges.contains(ResizeEdge::TOP) {
+                    let bottom = rect.loc.y + rect.size.h;
+                    if let Some(edge) = snap_edges.snap_y(bottom - tile_h, distance) {
+                        dy = bottom - edge - original_window_size.h - border_h;
+                        snap.y = Some(edge);
+                    }
+                } else if let Some(edge) = snap_edges.snap_y(rect.loc.y + tile_h, distance) {
+                    dy = edge - rect.loc.y - original_window_size.h - border_h;
+                    snap.y = Some(edge);
+                }
+            }
+
             let window_height = (original_window_size.h + dy).round() as i32;
             self.set_window_height(Some(window), SizeChange::SetFixed(window_height), false);
         }
 
+        self.snap_guides.set_snap(snap);
+
         true
     }
 
@@ -1182,10 +1249,12 @@ impl<W: LayoutElement> FloatingSpace<W> {
     pub fn refresh(&mut self, is_active: bool, is_focused: bool) {
         let active = self.active_window_id.clone();
         for tile in &mut self.tiles {
+            let is_sticky = tile.is_sticky;
             let win = tile.window_mut();
 
             win.set_active_in_column(true);
             win.set_floating(true);
+            win.set_sticky(is_sticky);
 
             let mut is_active = is_active && Some(win.id()) == active.as_ref();
             if self.options.deactivate_unfocused_windows {
@@ -1223,6 +1292,60 @@ impl<W: LayoutElement> FloatingSpace<W> {
         }
     }
 
+    /// Returns the edges that windows in this space snap to.
+    ///
+    /// These are the view edges, the working area edges, and the edges of the other windows.
+    pub fn snap_edges(&self, exclude: Option<&W::Id>) -> SnapEdges {
+        let mut edges = SnapEdges::default();
+        edges.add_rect(Rectangle::from_size(self.view_size));
+        edges.add_rect(self.working_area);
+
+        for (tile, data) in zip(&self.tiles, &self.data) {
+            if Some(tile.window().id()) == exclude {
+                continue;
+            }
+
+            edges.add_rect(Rectangle::new(data.logical_pos, data.size));
+        }
+
+        edges
+    }
+
+    /// Returns the area that a window snapped to the zone occupies.
+    pub fn snap_zone_area(&self, zone: SnapZone) -> Rectangle<f64, Logical> {
+        snap_zone_area(zone, self.working_area, self.options.layout.gaps)
+    }
+
+    /// Moves and resizes a window to fill a snap zone.
+    pub fn snap_window_to_zone(&mut self, id: Option<&W::Id>, zone: SnapZone, animate: bool) {
+        let Some(id) = id.or(self.active_window_id.as_ref()) else {
+            return;
+        };
+        let idx = self.idx_of(id).unwrap();
+        let area = self.snap_zone_area(zone);
+
+        let tile = &mut self.tiles[idx];
+        tile.floating_preset_width_idx = None;
+        tile.floating_preset_height_idx = None;
+
+        let win_width = tile.window_width_for_tile_width(area.size.w);
+        let win_height = tile.window_height_for_tile_height(area.size.h);
+
+        let win = tile.window_mut();
+        let min_size = win.min_size();
+        let max_size = win.max_size();
+
+        let win_width =
+            ensure_min_max_size(win_width.round().max(1.) as i32, min_size.w, max_size.w);
+        let win_height =
+            ensure_min_max_size(win_height.round().max(1.) as i32, min_size.h, max_size.h);
+
+        let win_size = Size::from((win_width, win_height));
+        win.request_size_once(win_size, animate);
+
+        self.move_to(idx, area.loc, animate);
+    }
+
     pub fn clamp_within_working_area(
         &self,
         pos: Point<f64, Logical>,
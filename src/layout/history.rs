@@ -0,0 +1,245 @@
+//! Undo and redo of structural layout changes.
+//!
+//! Before a structural operation, such as moving a window into another column or to another
+//! workspace, the layout records a snapshot of where every window is. Undoing puts the windows
+//! back where the snapshot says through the regular remove and add paths, so the usual movement
+//! animations play.
+
+use std::collections::VecDeque;
+
+use niri_ipc::ColumnDisplay;
+use smithay::output::Output;
+
+use super::scrolling::ColumnWidth;
+use super::workspace::{Workspace, WorkspaceId};
+use super::LayoutElement;
+
+/// How many layout changes can be undone.
+const HISTORY_LIMIT: usize = 50;
+
+#[derive(Debug)]
+pub struct LayoutHistory<Id> {
+    undo: VecDeque<LayoutSnapshot<Id>>,
+    redo: Vec<LayoutSnapshot<Id>>,
+}
+
+/// Placement of every window in the layout.
+#[derive(Debug, Clone)]
+pub struct LayoutSnapshot<Id> {
+    pub workspaces: Vec<WorkspaceSnapshot<Id>>,
+    /// Window that had focus, focused again when the snapshot is applied.
+    pub focus: Option<Id>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkspaceSnapshot<Id> {
+    pub id: WorkspaceId,
+    /// Output of the monitor the workspace was on.
+    pub output: Output,
+    /// Index of the workspace on its monitor.
+    pub idx: usize,
+    pub columns: Vec<ColumnSnapshot<Id>>,
+    pub floating: Vec<Id>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnSnapshot<Id> {
+    pub windows: Vec<Id>,
+    pub width: ColumnWidth,
+    pub is_full_width: bool,
+    pub display: ColumnDisplay,
+}
+
+impl<Id> Default for LayoutHistory<Id> {
+    fn default() -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+        }
+    }
+}
+
+impl<Id: PartialEq + Clone> LayoutHistory<Id> {
+    /// Records the layout right before a structural change.
+    pub fn record(&mut self, snapshot: LayoutSnapshot<Id>) {
+        if self
+            .undo
+            .back()
+            .is_some_and(|last| last.same_layout(&snapshot))
+        {
+            return;
+        }
+
+        self.push_undo(snapshot);
+        self.redo.clear();
+    }
+
+    /// Takes the most recent snapshot that differs from `current`, keeping `current` for redo.
+    pub fn undo(&mut self, current: LayoutSnapshot<Id>) -> Option<LayoutSnapshot<Id>> {
+        while let Some(snapshot) = self.undo.pop_back() {
+            if !snapshot.same_layout(&current) {
+                self.redo.push(current);
+                return Some(snapshot);
+            }
+        }
+
+        None
+    }
+
+    /// Takes the most recently undone snapshot that differs from `current`, keeping `current` for
+    /// undo.
+    pub fn redo(&mut self, current: LayoutSnapshot<Id>) -> Option<LayoutSnapshot<Id>> {
+        while let Some(snapshot) = self.redo.pop() {
+            if !snapshot.same_layout(&current) {
+                self.push_undo(current);
+                return Some(snapshot);
+            }
+        }
+
+        None
+    }
+
+    /// Points snapshots at a workspace recreated in place of a removed one.
+    pub fn replace_workspace_id(&mut self, old: WorkspaceId, new: WorkspaceId) {
+        for snapshot in self.undo.iter_mut().chain(&mut self.redo) {
+            for ws in &mut snapshot.workspaces {
+                if ws.id == old {
+                    ws.id = new;
+                }
+            }
+        }
+    }
+
+    fn push_undo(&mut self, snapshot: LayoutSnapshot<Id>) {
+        if self.undo.len() == HISTORY_LIMIT {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(snapshot);
+    }
+}
+
+impl<Id: PartialEq + Clone> LayoutSnapshot<Id> {
+    pub fn contains(&self, id: &Id) -> bool {
+        self.workspaces.iter().any(|ws| ws.contains(id))
+    }
+
+    /// Drops windows not matching `f`, along with workspaces left without windows.
+    pub fn retain_windows(&mut self, f: impl Fn(&Id) -> bool) {
+        for ws in &mut self.workspaces {
+            ws.retain_windows(&f);
+        }
+        self.workspaces.retain(|ws| !ws.is_empty());
+    }
+
+    /// Swaps the places of two windows.
+    pub fn swap_windows(&mut self, a: &Id, b: &Id) {
+        for ws in &mut self.workspaces {
+            let tiled = ws.columns.iter_mut().flat_map(|col| &mut col.windows);
+            for id in tiled.chain(&mut ws.floating) {
+                if id == a {
+                    *id = b.clone();
+                } else if id == b {
+                    *id = a.clone();
+                }
+            }
+        }
+    }
+
+    pub fn workspace(&self, id: WorkspaceId) -> Option<&WorkspaceSnapshot<Id>> {
+        self.workspaces.iter().find(|ws| ws.id == id)
+    }
+
+    /// Returns whether applying one snapshot onto the other would move nothing.
+    ///
+    /// Only windows present in both snapshots are compared, since windows opened or closed in
+    /// between are left alone.
+    fn same_layout(&self, other: &Self) -> bool {
+        let mut this = self.clone();
+        this.retain_windows(|id| other.contains(id));
+        let mut other = other.clone();
+        other.retain_windows(|id| this.contains(id));
+
+        this.workspaces.len() == other.workspaces.len()
+            && this.workspaces.iter().all(|ws| {
+                other
+                    .workspace(ws.id)
+                    .is_some_and(|o| o.output == ws.output && o.has_same_windows(ws))
+            })
+    }
+}
+
+impl<Id: PartialEq + Clone> WorkspaceSnapshot<Id> {
+    pub fn from_workspace<W: LayoutElement<Id = Id>>(
+        ws: &Workspace<W>,
+        output: &Output,
+        idx: usize,
+    ) -> Self {
+        let mut columns: Vec<_> = ws
+            .scrolling()
+            .columns()
+            .map(|col| ColumnSnapshot {
+                windows: col
+                    .tiles()
+                    .map(|(tile, _)| tile.window().id().clone())
+                    .collect(),
+                width: col.desired_width(),
+                is_full_width: col.is_full_width(),
+                display: col.display_mode(),
+            })
+            .collect();
+
+        // Like in saved layouts, windows in the other tiled layouts are one column each.
+        let width = ws
+            .scrolling()
+            .columns()
+            .next()
+            .map_or(ColumnWidth::Proportion(0.5), |col| col.desired_width());
+        columns.extend(ws.tiling().tiles().map(|tile| ColumnSnapshot {
+            windows: vec![tile.window().id().clone()],
+            width,
+            is_full_width: false,
+            display: ColumnDisplay::Normal,
+        }));
+
+        Self {
+            id: ws.id(),
+            output: output.clone(),
+            idx,
+            columns,
+            floating: ws
+                .floating()
+                .tiles()
+                .map(|tile| tile.window().id().clone())
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty() && self.floating.is_empty()
+    }
+
+    pub fn contains(&self, id: &Id) -> bool {
+        self.windows().any(|win| win == id)
+    }
+
+    pub fn windows(&self) -> impl Iterator<Item = &Id> + '_ {
+        let tiled = self.columns.iter().flat_map(|col| &col.windows);
+        tiled.chain(&self.floating)
+    }
+
+    /// Returns whether both snapshots have the same windows in the same places.
+    pub fn has_same_windows(&self, other: &Self) -> bool {
+        // Floating windows reorder as they are raised, which doesn't move them anywhere.
+        self.columns == other.columns
+            && self.floating.len() == other.floating.len()
+            && self.floating.iter().all(|id| other.floating.contains(id))
+    }
+
+    pub fn retain_windows(&mut self, f: impl Fn(&Id) -> bool) {
+        for col in &mut self.columns {
+            col.windows.retain(&f);
+        }
+        self.columns.retain(|col| !col.windows.is_empty());
+        self.floating.retain(&f);
+    }
+}
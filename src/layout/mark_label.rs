@@ -0,0 +1,137 @@
+use std::cell::RefCell;
+
+use anyhow::ensure;
+use pangocairo::cairo::{self, ImageSurface};
+use pangocairo::pango::FontDescription;
+use smithay::backend::allocator::Fourcc;
+use smithay::backend::renderer::element::Kind;
+use smithay::backend::renderer::gles::{GlesRenderer, GlesTexture};
+use smithay::utils::{Logical, Point, Transform};
+
+use crate::render_helpers::primary_gpu_texture::PrimaryGpuTextureRenderElement;
+use crate::render_helpers::renderer::NiriRenderer;
+use crate::render_helpers::texture::{TextureBuffer, TextureRenderElement};
+use crate::utils::to_physical_precise_round;
+
+const FONT: &str = "monospace bold 12px";
+const PADDING: i32 = 4;
+/// Distance from the top left corner of the window.
+const OFFSET: f64 = 8.;
+
+/// Small label showing the mark of a window.
+#[derive(Debug, Default)]
+pub struct MarkLabel {
+    texture: RefCell<MarkTexture>,
+}
+
+/// Cached mark texture.
+#[derive(Debug, Default)]
+struct MarkTexture {
+    mark: String,
+    scale: f64,
+    texture: Option<Option<TextureBuffer<GlesTexture>>>,
+}
+
+impl MarkLabel {
+    pub fn render<R: NiriRenderer>(
+        &self,
+        renderer: &mut R,
+        mark: &str,
+        scale: f64,
+        location: Point<f64, Logical>,
+    ) -> Option<PrimaryGpuTextureRenderElement> {
+        let buffer = self
+            .texture
+            .borrow_mut()
+            .get(renderer.as_gles_renderer(), mark, scale)?;
+
+        let location = location + Point::from((OFFSET, OFFSET));
+        let location = location.to_physical_precise_round(scale).to_logical(scale);
+
+        let elem = TextureRenderElement::from_texture_buffer(
+            buffer,
+            location,
+            1.,
+            None,
+            None,
+            Kind::Unspecified,
+        );
+        Some(PrimaryGpuTextureRenderElement(elem))
+    }
+}
+
+impl MarkTexture {
+    fn get(
+        &mut self,
+        renderer: &mut GlesRenderer,
+        mark: &str,
+        scale: f64,
+    ) -> Option<TextureBuffer<GlesTexture>> {
+        if self.mark != mark || self.scale != scale {
+            self.texture = None;
+            self.mark = mark.to_owned();
+            self.scale = scale;
+        }
+
+        self.texture
+            .get_or_insert_with(|| render(renderer, mark, scale).ok())
+            .clone()
+    }
+}
+
+fn render(
+    renderer: &mut GlesRenderer,
+    mark: &str,
+    scale: f64,
+) -> anyhow::Result<TextureBuffer<GlesTexture>> {
+    let _span = tracy_client::span!("mark_label::render");
+
+    let padding: i32 = to_physical_precise_round(scale, PADDING);
+
+    let mut font = FontDescription::from_string(FONT);
+    font.set_absolute_size(to_physical_precise_round(scale, font.size()));
+
+    let surface = ImageSurface::create(cairo::Format::ARgb32, 0, 0)?;
+    let cr = cairo::Context::new(&surface)?;
+    let layout = pangocairo::functions::create_layout(&cr);
+    layout.context().set_round_glyph_positions(false);
+    layout.set_single_paragraph_mode(true);
+    layout.set_font_description(Some(&font));
+    layout.set_text(mark);
+
+    let (mut width, mut height) = layout.pixel_size();
+    ensure!(width > 0 && height > 0);
+
+    width += padding * 2;
+    height += padding * 2;
+
+    let surface = ImageSurface::create(cairo::Format::ARgb32, width, height)?;
+    let cr = cairo::Context::new(&surface)?;
+    cr.set_source_rgba(0.1, 0.1, 0.1, 0.85);
+    cr.paint()?;
+
+    cr.move_to(padding.into(), padding.into());
+    let layout = pangocairo::functions::create_layout(&cr);
+    layout.context().set_round_glyph_positions(false);
+    layout.set_single_paragraph_mode(true);
+    layout.set_font_description(Some(&font));
+    layout.set_text(mark);
+
+    cr.set_source_rgb(1., 1., 1.);
+    pangocairo::functions::show_layout(&cr, &layout);
+
+    drop(cr);
+    let data = surface.take_data().unwrap();
+    let buffer = TextureBuffer::from_memory(
+        renderer,
+        &data,
+        Fourcc::Argb8888,
+        (width, height),
+        false,
+        scale,
+        Transform::Normal,
+        Vec::new(),
+    )?;
+
+    Ok(buffer)
+}
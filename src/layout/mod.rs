@@ -39,9 +39,10 @@ use std::time::Duration;
 use monitor::{InsertHint, InsertPosition, InsertWorkspace, MonitorAddWindowTarget};
 use niri_config::utils::MergeWith as _;
 use niri_config::{
-    Config, CornerRadius, LayoutPart, PresetSize, Workspace as WorkspaceConfig, WorkspaceReference,
+    Config, CornerRadius, LayoutPart, OutputName, PresetSize, Workspace as WorkspaceConfig,
+    WorkspaceReference,
 };
-use niri_ipc::{ColumnDisplay, PositionChange, SizeChange, WindowLayout};
+use niri_ipc::{ColumnDisplay, LayoutMode, PositionChange, SizeChange, SnapZone, WindowLayout};
 use scrolling::{Column, ColumnWidth};
 use smithay::backend::renderer::element::surface::WaylandSurfaceRenderElement;
 use smithay::backend::renderer::element::utils::RescaleRenderElement;
@@ -52,8 +53,16 @@ use smithay::utils::{Logical, Point, Rectangle, Scale, Serial, Size, Transform};
 use tile::{Tile, TileRenderElement};
 use workspace::{WorkspaceAddWindowTarget, WorkspaceId};
 
+use self::history::{LayoutHistory, LayoutSnapshot, WorkspaceSnapshot};
 pub use self::monitor::MonitorRenderElement;
 use self::monitor::{Monitor, WorkspaceSwitch};
+use self::restore::{
+    PendingRestore, RestoreSlot, RestoreSlotKind, SavedLayout, SavedMonitor, SavedWorkspace,
+    WindowHints, RESTORE_TIMEOUT,
+};
+use self::scratchpad::Scratchpad;
+use self::snap::{snap_zone_at, SnapGuides};
+use self::swallow::Swallowed;
 use self::workspace::{OutputId, Workspace};
 use crate::animation::{Animation, Clock};
 use crate::input::swipe_tracker::SwipeTracker;
@@ -78,13 +87,20 @@ use crate::window::ResolvedWindowRules;
 pub mod closing_window;
 pub mod floating;
 pub mod focus_ring;
+pub mod history;
 pub mod insert_hint_element;
+pub mod mark_label;
 pub mod monitor;
 pub mod opening_window;
+pub mod restore;
+pub mod scratchpad;
 pub mod scrolling;
 pub mod shadow;
+pub mod snap;
+pub mod swallow;
 pub mod tab_indicator;
 pub mod tile;
+pub mod tiling;
 pub mod workspace;
 
 #[cfg(test)]
@@ -230,6 +246,11 @@ pub trait LayoutElement {
         self.request_size(size, SizingMode::Normal, animate, None);
     }
 
+    /// Replaces the transaction for the next configure, if there is one.
+    ///
+    /// This makes pending size changes from separate layout operations apply together.
+    fn share_transaction(&mut self, transaction: &Transaction);
+
     fn min_size(&self) -> Size<i32, Logical>;
     fn max_size(&self) -> Size<i32, Logical>;
     fn is_wl_surface(&self, wl_surface: &WlSurface) -> bool;
@@ -241,10 +262,13 @@ pub trait LayoutElement {
     fn set_activated(&mut self, active: bool);
     fn set_active_in_column(&mut self, active: bool);
     fn set_floating(&mut self, floating: bool);
+    fn set_sticky(&mut self, sticky: bool);
+    fn set_minimized(&mut self, minimized: bool);
     fn set_bounds(&self, bounds: Size<i32, Logical>);
     fn is_ignoring_opacity_window_rule(&self) -> bool;
 
     fn is_urgent(&self) -> bool;
+    fn mark(&self) -> Option<&str>;
 
     fn configure_intent(&self) -> ConfigureIntent;
     fn send_pending_configure(&mut self);
@@ -371,6 +395,14 @@ pub struct Layout<W: LayoutElement> {
     overview_open: bool,
     /// The overview zoom progress.
     overview_progress: Option<OverviewProgress>,
+    /// Saved layout that newly mapped windows are being restored into.
+    pending_restore: Option<PendingRestore<W>>,
+    /// Windows hidden outside of every workspace.
+    scratchpad: Scratchpad<W>,
+    /// Windows hidden while a child window has taken over their tile.
+    swallowed: Swallowed<W>,
+    /// Snapshots of the layout for undoing and redoing structural changes.
+    history: LayoutHistory<W::Id>,
     /// Configurable properties of the layout.
     options: Rc<Options>,
 }
@@ -442,6 +474,10 @@ struct InteractiveMoveData<W: LayoutElement> {
     ///
     /// This helps the pointer remain inside the window as it resizes.
     pub(self) pointer_ratio_within_window: (f64, f64),
+    /// Snapping of the floating window to nearby edges, in output coordinates.
+    pub(self) snap_guides: SnapGuides,
+    /// Snap zone of the floating window under the pointer at an output edge.
+    pub(self) snap_zone: Option<SnapZone>,
     /// Config overrides for the output where the window is currently located.
     ///
     /// Cached here to be accessible while an output is removed.
@@ -496,6 +532,7 @@ pub enum ConfigureIntent {
 }
 
 /// Tile that was just removed from the layout.
+#[derive(Debug)]
 pub struct RemovedTile<W: LayoutElement> {
     tile: Tile<W>,
     /// Width of the column the tile was in.
@@ -616,7 +653,8 @@ impl<W: LayoutElement> InteractiveMoveData<W> {
         ));
         let pos = self.pointer_pos_within_output
             - (pointer_offset_within_window + self.tile.window_loc() - self.tile.render_offset())
-                .upscale(zoom);
+                .upscale(zoom)
+            + self.snap_guides.snap().offset;
         // Round to physical pixels.
         pos.to_physical_precise_round(scale).to_logical(scale)
     }
@@ -666,7 +704,7 @@ impl Options {
         Self {
             layout: config.layout.clone(),
             animations: config.animations.clone(),
-            gestures: config.gestures,
+            gestures: config.gestures.clone(),
             overview: config.overview,
             blur: config.blur,
             disable_resize_throttling: config.debug.disable_resize_throttling,
@@ -728,6 +766,10 @@ impl<W: LayoutElement> Layout<W> {
             update_render_elements_time: Duration::ZERO,
             overview_open: false,
             overview_progress: None,
+            pending_restore: None,
+            scratchpad: Scratchpad::default(),
+            swallowed: Swallowed::default(),
+            history: LayoutHistory::default(),
             options: Rc::new(options),
         }
     }
@@ -753,6 +795,10 @@ impl<W: LayoutElement> Layout<W> {
             update_render_elements_time: Duration::ZERO,
             overview_open: false,
             overview_progress: None,
+            pending_restore: None,
+            scratchpad: Scratchpad::default(),
+            swallowed: Swallowed::default(),
+            history: LayoutHistory::default(),
             options: opts,
         }
     }
@@ -1132,6 +1178,251 @@ impl<W: LayoutElement> Layout<W> {
         }
     }
 
+    /// Starts restoring newly mapped windows into a previously saved layout.
+    pub fn set_pending_restore(&mut self, saved: SavedLayout) {
+        if saved.is_empty() {
+            self.pending_restore = None;
+            return;
+        }
+
+        let deadline = self.clock.now_unadjusted() + RESTORE_TIMEOUT;
+        self.pending_restore = Some(PendingRestore::new(saved, deadline));
+    }
+
+    pub fn has_pending_restore(&self) -> bool {
+        self.pending_restore
+            .as_ref()
+            .is_some_and(|pending| self.clock.now_unadjusted() < pending.deadline)
+    }
+
+    /// Returns the current layout in a form suitable for restoring after a restart.
+    ///
+    /// Returns `None` if there are no outputs.
+    pub fn saved_layout(&self, hints: impl Fn(&W) -> WindowHints) -> Option<SavedLayout> {
+        let MonitorSet::Normal { monitors, .. } = &self.monitor_set else {
+            return None;
+        };
+
+        let monitors = monitors
+            .iter()
+            .map(|mon| {
+                let name = mon.output.user_data().get::<OutputName>().unwrap();
+                SavedMonitor {
+                    output: name.format_make_model_serial_or_connector(),
+                    workspaces: mon
+                        .workspaces
+                        .iter()
+                        .filter(|ws| ws.has_windows_or_name())
+                        .map(|ws| SavedWorkspace::from_workspace(ws, &hints))
+                        .collect(),
+                }
+            })
+            .collect();
+
+        Some(SavedLayout { monitors })
+    }
+
+    /// Finds a slot in the pending restored layout for a window with these hints.
+    pub fn find_restore_slot(&mut self, hints: &WindowHints) -> Option<RestoreSlot> {
+        if !self.has_pending_restore() {
+            self.pending_restore = None;
+            return None;
+        }
+
+        self.pending_restore.as_ref()?.find_slot(hints)
+    }
+
+    /// Adds a new window into its slot in the pending restored layout.
+    ///
+    /// Returns an output that the window was added to, if there were any outputs.
+    pub fn add_window_restored(
+        &mut self,
+        window: W,
+        slot: RestoreSlot,
+        activate: ActivateWindow,
+    ) -> Option<&Output> {
+        if self.pending_restore.is_none()
+            || matches!(self.monitor_set, MonitorSet::NoOutputs { .. })
+        {
+            return self.add_window(
+                window,
+                AddWindowTarget::Auto,
+                None,
+                None,
+                false,
+                false,
+                activate,
+            );
+        }
+
+        let MonitorSet::Normal {
+            monitors,
+            active_monitor_idx,
+            ..
+        } = &mut self.monitor_set
+        else {
+            unreachable!()
+        };
+        let pending = self.pending_restore.as_mut().unwrap();
+
+        let saved_mon = &pending.layout.monitors[slot.monitor];
+        let saved_ws = &saved_mon.workspaces[slot.workspace];
+
+        // Named workspaces are found by name wherever they are.
+        let named = saved_ws.name.as_ref().and_then(|name| {
+            monitors
+                .iter()
+                .enumerate()
+                .find_map(|(m, mon)| Some((m, mon.find_named_workspace_index(name)?)))
+        });
+
+        let (mon_idx, ws_idx) = if let Some(found) = named {
+            found
+        } else {
+            let mon_idx = monitors
+                .iter()
+                .position(|mon| output_matches_name(&mon.output, &saved_mon.output))
+                .unwrap_or(*active_monitor_idx);
+            let mon = &mut monitors[mon_idx];
+
+            let key = (slot.monitor, slot.workspace);
+            let existing = pending
+                .workspaces
+                .get(&key)
+                .and_then(|id| mon.idx_of_ws(*id));
+
+            let ws_idx = if let Some(ws_idx) = existing {
+                ws_idx
+            } else {
+                // Keep the saved order by going right above the closest restored workspace
+                // that was below this one.
+                let below = pending
+                    .workspaces
+                    .iter()
+                    .filter(|((m, w), _)| *m == slot.monitor && *w > slot.workspace)
+                    .filter_map(|((_, w), id)| Some((*w, mon.idx_of_ws(*id)?)))
+                    .min_by_key(|(w, _)| *w);
+
+                let ws_idx = if let Some((_, idx)) = below {
+                    mon.add_workspace_at(idx);
+                    idx
+                } else {
+                    mon.workspaces.len() - 1
+                };
+
+                pending.workspaces.insert(key, mon.workspaces[ws_idx].id());
+                ws_idx
+            };
+
+            (mon_idx, ws_idx)
+        };
+
+        let mon = &mut monitors[mon_idx];
+        let ws = &mon.workspaces[ws_idx];
+        let ws_id = ws.id();
+        let id = window.id().clone();
+
+        match slot.kind {
+            RestoreSlotKind::Floating(idx) => {
+                let width = ws.resolve_scrolling_width(&window, None);
+                let mut tile = ws.make_tile(window);
+                tile.floating_pos = Some(saved_ws.floating[idx].pos());
+
+                let target = MonitorAddWindowTarget::Workspace {
+                    id: ws_id,
+                    column_idx: None,
+                };
+                mon.add_tile(tile, target, activate, true, width, false, true, None);
+            }
+            RestoreSlotKind::Tiled { column, tile } => {
+                let saved_col = &saved_ws.columns[column];
+
+                // Windows already restored into this workspace, with their saved column and tile
+                // indices, and their current column index.
+                let restored: Vec<_> = pending
+                    .restored
+                    .iter()
+                    .filter(|(s, _)| s.is_same_workspace(&slot))
+                    .filter_map(|(s, win)| {
+                        let RestoreSlotKind::Tiled { column, tile } = s.kind else {
+                            return None;
+                        };
+                        let col_idx = ws.scrolling().columns().position(|col| col.contains(win))?;
+                        Some((column, tile, col_idx))
+                    })
+                    .collect();
+
+                let sibling_col_idx = restored
+                    .iter()
+                    .find(|(c, _, _)| *c == column)
+                    .map(|(_, _, col_idx)| *col_idx);
+
+                if let Some(col_idx) = sibling_col_idx {
+                    let tile_idx = restored
+                        .iter()
+                        .filter(|(c, t, idx)| *c == column && *t < tile && *idx == col_idx)
+                        .count();
+                    let tile = mon.workspaces[ws_idx].make_tile(window);
+                    let activate = activate.map_smart(|| false);
+                    mon.add_tile_to_column(ws_idx, col_idx, Some(tile_idx), tile, activate, true);
+                } else {
+                    let column_idx = if ws.is_scrolling_layout() {
+                        let left = restored
+                            .iter()
+                            .filter(|(c, _, _)| *c < column)
+                            .max_by_key(|(c, _, _)| *c);
+                        Some(left.map_or(0, |(_, _, col_idx)| col_idx + 1))
+                    } else {
+                        None
+                    };
+
+                    let target = MonitorAddWindowTarget::Workspace {
+                        id: ws_id,
+                        column_idx,
+                    };
+                    let width = saved_col.width;
+                    let is_full_width = saved_col.is_full_width;
+                    mon.add_window(window, target, activate, width, is_full_width, false);
+
+                    if saved_col.display != ColumnDisplay::Normal {
+                        let ws_idx = mon.idx_of_ws(ws_id).unwrap();
+                        mon.workspaces[ws_idx]
+                            .scrolling_mut()
+                            .set_window_column_display(&id, saved_col.display);
+                    }
+                }
+            }
+        }
+
+        pending.restored.push((slot, id));
+
+        if let RestoreSlotKind::Tiled { column, .. } = slot.kind {
+            // Heights depend on the other windows in the column, so set all of them again as the
+            // column fills up.
+            let ws_idx = mon.idx_of_ws(ws_id).unwrap();
+            let scrolling = mon.workspaces[ws_idx].scrolling_mut();
+            for (s, win) in &pending.restored {
+                let RestoreSlotKind::Tiled { column: c, tile } = s.kind else {
+                    continue;
+                };
+                if s.is_same_workspace(&slot) && c == column {
+                    let height = pending.workspace(*s).columns[c].windows[tile].height;
+                    scrolling.set_window_restored_height(win, height);
+                }
+            }
+        }
+
+        if activate.map_smart(|| false) {
+            *active_monitor_idx = mon_idx;
+        }
+
+        if pending.is_done() {
+            self.pending_restore = None;
+        }
+
+        Some(&monitors[mon_idx].output)
+    }
+
     pub fn remove_window(
         &mut self,
         window: &W::Id,
@@ -1172,12 +1463,35 @@ impl<W: LayoutElement> Layout<W> {
             }
         }
 
+        if let Some(tile) = self.scratchpad.remove(window) {
+            let width = ColumnWidth::Fixed(tile.tile_expected_or_current_size().w);
+            return Some(RemovedTile {
+                tile,
+                width,
+                is_full_width: false,
+                is_floating: true,
+            });
+        }
+
+        if let Some(tile) = self.swallowed.remove(window) {
+            let width = ColumnWidth::Fixed(tile.tile_expected_or_current_size().w);
+            return Some(RemovedTile {
+                tile,
+                width,
+                is_full_width: false,
+                is_floating: false,
+            });
+        }
+
         match &mut self.monitor_set {
             MonitorSet::Normal { monitors, .. } => {
                 for mon in monitors {
                     for (idx, ws) in mon.workspaces.iter_mut().enumerate() {
-                        if ws.has_window(window) {
-                            let removed = ws.remove_tile(window, transaction);
+                        if ws.has_window(window) || ws.has_minimized_window(window) {
+                            let removed = match ws.remove_minimized_window(window) {
+                                Some(removed) => removed,
+                                None => ws.remove_tile(window, transaction),
+                            };
 
                             // Clean up empty workspaces that are not active and not last.
                             if !ws.has_windows_or_name()
@@ -1210,8 +1524,11 @@ impl<W: LayoutElement> Layout<W> {
             }
             MonitorSet::NoOutputs { workspaces, .. } => {
                 for (idx, ws) in workspaces.iter_mut().enumerate() {
-                    if ws.has_window(window) {
-                        let removed = ws.remove_tile(window, transaction);
+                    if ws.has_window(window) || ws.has_minimized_window(window) {
+                        let removed = match ws.remove_minimized_window(window) {
+                            Some(removed) => removed,
+                            None => ws.remove_tile(window, transaction),
+                        };
 
                         // Clean up empty workspaces.
                         if !ws.has_windows_or_name() {
@@ -1250,11 +1567,25 @@ impl<W: LayoutElement> Layout<W> {
             }
         }
 
+        if let Some(tile) = self
+            .scratchpad
+            .tiles_mut()
+            .chain(self.swallowed.tiles_mut())
+            .find(|tile| tile.window().id() == window)
+        {
+            if let Some(serial) = serial {
+                tile.window_mut().on_commit(serial);
+            }
+
+            tile.update_window();
+            return;
+        }
+
         match &mut self.monitor_set {
             MonitorSet::Normal { monitors, .. } => {
                 for mon in monitors {
                     for ws in &mut mon.workspaces {
-                        if ws.has_window(window) {
+                        if ws.has_window(window) || ws.has_minimized_window(window) {
                             ws.update_window(window, serial);
                             return;
                         }
@@ -1263,7 +1594,7 @@ impl<W: LayoutElement> Layout<W> {
             }
             MonitorSet::NoOutputs { workspaces, .. } => {
                 for ws in workspaces {
-                    if ws.has_window(window) {
+                    if ws.has_window(window) || ws.has_minimized_window(window) {
                         ws.update_window(window, serial);
                         return;
                     }
@@ -1388,6 +1719,14 @@ impl<W: LayoutElement> Layout<W> {
             }
         }
 
+        if let Some(window) = self.scratchpad.find_wl_surface(wl_surface) {
+            return Some((window, None));
+        }
+
+        if let Some(window) = self.swallowed.find_wl_surface(wl_surface) {
+            return Some((window, None));
+        }
+
         match &self.monitor_set {
             MonitorSet::Normal { monitors, .. } => {
                 for mon in monitors {
@@ -1420,6 +1759,14 @@ impl<W: LayoutElement> Layout<W> {
             }
         }
 
+        if let Some(window) = self.scratchpad.find_wl_surface_mut(wl_surface) {
+            return Some((window, None));
+        }
+
+        if let Some(window) = self.swallowed.find_wl_surface_mut(wl_surface) {
+            return Some((window, None));
+        }
+
         match &mut self.monitor_set {
             MonitorSet::Normal { monitors, .. } => {
                 for mon in monitors {
@@ -1461,6 +1808,15 @@ impl<W: LayoutElement> Layout<W> {
             }
         }
 
+        if let Some(tile) = self
+            .scratchpad
+            .tiles()
+            .chain(self.swallowed.tiles())
+            .find(|tile| tile.window().id() == window)
+        {
+            return Rectangle::from_size(tile.window_size());
+        }
+
         self.workspaces()
             .find_map(|(_, _, ws)| ws.popup_target_rect(window))
             .unwrap()
@@ -1537,6 +1893,24 @@ impl<W: LayoutElement> Layout<W> {
             }
         }
 
+        if self.scratchpad.has_hidden_window(window) {
+            self.summon_from_scratchpad(Some(window));
+            return;
+        }
+
+        // Swallowed windows are represented by the child that took over their tile.
+        if let Some(child) = self.swallowed.child_of(window).cloned() {
+            self.activate_window(&child);
+            return;
+        }
+
+        // Activating a minimized window restores it.
+        for ws in self.workspaces_mut() {
+            if ws.restore_minimized_window(Some(window)) {
+                break;
+            }
+        }
+
         let MonitorSet::Normal {
             monitors,
             active_monitor_idx,
@@ -1694,6 +2068,10 @@ impl<W: LayoutElement> Layout<W> {
             f(move_.tile.window(), Some(&move_.output), None, layout);
         }
 
+        for tile in self.scratchpad.tiles().chain(self.swallowed.tiles()) {
+            f(tile.window(), None, None, tile.ipc_layout_template());
+        }
+
         match &self.monitor_set {
             MonitorSet::Normal { monitors, .. } => {
                 for mon in monitors {
@@ -1701,6 +2079,10 @@ impl<W: LayoutElement> Layout<W> {
                         for (tile, layout) in ws.tiles_with_ipc_layouts() {
                             f(tile.window(), Some(&mon.output), Some(ws.id()), layout);
                         }
+                        for tile in ws.minimized_tiles() {
+                            let layout = tile.ipc_layout_template();
+                            f(tile.window(), Some(&mon.output), Some(ws.id()), layout);
+                        }
                     }
                 }
             }
@@ -1709,16 +2091,41 @@ impl<W: LayoutElement> Layout<W> {
                     for (tile, layout) in ws.tiles_with_ipc_layouts() {
                         f(tile.window(), None, Some(ws.id()), layout);
                     }
+                    for tile in ws.minimized_tiles() {
+                        f(
+                            tile.window(),
+                            None,
+                            Some(ws.id()),
+                            tile.ipc_layout_template(),
+                        );
+                    }
                 }
             }
         }
     }
 
+    /// Makes all pending window size changes wait on one transaction.
+    ///
+    /// Every layout operation starts its own transaction, so without this, windows resized by
+    /// several operations in a row could show their new sizes in different frames.
+    pub fn share_pending_transactions(&mut self) {
+        let transaction = Transaction::new();
+        self.with_windows_mut(|win, _| win.share_transaction(&transaction));
+    }
+
     pub fn with_windows_mut(&mut self, mut f: impl FnMut(&mut W, Option<&Output>)) {
         if let Some(InteractiveMoveState::Moving(move_)) = &mut self.interactive_move {
             f(move_.tile.window_mut(), Some(&move_.output));
         }
 
+        for tile in self
+            .scratchpad
+            .tiles_mut()
+            .chain(self.swallowed.tiles_mut())
+        {
+            f(tile.window_mut(), None);
+        }
+
         match &mut self.monitor_set {
             MonitorSet::Normal { monitors, .. } => {
                 for mon in monitors {
@@ -1726,6 +2133,9 @@ impl<W: LayoutElement> Layout<W> {
                         for win in ws.windows_mut() {
                             f(win, Some(&mon.output));
                         }
+                        for tile in ws.minimized_tiles_mut() {
+                            f(tile.window_mut(), Some(&mon.output));
+                        }
                     }
                 }
             }
@@ -1734,6 +2144,9 @@ impl<W: LayoutElement> Layout<W> {
                     for win in ws.windows_mut() {
                         f(win, None);
                     }
+                    for tile in ws.minimized_tiles_mut() {
+                        f(tile.window_mut(), None);
+                    }
                 }
             }
         }
@@ -1808,6 +2221,8 @@ impl<W: LayoutElement> Layout<W> {
     }
 
     pub fn move_left(&mut self) {
+        self.record_history();
+
         let Some(workspace) = self.active_workspace_mut() else {
             return;
         };
@@ -1815,6 +2230,8 @@ impl<W: LayoutElement> Layout<W> {
     }
 
     pub fn move_right(&mut self) {
+        self.record_history();
+
         let Some(workspace) = self.active_workspace_mut() else {
             return;
         };
@@ -1822,6 +2239,8 @@ impl<W: LayoutElement> Layout<W> {
     }
 
     pub fn move_column_to_first(&mut self) {
+        self.record_history();
+
         let Some(workspace) = self.active_workspace_mut() else {
             return;
         };
@@ -1829,6 +2248,8 @@ impl<W: LayoutElement> Layout<W> {
     }
 
     pub fn move_column_to_last(&mut self) {
+        self.record_history();
+
         let Some(workspace) = self.active_workspace_mut() else {
             return;
         };
@@ -1836,6 +2257,8 @@ impl<W: LayoutElement> Layout<W> {
     }
 
     pub fn move_column_left_or_to_output(&mut self, output: &Output) -> bool {
+        self.record_history();
+
         if let Some(workspace) = self.active_workspace_mut() {
             if workspace.move_left() {
                 return false;
@@ -1847,6 +2270,8 @@ impl<W: LayoutElement> Layout<W> {
     }
 
     pub fn move_column_right_or_to_output(&mut self, output: &Output) -> bool {
+        self.record_history();
+
         if let Some(workspace) = self.active_workspace_mut() {
             if workspace.move_right() {
                 return false;
@@ -1858,6 +2283,8 @@ impl<W: LayoutElement> Layout<W> {
     }
 
     pub fn move_column_to_index(&mut self, index: usize) {
+        self.record_history();
+
         let Some(workspace) = self.active_workspace_mut() else {
             return;
         };
@@ -1865,6 +2292,8 @@ impl<W: LayoutElement> Layout<W> {
     }
 
     pub fn move_down(&mut self) {
+        self.record_history();
+
         let Some(workspace) = self.active_workspace_mut() else {
             return;
         };
@@ -1872,6 +2301,8 @@ impl<W: LayoutElement> Layout<W> {
     }
 
     pub fn move_up(&mut self) {
+        self.record_history();
+
         let Some(workspace) = self.active_workspace_mut() else {
             return;
         };
@@ -1879,6 +2310,8 @@ impl<W: LayoutElement> Layout<W> {
     }
 
     pub fn move_down_or_to_workspace_down(&mut self) {
+        self.record_history();
+
         let Some(monitor) = self.active_monitor() else {
             return;
         };
@@ -1886,6 +2319,8 @@ impl<W: LayoutElement> Layout<W> {
     }
 
     pub fn move_up_or_to_workspace_up(&mut self) {
+        self.record_history();
+
         let Some(monitor) = self.active_monitor() else {
             return;
         };
@@ -1893,6 +2328,8 @@ impl<W: LayoutElement> Layout<W> {
     }
 
     pub fn consume_or_expel_window_left(&mut self, window: Option<&W::Id>) {
+        self.record_history();
+
         if let Some(InteractiveMoveState::Moving(move_)) = &mut self.interactive_move {
             if window.is_none() || window == Some(move_.tile.window().id()) {
                 return;
@@ -1916,6 +2353,8 @@ impl<W: LayoutElement> Layout<W> {
     }
 
     pub fn consume_or_expel_window_right(&mut self, window: Option<&W::Id>) {
+        self.record_history();
+
         if let Some(InteractiveMoveState::Moving(move_)) = &mut self.interactive_move {
             if window.is_none() || window == Some(move_.tile.window().id()) {
                 return;
@@ -2123,6 +2562,8 @@ impl<W: LayoutElement> Layout<W> {
     }
 
     pub fn move_to_workspace_up(&mut self, focus: bool) {
+        self.record_history();
+
         let Some(monitor) = self.active_monitor() else {
             return;
         };
@@ -2135,6 +2576,8 @@ impl<W: LayoutElement> Layout<W> {
     }
 
     pub fn move_to_workspace_down(&mut self, focus: bool) {
+        self.record_history();
+
         let Some(monitor) = self.active_monitor() else {
             return;
         };
@@ -2152,6 +2595,8 @@ impl<W: LayoutElement> Layout<W> {
         idx: usize,
         activate: ActivateWindow,
     ) {
+        self.record_history();
+
         if let Some(InteractiveMoveState::Moving(move_)) = &mut self.interactive_move {
             if window.is_none() || window == Some(move_.tile.window().id()) {
                 return;
@@ -2178,6 +2623,8 @@ impl<W: LayoutElement> Layout<W> {
     }
 
     pub fn move_column_to_workspace_up(&mut self, activate: bool) {
+        self.record_history();
+
         let Some(monitor) = self.active_monitor() else {
             return;
         };
@@ -2185,6 +2632,8 @@ impl<W: LayoutElement> Layout<W> {
     }
 
     pub fn move_column_to_workspace_down(&mut self, activate: bool) {
+        self.record_history();
+
         let Some(monitor) = self.active_monitor() else {
             return;
         };
@@ -2192,6 +2641,8 @@ impl<W: LayoutElement> Layout<W> {
     }
 
     pub fn move_column_to_workspace(&mut self, idx: usize, activate: bool) {
+        self.record_history();
+
         let Some(monitor) = self.active_monitor() else {
             return;
         };
@@ -2234,6 +2685,8 @@ impl<W: LayoutElement> Layout<W> {
     }
 
     pub fn consume_into_column(&mut self) {
+        self.record_history();
+
         let Some(workspace) = self.active_workspace_mut() else {
             return;
         };
@@ -2241,6 +2694,8 @@ impl<W: LayoutElement> Layout<W> {
     }
 
     pub fn expel_from_column(&mut self) {
+        self.record_history();
+
         let Some(workspace) = self.active_workspace_mut() else {
             return;
         };
@@ -2248,6 +2703,8 @@ impl<W: LayoutElement> Layout<W> {
     }
 
     pub fn swap_window_in_direction(&mut self, direction: ScrollDirection) {
+        self.record_history();
+
         let Some(workspace) = self.active_workspace_mut() else {
             return;
         };
@@ -2255,6 +2712,8 @@ impl<W: LayoutElement> Layout<W> {
     }
 
     pub fn toggle_column_tabbed_display(&mut self) {
+        self.record_history();
+
         let Some(workspace) = self.active_workspace_mut() else {
             return;
         };
@@ -2262,12 +2721,21 @@ impl<W: LayoutElement> Layout<W> {
     }
 
     pub fn set_column_display(&mut self, display: ColumnDisplay) {
+        self.record_history();
+
         let Some(workspace) = self.active_workspace_mut() else {
             return;
         };
         workspace.set_column_display(display);
     }
 
+    pub fn set_layout_mode(&mut self, mode: LayoutMode) {
+        let Some(workspace) = self.active_workspace_mut() else {
+            return;
+        };
+        workspace.set_layout_mode(mode);
+    }
+
     pub fn center_column(&mut self) {
         let Some(workspace) = self.active_workspace_mut() else {
             return;
@@ -2404,6 +2872,41 @@ impl<W: LayoutElement> Layout<W> {
 
         let zoom = self.overview_zoom();
 
+        self.scratchpad.verify_invariants();
+        for tile in self.scratchpad.tiles() {
+            assert!(
+                !self.has_window(tile.window().id()),
+                "hidden scratchpad windows must not also be in the layout"
+            );
+        }
+
+        self.swallowed.verify_invariants();
+        for tile in self.swallowed.tiles() {
+            let id = tile.window().id();
+            assert!(
+                !self.has_window(id),
+                "swallowed windows must not also be in the layout"
+            );
+            assert!(
+                !self.scratchpad.has_hidden_window(id),
+                "swallowed windows must not also be in the scratchpad"
+            );
+        }
+
+        for (_, _, ws) in self.workspaces() {
+            for tile in ws.minimized_tiles() {
+                let id = tile.window().id();
+                assert!(
+                    !self.has_window(id),
+                    "minimized windows must not also be in the layout"
+                );
+                assert!(
+                    !self.scratchpad.has_hidden_window(id) && !self.swallowed.has_window(id),
+                    "minimized windows must not also be hidden elsewhere"
+                );
+            }
+        }
+
         let mut move_win_id = None;
         if let Some(state) = &self.interactive_move {
             match state {
@@ -2811,6 +3314,11 @@ impl<W: LayoutElement> Layout<W> {
                         .downscale(zoom);
 
                 move_.tile.update_render_elements(true, view_rect);
+
+                let color = move_.tile.options.layout.floating_snap.color;
+                move_
+                    .snap_guides
+                    .update_render_elements(output_size(&move_.output), color.to_array_premul());
             }
         }
 
@@ -2888,7 +3396,9 @@ impl<W: LayoutElement> Layout<W> {
                     let pos_within_workspace =
                         (move_.pointer_pos_within_output - geo.loc).downscale(zoom);
                     let position = if move_.is_floating {
-                        InsertPosition::Floating
+                        move_
+                            .snap_zone
+                            .map_or(InsertPosition::Floating, InsertPosition::SnapZone)
                     } else {
                         ws.scrolling_insert_position(pos_within_workspace)
                     };
@@ -3007,6 +3517,8 @@ impl<W: LayoutElement> Layout<W> {
     }
 
     pub fn toggle_width(&mut self, forwards: bool) {
+        self.record_history();
+
         let Some(workspace) = self.active_workspace_mut() else {
             return;
         };
@@ -3060,6 +3572,8 @@ impl<W: LayoutElement> Layout<W> {
     }
 
     pub fn toggle_full_width(&mut self) {
+        self.record_history();
+
         let Some(workspace) = self.active_workspace_mut() else {
             return;
         };
@@ -3067,6 +3581,8 @@ impl<W: LayoutElement> Layout<W> {
     }
 
     pub fn set_column_width(&mut self, change: SizeChange) {
+        self.record_history();
+
         let Some(workspace) = self.active_workspace_mut() else {
             return;
         };
@@ -3143,6 +3659,8 @@ impl<W: LayoutElement> Layout<W> {
     }
 
     pub fn expand_column_to_available_width(&mut self) {
+        self.record_history();
+
         let Some(workspace) = self.active_workspace_mut() else {
             return;
         };
@@ -3150,9 +3668,13 @@ impl<W: LayoutElement> Layout<W> {
     }
 
     pub fn toggle_window_floating(&mut self, window: Option<&W::Id>) {
+        self.record_history();
+
         if let Some(InteractiveMoveState::Moving(move_)) = &mut self.interactive_move {
             if window.is_none() || window == Some(move_.tile.window().id()) {
                 move_.is_floating = !move_.is_floating;
+                move_.snap_guides.clear();
+                move_.snap_zone = None;
 
                 // When going to floating, restore the floating window size.
                 if move_.is_floating {
@@ -3213,6 +3735,8 @@ impl<W: LayoutElement> Layout<W> {
     }
 
     pub fn set_window_floating(&mut self, window: Option<&W::Id>, floating: bool) {
+        self.record_history();
+
         if let Some(InteractiveMoveState::Moving(move_)) = &mut self.interactive_move {
             if window.is_none() || window == Some(move_.tile.window().id()) {
                 if move_.is_floating != floating {
@@ -3238,29 +3762,648 @@ impl<W: LayoutElement> Layout<W> {
         workspace.set_window_floating(window, floating);
     }
 
-    pub fn focus_floating(&mut self) {
-        let Some(workspace) = self.active_workspace_mut() else {
-            return;
+    pub fn toggle_window_sticky(&mut self, window: Option<&W::Id>) {
+        if let Some(InteractiveMoveState::Moving(move_)) = &mut self.interactive_move {
+            if window.is_none() || window == Some(move_.tile.window().id()) {
+                move_.tile.is_sticky = !move_.tile.is_sticky;
+                if move_.tile.is_sticky && !move_.is_floating {
+                    self.toggle_window_floating(window);
+                }
+                return;
+            }
+        }
+
+        let workspace = if let Some(window) = window {
+            Some(
+                self.workspaces_mut()
+                    .find(|ws| ws.has_window(window))
+                    .unwrap(),
+            )
+        } else {
+            self.active_workspace_mut()
         };
-        workspace.focus_floating();
-    }
 
-    pub fn focus_tiling(&mut self) {
-        let Some(workspace) = self.active_workspace_mut() else {
+        let Some(workspace) = workspace else {
             return;
         };
-        workspace.focus_tiling();
+        workspace.toggle_window_sticky(window);
     }
 
-    pub fn switch_focus_floating_tiling(&mut self) {
-        let Some(workspace) = self.active_workspace_mut() else {
-            return;
+    /// Returns whether the window is hidden in the scratchpad.
+    pub fn scratchpad_has_window(&self, window: &W::Id) -> bool {
+        self.scratchpad.has_hidden_window(window)
+    }
+
+    /// Adds a new window straight into the scratchpad, without showing it.
+    pub fn add_window_to_scratchpad(&mut self, window: W) {
+        let tile = if let Some(ws) = self.workspaces().next().map(|(_, _, ws)| ws) {
+            ws.make_tile(window)
+        } else {
+            Tile::new(
+                window,
+                Size::from((1280., 720.)),
+                1.,
+                self.clock.clone(),
+                self.options.clone(),
+            )
         };
-        workspace.switch_focus_floating_tiling();
+
+        self.scratchpad.hide(tile);
     }
 
-    pub fn move_floating_window(
-        &mut self,
+    /// Returns whether the window is hidden after a child window took over its tile.
+    pub fn is_swallowed(&self, window: &W::Id) -> bool {
+        self.swallowed.has_window(window)
+    }
+
+    /// Returns whether a new window can take over the tile of this window.
+    pub fn can_swallow(&self, parent: &W::Id) -> bool {
+        if let Some(InteractiveMoveState::Moving(move_)) = &self.interactive_move {
+            if move_.tile.window().id() == parent {
+                return false;
+            }
+        }
+
+        self.workspaces().any(|(_, _, ws)| {
+            ws.scrolling()
+                .tiles()
+                .any(|tile| tile.window().id() == parent)
+        })
+    }
+
+    /// Opens a new window in place of a tiled window, hiding the latter.
+    ///
+    /// The parent window comes back into the tile when the new window closes. The column keeps
+    /// its width and the tile keeps its height.
+    ///
+    /// If the parent can't be swallowed (see [`Self::can_swallow()`]), the window is returned
+    /// back so that it can be added as usual.
+    pub fn swallow_window(
+        &mut self,
+        parent: &W::Id,
+        window: W,
+        activate: ActivateWindow,
+    ) -> Result<Option<&Output>, W> {
+        if !self.can_swallow(parent) {
+            return Err(window);
+        }
+
+        let id = window.id().clone();
+        let activate = activate.map_smart(|| self.focus().is_some_and(|win| win.id() == parent));
+
+        let Some(ws) = self.workspaces_mut().find(|ws| {
+            ws.scrolling()
+                .tiles()
+                .any(|tile| tile.window().id() == parent)
+        }) else {
+            return Err(window);
+        };
+        let tile = ws.make_tile(window);
+        let parent_tile = ws.replace_scrolling_tile(parent, tile);
+        self.swallowed.insert(id.clone(), parent_tile);
+
+        if activate {
+            self.activate_window(&id);
+        }
+
+        Ok(self
+            .workspaces()
+            .find(|(_, _, ws)| ws.has_window(&id))
+            .and_then(|(mon, _, _)| mon.map(|mon| &mon.output)))
+    }
+
+    /// Brings back the window swallowed by a closing window.
+    ///
+    /// If the closing window is still tiled in the scrolling layout, the swallowed window takes
+    /// its tile back, and the closing window is removed from the layout. Otherwise, the swallowed
+    /// window opens anew next to where the closing window is.
+    pub fn unswallow_window(&mut self, child: &W::Id) {
+        let Some(tile) = self.swallowed.take_for_child(child) else {
+            return;
+        };
+
+        if let Some(InteractiveMoveState::Moving(move_)) = &self.interactive_move {
+            if move_.tile.window().id() == child {
+                self.add_swallowed_tile_back(tile, None);
+                return;
+            }
+        }
+
+        let ws = self.workspaces_mut().find(|ws| ws.has_window(child));
+        let Some(ws) = ws else {
+            self.add_swallowed_tile_back(tile, None);
+            return;
+        };
+
+        if ws.scrolling().tiles().any(|t| t.window().id() == child) {
+            ws.replace_scrolling_tile(child, tile);
+        } else {
+            let ws_id = ws.id();
+            self.add_swallowed_tile_back(tile, Some(ws_id));
+        }
+    }
+
+    fn add_swallowed_tile_back(&mut self, tile: Tile<W>, workspace_id: Option<WorkspaceId>) {
+        let target = match workspace_id {
+            Some(id) => AddWindowTarget::Workspace(id),
+            None => AddWindowTarget::Auto,
+        };
+
+        self.add_window(
+            tile.into_window(),
+            target,
+            None,
+            None,
+            false,
+            false,
+            ActivateWindow::No,
+        );
+    }
+
+    /// Hides a window in the scratchpad.
+    pub fn move_window_to_scratchpad(&mut self, window: Option<&W::Id>) {
+        if let Some(InteractiveMoveState::Moving(move_)) = &self.interactive_move {
+            if window.is_none() || window == Some(move_.tile.window().id()) {
+                return;
+            }
+        }
+
+        let Some(id) = window
+            .cloned()
+            .or_else(|| self.focus().map(|win| win.id().clone()))
+        else {
+            return;
+        };
+
+        if !self.workspaces().any(|(_, _, ws)| ws.has_window(&id)) {
+            return;
+        }
+
+        // Summoned windows show up floating, so drop fullscreen and maximize here.
+        self.set_fullscreen(&id, false);
+        self.set_maximized(&id, false);
+
+        if let Some(removed) = self.remove_window(&id, Transaction::new()) {
+            self.scratchpad.hide(removed.tile);
+        }
+    }
+
+    /// Hides the focused scratchpad window, or shows one on the active workspace.
+    pub fn toggle_scratchpad(&mut self) {
+        // Forget summoned windows that were closed or moved away in the meantime.
+        let mut scratchpad = mem::take(&mut self.scratchpad);
+        scratchpad.retain_summoned(|id| self.workspaces().any(|(_, _, ws)| ws.has_window(id)));
+        self.scratchpad = scratchpad;
+
+        if let Some(focus) = self.focus().map(|win| win.id().clone()) {
+            if self.scratchpad.is_summoned(&focus) {
+                self.move_window_to_scratchpad(Some(&focus));
+                return;
+            }
+        }
+
+        // Bring an already summoned window over to the active workspace.
+        if let Some(id) = self.scratchpad.last_summoned().cloned() {
+            self.move_window_to_scratchpad(Some(&id));
+            self.summon_from_scratchpad(Some(&id));
+            return;
+        }
+
+        self.summon_from_scratchpad(None);
+    }
+
+    fn summon_from_scratchpad(&mut self, window: Option<&W::Id>) {
+        let MonitorSet::Normal {
+            monitors,
+            active_monitor_idx,
+            ..
+        } = &mut self.monitor_set
+        else {
+            return;
+        };
+
+        let Some(tile) = self.scratchpad.summon(window) else {
+            return;
+        };
+
+        let mon = &mut monitors[*active_monitor_idx];
+        let ws = &mon.workspaces[mon.active_workspace_idx];
+        let width = ws.resolve_scrolling_width(tile.window(), None);
+
+        mon.add_tile(
+            tile,
+            MonitorAddWindowTarget::Auto,
+            ActivateWindow::Yes,
+            true,
+            width,
+            false,
+            true,
+            None,
+        );
+    }
+
+    /// Returns whether the window is minimized.
+    pub fn is_minimized(&self, window: &W::Id) -> bool {
+        self.workspaces()
+            .any(|(_, _, ws)| ws.has_minimized_window(window))
+    }
+
+    /// Returns all minimized windows.
+    pub fn minimized_windows(&self) -> impl Iterator<Item = &W> {
+        self.workspaces()
+            .flat_map(|(_, _, ws)| ws.minimized_tiles().map(Tile::window))
+    }
+
+    /// Minimizes a window into the restore list of its workspace.
+    pub fn minimize_window(&mut self, window: Option<&W::Id>) {
+        if let Some(InteractiveMoveState::Moving(move_)) = &self.interactive_move {
+            if window.is_none() || window == Some(move_.tile.window().id()) {
+                return;
+            }
+        }
+
+        let Some(id) = window
+            .cloned()
+            .or_else(|| self.focus().map(|win| win.id().clone()))
+        else {
+            return;
+        };
+
+        if !self.workspaces().any(|(_, _, ws)| ws.has_window(&id)) {
+            return;
+        }
+
+        if let Some(InteractiveMoveState::Starting { window_id, .. }) = &self.interactive_move {
+            if *window_id == id {
+                self.interactive_move_end(&id);
+            }
+        }
+
+        // Minimized windows come back as regular windows, so drop fullscreen and maximize here.
+        self.set_fullscreen(&id, false);
+        self.set_maximized(&id, false);
+
+        if let Some(ws) = self.workspaces_mut().find(|ws| ws.has_window(&id)) {
+            ws.minimize_window(&id);
+        }
+    }
+
+    /// Restores a minimized window and focuses it.
+    ///
+    /// Without a window, restores the most recently minimized window of the active workspace.
+    pub fn restore_minimized_window(&mut self, window: Option<&W::Id>) {
+        match window {
+            Some(id) => {
+                if self.is_minimized(id) {
+                    self.activate_window(id);
+                }
+            }
+            None => {
+                if let Some(ws) = self.active_workspace_mut() {
+                    ws.restore_minimized_window(None);
+                }
+            }
+        }
+    }
+
+    /// Returns where every window currently is.
+    fn layout_snapshot(&self) -> Option<LayoutSnapshot<W::Id>> {
+        let MonitorSet::Normal { monitors, .. } = &self.monitor_set else {
+            return None;
+        };
+
+        let workspaces = monitors
+            .iter()
+            .flat_map(|mon| {
+                mon.workspaces
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, ws)| WorkspaceSnapshot::from_workspace(ws, &mon.output, idx))
+            })
+            .filter(|ws| !ws.is_empty())
+            .collect();
+
+        Some(LayoutSnapshot {
+            workspaces,
+            focus: self.focus().map(|win| win.id().clone()),
+        })
+    }
+
+    /// Records the layout before a structural change so that it can be undone.
+    fn record_history(&mut self) {
+        if let Some(snapshot) = self.layout_snapshot() {
+            self.history.record(snapshot);
+        }
+    }
+
+    pub fn undo_layout_change(&mut self) {
+        if self.interactive_move.is_some() {
+            return;
+        }
+
+        let Some(current) = self.layout_snapshot() else {
+            return;
+        };
+
+        let mut history = mem::take(&mut self.history);
+        if let Some(snapshot) = history.undo(current) {
+            self.apply_layout_snapshot(snapshot, &mut history);
+        }
+        self.history = history;
+    }
+
+    pub fn redo_layout_change(&mut self) {
+        if self.interactive_move.is_some() {
+            return;
+        }
+
+        let Some(current) = self.layout_snapshot() else {
+            return;
+        };
+
+        let mut history = mem::take(&mut self.history);
+        if let Some(snapshot) = history.redo(current) {
+            self.apply_layout_snapshot(snapshot, &mut history);
+        }
+        self.history = history;
+    }
+
+    /// Swaps the places of two windows, which can be on different workspaces and monitors.
+    ///
+    /// Without a window, swaps the focused window. Focus stays on the same window.
+    pub fn swap_windows(&mut self, window: Option<&W::Id>, other: &W::Id) {
+        if self.interactive_move.is_some() {
+            return;
+        }
+
+        let Some(id) = window
+            .cloned()
+            .or_else(|| self.focus().map(|win| win.id().clone()))
+        else {
+            return;
+        };
+
+        if id == *other {
+            return;
+        }
+
+        let Some(mut snapshot) = self.layout_snapshot() else {
+            return;
+        };
+        if !snapshot.contains(&id) || !snapshot.contains(other) {
+            return;
+        }
+
+        self.record_history();
+        snapshot.swap_windows(&id, other);
+
+        let mut history = mem::take(&mut self.history);
+        self.apply_layout_snapshot(snapshot, &mut history);
+        self.history = history;
+    }
+
+    /// Moves windows back to where they were in the snapshot.
+    ///
+    /// Windows opened since the snapshot stay where they are, and windows closed or hidden since
+    /// are skipped. Workspaces that changed are rebuilt from the snapshot, and their windows
+    /// animate from their previous positions.
+    fn apply_layout_snapshot(
+        &mut self,
+        mut snapshot: LayoutSnapshot<W::Id>,
+        history: &mut LayoutHistory<W::Id>,
+    ) {
+        let Some(current) = self.layout_snapshot() else {
+            return;
+        };
+        snapshot.retain_windows(|id| current.contains(id));
+
+        // Put workspaces back on their monitors.
+        for ws_snapshot in &snapshot.workspaces {
+            let MonitorSet::Normal { monitors, .. } = &self.monitor_set else {
+                unreachable!()
+            };
+
+            if !monitors.iter().any(|mon| mon.output == ws_snapshot.output) {
+                continue;
+            }
+
+            let location = monitors
+                .iter()
+                .find_map(|mon| Some((mon.output.clone(), mon.idx_of_ws(ws_snapshot.id)?)));
+            if let Some((output, idx)) = location {
+                if output != ws_snapshot.output {
+                    self.move_workspace_to_output_by_id(idx, Some(output), &ws_snapshot.output);
+                }
+            }
+        }
+
+        let MonitorSet::Normal {
+            monitors,
+            active_monitor_idx,
+            ..
+        } = &mut self.monitor_set
+        else {
+            unreachable!()
+        };
+
+        // Recreate workspaces that were removed after they became empty.
+        for ws_snapshot in &mut snapshot.workspaces {
+            if monitors.iter().any(|mon| mon.has_ws(ws_snapshot.id)) {
+                continue;
+            }
+
+            let mon_idx = monitors
+                .iter()
+                .position(|mon| mon.output == ws_snapshot.output)
+                .unwrap_or(*active_monitor_idx);
+            let mon = &mut monitors[mon_idx];
+            let idx = ws_snapshot.idx.min(mon.workspaces.len() - 1);
+            mon.add_workspace_at(idx);
+
+            let id = mon.workspaces[idx].id();
+            history.replace_workspace_id(ws_snapshot.id, id);
+            ws_snapshot.id = id;
+        }
+
+        let is_changed = |id: WorkspaceId| match (snapshot.workspace(id), current.workspace(id)) {
+            (Some(target), Some(current)) => {
+                let mut current = current.clone();
+                current.retain_windows(|id| snapshot.contains(id));
+                !target.has_same_windows(&current)
+            }
+            (Some(_), None) => true,
+            (None, Some(current)) => current.windows().any(|id| snapshot.contains(id)),
+            (None, None) => false,
+        };
+        let changed: Vec<WorkspaceId> = monitors
+            .iter()
+            .flat_map(|mon| mon.workspaces.iter().map(|ws| ws.id()))
+            .filter(|id| is_changed(*id))
+            .collect();
+
+        // Take windows out of the changed workspaces, remembering where they were.
+        let mut old_positions = Vec::new();
+        let mut removed_tiles = Vec::new();
+        for mon in monitors.iter_mut() {
+            for ws in &mut mon.workspaces {
+                let ws_id = ws.id();
+                if !changed.contains(&ws_id) {
+                    continue;
+                }
+
+                for (tile, pos, _) in ws.tiles_with_render_positions() {
+                    old_positions.push((tile.window().id().clone(), ws_id, pos));
+                }
+
+                let ids: Vec<_> = ws
+                    .windows()
+                    .map(|win| win.id().clone())
+                    .filter(|id| snapshot.contains(id))
+                    .collect();
+                for id in ids {
+                    let mut removed = ws.remove_tile(&id, Transaction::new());
+                    if snapshot.workspace(ws_id).is_none_or(|ws| !ws.contains(&id)) {
+                        removed.tile.stop_move_animations();
+                    }
+                    removed_tiles.push((id, removed));
+                }
+            }
+        }
+
+        let mut take = |id: &W::Id| {
+            let idx = removed_tiles
+                .iter()
+                .position(|(removed, _)| removed == id)?;
+            Some(removed_tiles.swap_remove(idx).1)
+        };
+
+        // Put the windows back as in the snapshot.
+        for ws_snapshot in &snapshot.workspaces {
+            if !changed.contains(&ws_snapshot.id) {
+                continue;
+            }
+
+            let mon = monitors
+                .iter_mut()
+                .find(|mon| mon.has_ws(ws_snapshot.id))
+                .unwrap();
+
+            for (col_idx, column) in ws_snapshot.columns.iter().enumerate() {
+                let mut first = None;
+                for id in &column.windows {
+                    let Some(RemovedTile { tile, .. }) = take(id) else {
+                        continue;
+                    };
+
+                    if first.is_none() {
+                        let target = MonitorAddWindowTarget::Workspace {
+                            id: ws_snapshot.id,
+                            column_idx: Some(col_idx),
+                        };
+                        mon.add_tile(
+                            tile,
+                            target,
+                            ActivateWindow::No,
+                            false,
+                            column.width,
+                            column.is_full_width,
+                            false,
+                            None,
+                        );
+                        first = Some(id);
+                    } else {
+                        let ws_idx = mon.idx_of_ws(ws_snapshot.id).unwrap();
+                        mon.add_tile_to_column(ws_idx, col_idx, None, tile, false, false);
+                    }
+                }
+
+                if let Some(first) = first {
+                    let ws_idx = mon.idx_of_ws(ws_snapshot.id).unwrap();
+                    let ws = &mut mon.workspaces[ws_idx];
+                    if ws.is_scrolling_layout() && column.display != ColumnDisplay::Normal {
+                        ws.scrolling_mut()
+                            .set_window_column_display(first, column.display);
+                    }
+                }
+            }
+
+            for id in &ws_snapshot.floating {
+                let Some(removed) = take(id) else {
+                    continue;
+                };
+
+                let target = MonitorAddWindowTarget::Workspace {
+                    id: ws_snapshot.id,
+                    column_idx: None,
+                };
+                mon.add_tile(
+                    removed.tile,
+                    target,
+                    ActivateWindow::No,
+                    false,
+                    removed.width,
+                    removed.is_full_width,
+                    true,
+                    None,
+                );
+            }
+        }
+
+        // Animate windows that stayed on their workspace from where they were.
+        for mon in monitors.iter_mut() {
+            for ws in &mut mon.workspaces {
+                let ws_id = ws.id();
+                if !changed.contains(&ws_id) {
+                    continue;
+                }
+
+                for (tile, pos) in ws.tiles_with_render_positions_mut(false) {
+                    let old = old_positions
+                        .iter()
+                        .find(|(id, old_ws_id, _)| id == tile.window().id() && *old_ws_id == ws_id);
+                    if let Some((_, _, old_pos)) = old {
+                        tile.animate_move_from(*old_pos - pos);
+                    }
+                }
+            }
+        }
+
+        for mon in monitors.iter_mut() {
+            if mon.workspace_switch.is_none() {
+                mon.clean_up_workspaces();
+            }
+        }
+
+        if let Some(focus) = &snapshot.focus {
+            if self.has_window(focus) {
+                self.activate_window(focus);
+            }
+        }
+    }
+
+    pub fn focus_floating(&mut self) {
+        let Some(workspace) = self.active_workspace_mut() else {
+            return;
+        };
+        workspace.focus_floating();
+    }
+
+    pub fn focus_tiling(&mut self) {
+        let Some(workspace) = self.active_workspace_mut() else {
+            return;
+        };
+        workspace.focus_tiling();
+    }
+
+    pub fn switch_focus_floating_tiling(&mut self) {
+        let Some(workspace) = self.active_workspace_mut() else {
+            return;
+        };
+        workspace.switch_focus_floating_tiling();
+    }
+
+    pub fn move_floating_window(
+        &mut self,
         id: Option<&W::Id>,
         x: PositionChange,
         y: PositionChange,
@@ -3284,6 +4427,25 @@ impl<W: LayoutElement> Layout<W> {
         workspace.move_floating_window(id, x, y, animate);
     }
 
+    pub fn snap_floating_window(&mut self, id: Option<&W::Id>, zone: SnapZone, animate: bool) {
+        if let Some(InteractiveMoveState::Moving(move_)) = &mut self.interactive_move {
+            if id.is_none() || id == Some(move_.tile.window().id()) {
+                return;
+            }
+        }
+
+        let workspace = if let Some(id) = id {
+            Some(self.workspaces_mut().find(|ws| ws.has_window(id)).unwrap())
+        } else {
+            self.active_workspace_mut()
+        };
+
+        let Some(workspace) = workspace else {
+            return;
+        };
+        workspace.snap_floating_window(id, zone, animate);
+    }
+
     pub fn focus_output(&mut self, output: &Output) {
         if let MonitorSet::Normal {
             monitors,
@@ -3307,6 +4469,8 @@ impl<W: LayoutElement> Layout<W> {
         target_ws_idx: Option<usize>,
         activate: ActivateWindow,
     ) {
+        self.record_history();
+
         if let Some(InteractiveMoveState::Moving(move_)) = &mut self.interactive_move {
             if window.is_none() || window == Some(move_.tile.window().id()) {
                 return;
@@ -3408,6 +4572,8 @@ impl<W: LayoutElement> Layout<W> {
         target_ws_idx: Option<usize>,
         activate: bool,
     ) {
+        self.record_history();
+
         if let MonitorSet::Normal {
             monitors,
             active_monitor_idx,
@@ -3422,7 +4588,7 @@ impl<W: LayoutElement> Layout<W> {
             let current = &mut monitors[*active_monitor_idx];
             let ws = current.active_workspace();
 
-            if ws.floating_is_active() {
+            if ws.floating_is_active() || !ws.is_scrolling_layout() {
                 self.move_to_output(None, output, None, ActivateWindow::Smart);
                 return;
             }
@@ -3459,6 +4625,8 @@ impl<W: LayoutElement> Layout<W> {
         old_output: Option<Output>,
         new_output: &Output,
     ) -> bool {
+        self.record_history();
+
         let MonitorSet::Normal {
             monitors,
             active_monitor_idx,
@@ -3665,6 +4833,20 @@ impl<W: LayoutElement> Layout<W> {
         None
     }
 
+    /// Returns whether view offset gestures on this output run along the vertical axis.
+    ///
+    /// This is the case when the active workspace uses the vertical scrolling orientation. Then
+    /// gestures swap their axes, and workspace switch gestures use the horizontal axis. The
+    /// overview always keeps the regular axes since workspaces remain stacked vertically there.
+    pub fn is_view_offset_gesture_vertical(&self, output: &Output) -> bool {
+        if self.overview_open {
+            return false;
+        }
+
+        self.monitor_for_output(output)
+            .is_some_and(|mon| mon.active_workspace_ref().is_scrolling_vertical())
+    }
+
     pub fn view_offset_gesture_begin(
         &mut self,
         output: &Output,
@@ -4027,6 +5209,8 @@ impl<W: LayoutElement> Layout<W> {
                     is_full_width,
                     is_floating,
                     pointer_ratio_within_window,
+                    snap_guides: SnapGuides::default(),
+                    snap_zone: None,
                     output_config,
                     workspace_config,
                 };
@@ -4091,6 +5275,37 @@ impl<W: LayoutElement> Layout<W> {
 
                 move_.pointer_pos_within_output = pointer_pos_within_output;
 
+                // Dragging floating windows to an output edge activates a snap zone.
+                move_.snap_zone = None;
+                let zones_config = move_.tile.options.layout.snap_zones;
+                if move_.is_floating && !zones_config.off && self.overview_progress.is_none() {
+                    move_.snap_zone = snap_zone_at(
+                        pointer_pos_within_output,
+                        output_size(&output),
+                        &zones_config,
+                    );
+                }
+
+                // Otherwise, snap floating windows to the edges on the workspace under the pointer.
+                move_.snap_guides.clear();
+                let snap_config = move_.tile.options.layout.floating_snap;
+                if move_.is_floating
+                    && move_.snap_zone.is_none()
+                    && !snap_config.off
+                    && self.overview_progress.is_none()
+                {
+                    if let Some((ws, ws_geo)) = self
+                        .monitor_for_output(&output)
+                        .and_then(|mon| mon.workspace_under(pointer_pos_within_output))
+                    {
+                        let edges = ws.floating().snap_edges(None).offset(ws_geo.loc);
+                        let rect =
+                            Rectangle::new(move_.tile_render_location(1.), move_.tile.tile_size());
+                        let snap = edges.snap_rect(rect, snap_config.distance);
+                        move_.snap_guides.set_snap(snap);
+                    }
+                }
+
                 self.interactive_move = Some(InteractiveMoveState::Moving(move_));
             }
         }
@@ -4189,7 +5404,9 @@ impl<W: LayoutElement> Layout<W> {
                                 let ws_idx = mon.idx_of_ws(ws_id).unwrap();
 
                                 let position = if move_.is_floating {
-                                    InsertPosition::Floating
+                                    move_
+                                        .snap_zone
+                                        .map_or(InsertPosition::Floating, InsertPosition::SnapZone)
                                 } else {
                                     let pos_within_workspace =
                                         (move_.pointer_pos_within_output - geo.loc).downscale(zoom);
@@ -4274,7 +5491,7 @@ impl<W: LayoutElement> Layout<W> {
                             allow_to_activate_workspace,
                         );
                     }
-                    InsertPosition::Floating => {
+                    InsertPosition::Floating | InsertPosition::SnapZone(_) => {
                         let mut tile = move_.tile;
                         tile.floating_pos = None;
 
@@ -4318,6 +5535,11 @@ impl<W: LayoutElement> Layout<W> {
                             true,
                             None,
                         );
+
+                        if let InsertPosition::SnapZone(zone) = position {
+                            let ws = mon.workspaces.iter_mut().find(|ws| ws.has_window(&win_id));
+                            ws.unwrap().snap_floating_window(Some(&win_id), zone, false);
+                        }
                     }
                 }
 
@@ -4337,7 +5559,10 @@ impl<W: LayoutElement> Layout<W> {
                 // Interactive move into floating barely animates (it doesn't really move after
                 // being dropped), so setting it as moving between workspaces would just cause it to
                 // awkwardly sit unclipped for a moment before the animation runs out.
-                if !matches!(position, InsertPosition::Floating) {
+                if !matches!(
+                    position,
+                    InsertPosition::Floating | InsertPosition::SnapZone(_)
+                ) {
                     tile.set_anim_y_between_workspaces();
                 }
             }
@@ -4854,6 +6079,17 @@ impl<W: LayoutElement> Layout<W> {
                     zoom,
                 ));
             });
+
+        move_
+            .snap_guides
+            .render(Point::from((0., 0.)), &mut |elem| {
+                let elem = TileRenderElement::from(elem);
+                push(RescaleRenderElement::from_element(
+                    elem,
+                    Point::from((0, 0)),
+                    1.,
+                ));
+            });
     }
 
     pub fn refresh(&mut self, is_active: bool) {
@@ -4861,13 +6097,18 @@ impl<W: LayoutElement> Layout<W> {
 
         self.is_active = is_active;
 
+        self.scratchpad.refresh();
+        self.swallowed.refresh();
+
         let mut ongoing_scrolling_dnd = self.dnd.is_some().then_some(true);
 
         if let Some(InteractiveMoveState::Moving(move_)) = &mut self.interactive_move {
+            let is_sticky = move_.is_floating && move_.tile.is_sticky;
             let win = move_.tile.window_mut();
 
             win.set_active_in_column(true);
             win.set_floating(move_.is_floating);
+            win.set_sticky(is_sticky);
             win.set_activated(true);
 
             win.set_interactive_resize(None);
@@ -4,6 +4,7 @@ use std::rc::Rc;
 use std::time::Duration;
 
 use niri_config::{CornerRadius, LayoutPart};
+use niri_ipc::SnapZone;
 use smithay::backend::renderer::element::utils::{
     CropRenderElement, Relocate, RelocateRenderElement, RescaleRenderElement,
 };
@@ -132,6 +133,7 @@ pub(super) enum InsertPosition {
     NewColumn(usize),
     InColumn(usize, usize),
     Floating,
+    SnapZone(SnapZone),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -457,6 +459,7 @@ impl<W: LayoutElement> Monitor<W> {
 
         let prev_active_idx = self.active_workspace_idx;
         self.active_workspace_idx = idx;
+        self.gather_sticky_windows();
 
         let config = config.unwrap_or(self.options.animations.workspace_switch.0);
 
@@ -491,6 +494,40 @@ impl<W: LayoutElement> Monitor<W> {
         }
     }
 
+    /// Moves sticky windows from the other workspaces to the active one.
+    fn gather_sticky_windows(&mut self) {
+        let active_idx = self.active_workspace_idx;
+        for idx in 0..self.workspaces.len() {
+            if idx == active_idx {
+                continue;
+            }
+
+            let ids: Vec<_> = self.workspaces[idx].sticky_windows().cloned().collect();
+            for id in ids {
+                // Keep the focus on a sticky window that had it.
+                let ws = &self.workspaces[idx];
+                let was_focused =
+                    ws.floating_is_active() && ws.active_window().is_some_and(|w| w.id() == &id);
+                let activate = if was_focused {
+                    ActivateWindow::Yes
+                } else {
+                    ActivateWindow::No
+                };
+
+                let removed = self.workspaces[idx].remove_tile(&id, Transaction::new());
+                self.workspaces[active_idx].add_tile(
+                    removed.tile,
+                    WorkspaceAddWindowTarget::Auto,
+                    activate,
+                    removed.width,
+                    removed.is_full_width,
+                    true,
+                    None,
+                );
+            }
+        }
+    }
+
     pub(super) fn resolve_add_window_target<'a>(
         &mut self,
         target: MonitorAddWindowTarget<'a, W>,
@@ -926,7 +963,7 @@ impl<W: LayoutElement> Monitor<W> {
         }
 
         let workspace = &mut self.workspaces[source_workspace_idx];
-        if workspace.floating_is_active() {
+        if workspace.floating_is_active() || !workspace.is_scrolling_layout() {
             let activate = if activate {
                 ActivateWindow::Smart
             } else {
@@ -962,11 +999,14 @@ impl<W: LayoutElement> Monitor<W> {
         self.add_column(new_idx, column, activate, Some(config));
 
         let new_idx = self.idx_of_ws(new_id).unwrap();
-        let (column, new_render_pos) = self.workspaces[new_idx]
+        // In non-scrolling layout modes, the column is split into separate tiles.
+        let Some((column, new_render_pos)) = self.workspaces[new_idx]
             .scrolling_mut()
             .columns_with_render_positions_mut()
             .find(|(col, _pos)| col.id() == id)
-            .unwrap();
+        else {
+            return;
+        };
         column.animate_move_from_with_config(old_render_pos - new_render_pos, config);
         column.set_anim_y_between_workspaces();
     }
@@ -2083,6 +2123,7 @@ impl<W: LayoutElement> Monitor<W> {
             velocity,
             self.options.animations.workspace_switch.0,
         )));
+        self.gather_sticky_windows();
 
         true
     }
@@ -0,0 +1,300 @@
+//! Saving and restoring the window layout across compositor restarts.
+//!
+//! The layout is saved as a tree of monitors, workspaces and columns, where every window is
+//! recorded as a set of hints: its app id and title. After a restart, newly mapped windows are
+//! matched against these hints and put back into the slot they occupied before, with their saved
+//! height or floating position.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use niri_ipc::ColumnDisplay;
+use serde::{Deserialize, Serialize};
+use smithay::utils::Point;
+
+use super::scrolling::{ColumnWidth, WindowHeight};
+use super::workspace::{Workspace, WorkspaceId};
+use super::{LayoutElement, SizeFrac};
+
+/// How long after loading a saved layout to keep matching new windows against it.
+pub const RESTORE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Properties used to recognize a window after a restart.
+///
+/// These have to survive the restart, so things like the pid can't be used.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowHints {
+    pub app_id: Option<String>,
+    pub title: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedLayout {
+    pub monitors: Vec<SavedMonitor>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedMonitor {
+    /// Name of the output, as matched by `output_matches_name()`.
+    pub output: String,
+    pub workspaces: Vec<SavedWorkspace>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedWorkspace {
+    pub name: Option<String>,
+    pub columns: Vec<SavedColumn>,
+    pub floating: Vec<SavedFloating>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedColumn {
+    pub width: ColumnWidth,
+    pub is_full_width: bool,
+    pub display: ColumnDisplay,
+    pub windows: Vec<SavedTile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedTile {
+    pub hints: WindowHints,
+    pub height: WindowHeight,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedFloating {
+    pub hints: WindowHints,
+    /// Position relative to the working area, as a fraction of its size.
+    pub x: f64,
+    pub y: f64,
+}
+
+/// Position of a window in a [`SavedLayout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RestoreSlot {
+    pub monitor: usize,
+    pub workspace: usize,
+    pub kind: RestoreSlotKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestoreSlotKind {
+    Tiled { column: usize, tile: usize },
+    Floating(usize),
+}
+
+/// A saved layout that windows are currently being restored into.
+#[derive(Debug)]
+pub(super) struct PendingRestore<W: LayoutElement> {
+    pub layout: SavedLayout,
+    /// Slots that already have their window back, along with the window.
+    pub restored: Vec<(RestoreSlot, W::Id)>,
+    /// Workspaces picked for unnamed saved workspaces, keyed by (monitor, workspace) index.
+    pub workspaces: HashMap<(usize, usize), WorkspaceId>,
+    /// Time after which the restore is abandoned.
+    pub deadline: Duration,
+}
+
+impl WindowHints {
+    /// Returns how well these hints match the `saved` ones, or `None` if they don't match.
+    ///
+    /// The app id must match whenever it was saved. Windows without a saved app id must match by
+    /// title instead.
+    pub fn match_score(&self, saved: &WindowHints) -> Option<u32> {
+        let app_id = saved.app_id.is_some() && self.app_id == saved.app_id;
+        let title = saved.title.is_some() && self.title == saved.title;
+
+        if saved.app_id.is_some() {
+            if !app_id {
+                return None;
+            }
+        } else if !title {
+            return None;
+        }
+
+        Some(u32::from(title) * 2 + u32::from(app_id))
+    }
+}
+
+impl SavedLayout {
+    pub fn is_empty(&self) -> bool {
+        self.monitors.iter().all(|mon| mon.workspaces.is_empty())
+    }
+
+    fn slots(&self) -> impl Iterator<Item = (RestoreSlot, &WindowHints)> + '_ {
+        self.monitors.iter().enumerate().flat_map(|(m, mon)| {
+            mon.workspaces.iter().enumerate().flat_map(move |(w, ws)| {
+                let tiled = ws.columns.iter().enumerate().flat_map(move |(c, col)| {
+                    col.windows.iter().enumerate().map(move |(t, saved)| {
+                        let kind = RestoreSlotKind::Tiled { column: c, tile: t };
+                        (RestoreSlot::new(m, w, kind), &saved.hints)
+                    })
+                });
+                let floating = ws.floating.iter().enumerate().map(move |(f, saved)| {
+                    let kind = RestoreSlotKind::Floating(f);
+                    (RestoreSlot::new(m, w, kind), &saved.hints)
+                });
+                tiled.chain(floating)
+            })
+        })
+    }
+}
+
+impl SavedWorkspace {
+    pub fn from_workspace<W: LayoutElement>(
+        ws: &Workspace<W>,
+        hints: &impl Fn(&W) -> WindowHints,
+    ) -> Self {
+        let mut columns: Vec<_> = ws
+            .scrolling()
+            .columns()
+            .map(|col| SavedColumn {
+                width: col.desired_width(),
+                is_full_width: col.is_full_width(),
+                display: col.display_mode(),
+                windows: col
+                    .tiles()
+                    .zip(col.window_heights())
+                    .map(|((tile, _), height)| SavedTile {
+                        hints: hints(tile.window()),
+                        height,
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        // Windows in the other tiled layouts are saved as one column each, which puts them back
+        // in the same order.
+        let default_width = ws
+            .scrolling()
+            .columns()
+            .next()
+            .map_or(ColumnWidth::Proportion(0.5), |col| col.desired_width());
+        columns.extend(ws.tiling().tiles().map(|tile| SavedColumn {
+            width: default_width,
+            is_full_width: false,
+            display: ColumnDisplay::Normal,
+            windows: vec![SavedTile {
+                hints: hints(tile.window()),
+                height: WindowHeight::auto_1(),
+            }],
+        }));
+
+        Self {
+            name: ws.name().cloned(),
+            columns,
+            floating: ws
+                .floating()
+                .tiles_with_size_frac_positions()
+                .map(|(tile, pos)| SavedFloating {
+                    hints: hints(tile.window()),
+                    x: pos.x,
+                    y: pos.y,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl RestoreSlot {
+    fn new(monitor: usize, workspace: usize, kind: RestoreSlotKind) -> Self {
+        Self {
+            monitor,
+            workspace,
+            kind,
+        }
+    }
+
+    pub fn is_same_workspace(&self, other: &RestoreSlot) -> bool {
+        self.monitor == other.monitor && self.workspace == other.workspace
+    }
+}
+
+impl<W: LayoutElement> PendingRestore<W> {
+    pub fn new(layout: SavedLayout, deadline: Duration) -> Self {
+        Self {
+            layout,
+            restored: Vec::new(),
+            workspaces: HashMap::new(),
+            deadline,
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.layout.slots().count() == self.restored.len()
+    }
+
+    /// Finds the free slot best matching `hints`.
+    pub fn find_slot(&self, hints: &WindowHints) -> Option<RestoreSlot> {
+        let mut best: Option<(RestoreSlot, u32)> = None;
+
+        for (slot, saved) in self.layout.slots() {
+            if self.restored.iter().any(|(s, _)| *s == slot) {
+                continue;
+            }
+
+            let Some(score) = hints.match_score(saved) else {
+                continue;
+            };
+
+            if best.is_none_or(|(_, best_score)| best_score < score) {
+                best = Some((slot, score));
+            }
+        }
+
+        best.map(|(slot, _)| slot)
+    }
+
+    pub fn column(&self, slot: RestoreSlot) -> Option<&SavedColumn> {
+        let RestoreSlotKind::Tiled { column, .. } = slot.kind else {
+            return None;
+        };
+        let ws = &self.layout.monitors[slot.monitor].workspaces[slot.workspace];
+        ws.columns.get(column)
+    }
+
+    pub fn workspace(&self, slot: RestoreSlot) -> &SavedWorkspace {
+        &self.layout.monitors[slot.monitor].workspaces[slot.workspace]
+    }
+}
+
+impl SavedFloating {
+    pub fn pos(&self) -> Point<f64, SizeFrac> {
+        Point::from((self.x, self.y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hints(app_id: Option<&str>, title: Option<&str>) -> WindowHints {
+        WindowHints {
+            app_id: app_id.map(String::from),
+            title: title.map(String::from),
+        }
+    }
+
+    #[test]
+    fn match_score_requires_app_id() {
+        let saved = hints(Some("foot"), Some("~"));
+
+        assert_eq!(hints(Some("foot"), None).match_score(&saved), Some(1));
+        assert_eq!(hints(Some("foot"), Some("~")).match_score(&saved), Some(3));
+        assert_eq!(hints(Some("kitty"), Some("~")).match_score(&saved), None);
+        assert_eq!(hints(None, Some("~")).match_score(&saved), None);
+    }
+
+    #[test]
+    fn match_score_without_app_id() {
+        let saved = hints(None, Some("Untitled"));
+
+        assert_eq!(hints(None, Some("Untitled")).match_score(&saved), Some(2));
+        assert_eq!(
+            hints(Some("foo"), Some("Untitled")).match_score(&saved),
+            Some(2)
+        );
+        assert_eq!(hints(None, Some("Other")).match_score(&saved), None);
+        assert_eq!(hints(None, None).match_score(&saved), None);
+    }
+}
@@ -0,0 +1,133 @@
+//! Storage for windows hidden outside of every workspace.
+//!
+//! Windows moved to the scratchpad are taken out of their workspace and kept here until they are
+//! summoned. Summoned windows open in the floating layout of the active workspace, and since they
+//! pass through `FloatingSpace`, they keep their floating size and position between summons.
+
+use std::collections::VecDeque;
+
+use smithay::reexports::wayland_server::protocol::wl_surface::WlSurface;
+
+use super::tile::Tile;
+use super::LayoutElement;
+
+#[derive(Debug)]
+pub struct Scratchpad<W: LayoutElement> {
+    /// Hidden windows, in the order they will be summoned.
+    hidden: VecDeque<Tile<W>>,
+    /// Scratchpad windows currently summoned onto a workspace, most recent last.
+    summoned: Vec<W::Id>,
+}
+
+impl<W: LayoutElement> Default for Scratchpad<W> {
+    fn default() -> Self {
+        Self {
+            hidden: VecDeque::new(),
+            summoned: Vec::new(),
+        }
+    }
+}
+
+impl<W: LayoutElement> Scratchpad<W> {
+    pub fn has_hidden_window(&self, window: &W::Id) -> bool {
+        self.hidden.iter().any(|tile| tile.window().id() == window)
+    }
+
+    pub fn is_summoned(&self, window: &W::Id) -> bool {
+        self.summoned.contains(window)
+    }
+
+    /// Hides a tile, putting it at the back of the summon queue.
+    pub fn hide(&mut self, tile: Tile<W>) {
+        let id = tile.window().id();
+        self.summoned.retain(|win| win != id);
+        self.hidden.push_back(tile);
+    }
+
+    /// Takes the next hidden tile to summon, or a specific one.
+    pub fn summon(&mut self, window: Option<&W::Id>) -> Option<Tile<W>> {
+        let idx = match window {
+            Some(id) => self
+                .hidden
+                .iter()
+                .position(|tile| tile.window().id() == id)?,
+            None => 0,
+        };
+        let tile = self.hidden.remove(idx)?;
+        self.summoned.push(tile.window().id().clone());
+        Some(tile)
+    }
+
+    /// Removes a hidden tile, forgetting about it.
+    pub fn remove(&mut self, window: &W::Id) -> Option<Tile<W>> {
+        let idx = self
+            .hidden
+            .iter()
+            .position(|tile| tile.window().id() == window)?;
+        self.hidden.remove(idx)
+    }
+
+    /// Returns the most recently summoned window, if any.
+    pub fn last_summoned(&self) -> Option<&W::Id> {
+        self.summoned.last()
+    }
+
+    /// Forgets summoned windows that no longer satisfy `is_present`.
+    pub fn retain_summoned(&mut self, mut is_present: impl FnMut(&W::Id) -> bool) {
+        self.summoned.retain(|id| is_present(id));
+    }
+
+    pub fn tiles(&self) -> impl Iterator<Item = &Tile<W>> + '_ {
+        self.hidden.iter()
+    }
+
+    pub fn tiles_mut(&mut self) -> impl Iterator<Item = &mut Tile<W>> + '_ {
+        self.hidden.iter_mut()
+    }
+
+    pub fn find_wl_surface(&self, wl_surface: &WlSurface) -> Option<&W> {
+        self.tiles()
+            .map(Tile::window)
+            .find(|win| win.is_wl_surface(wl_surface))
+    }
+
+    pub fn find_wl_surface_mut(&mut self, wl_surface: &WlSurface) -> Option<&mut W> {
+        self.tiles_mut()
+            .map(Tile::window_mut)
+            .find(|win| win.is_wl_surface(wl_surface))
+    }
+
+    pub fn refresh(&mut self) {
+        for tile in &mut self.hidden {
+            let win = tile.window_mut();
+
+            win.set_active_in_column(true);
+            win.set_floating(true);
+            win.set_sticky(false);
+            win.set_activated(false);
+            win.set_interactive_resize(None);
+
+            win.send_pending_configure();
+            win.refresh();
+        }
+    }
+
+    #[cfg(test)]
+    pub fn verify_invariants(&self) {
+        for (idx, tile) in self.hidden.iter().enumerate() {
+            let id = tile.window().id();
+            assert!(
+                !self.summoned.contains(id),
+                "hidden windows must not be marked as summoned"
+            );
+            assert!(
+                !self
+                    .hidden
+                    .iter()
+                    .skip(idx + 1)
+                    .any(|t| t.window().id() == id),
+                "hidden windows must be unique"
+            );
+        }
+    }
+}
@@ -1,14 +1,16 @@
 use std::cmp::{max, min};
 use std::iter::{self, zip};
+use std::mem;
 use std::rc::Rc;
 use std::time::Duration;
 
 use niri_config::utils::MergeWith as _;
-use niri_config::{CenterFocusedColumn, PresetSize, Struts};
+use niri_config::{CenterFocusedColumn, Orientation, PresetSize, Struts};
 use niri_ipc::{ColumnDisplay, SizeChange, WindowLayout};
 use ordered_float::NotNan;
+use serde::{Deserialize, Serialize};
 use smithay::backend::renderer::gles::GlesRenderer;
-use smithay::utils::{Logical, Point, Rectangle, Scale, Serial, Size};
+use smithay::utils::{Coordinate, Logical, Point, Rectangle, Scale, Serial, Size};
 
 use super::closing_window::{ClosingWindow, ClosingWindowRenderElement};
 use super::monitor::InsertPosition;
@@ -250,15 +252,15 @@ struct TileData {
     /// This is window height, not tile height, so it excludes tile decorations.
     height: WindowHeight,
 
-    /// Cached actual size of the tile.
+    /// Cached actual size of the tile, in column space.
     size: Size<f64, Logical>,
 
-    /// Cached whether the tile is being interactively resized by its left edge.
+    /// Cached whether the tile is being interactively resized by its left edge, in column space.
     interactively_resizing_by_left_edge: bool,
 }
 
 /// Width of a column.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum ColumnWidth {
     /// Proportion of the current view width.
     Proportion(f64),
@@ -279,7 +281,7 @@ pub enum ColumnWidth {
 /// you this behavior. The main reason to set a different window height, then, is when you want
 /// something in the window to fit exactly, e.g. to fit 30 lines in a terminal, which corresponds
 /// to the `Fixed` variant.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum WindowHeight {
     /// Automatically computed *tile* height, distributed across the column according to weights.
     ///
@@ -320,7 +322,11 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         clock: Clock,
         options: Rc<Options>,
     ) -> Self {
+        let vertical = options.layout.orientation == Orientation::Vertical;
         let working_area = compute_working_area(parent_area, scale, options.layout.struts);
+        let view_size = view_size.orient(vertical);
+        let working_area = working_area.orient(vertical);
+        let parent_area = parent_area.orient(vertical);
 
         Self {
             columns: Vec::new(),
@@ -347,7 +353,11 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         scale: f64,
         options: Rc<Options>,
     ) {
+        let vertical = options.layout.orientation == Orientation::Vertical;
         let working_area = compute_working_area(parent_area, scale, options.layout.struts);
+        let view_size = view_size.orient(vertical);
+        let working_area = working_area.orient(vertical);
+        let parent_area = parent_area.orient(vertical);
 
         for (column, data) in zip(&mut self.columns, &mut self.data) {
             column.update_config(view_size, working_area, parent_area, scale, options.clone());
@@ -430,6 +440,7 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         let view_pos = Point::from((self.view_pos(), 0.));
         let view_size = self.view_size;
         let active_idx = self.active_column_idx;
+        let vertical = self.is_vertical();
         for (col_idx, (col, col_x)) in self.columns_mut().enumerate() {
             // Skip columns belonging to a different render layer.
             if layer.is_normal() == col.is_moving_between_workspaces() {
@@ -439,7 +450,7 @@ impl<W: LayoutElement> ScrollingSpace<W> {
             let is_active = is_active && col_idx == active_idx;
             let col_off = Point::from((col_x, 0.));
             let col_pos = view_pos - col_off - col.render_offset();
-            let view_rect = Rectangle::new(col_pos, view_size);
+            let view_rect = Rectangle::new(col_pos, view_size).orient(vertical);
             col.update_render_elements(is_active, view_rect);
         }
     }
@@ -507,7 +518,7 @@ impl<W: LayoutElement> ScrollingSpace<W> {
 
         compute_toplevel_bounds(
             border_config,
-            self.working_area.size,
+            self.working_area.size.orient(self.is_vertical()),
             extra_size,
             self.options.layout.gaps,
         )
@@ -526,7 +537,9 @@ impl<W: LayoutElement> ScrollingSpace<W> {
             .unwrap_or(self.options.layout.default_column_display);
         let will_tab = display_mode == ColumnDisplay::Tabbed;
         let extra = if will_tab {
-            TabIndicator::new(self.options.layout.tab_indicator).extra_size(1, self.scale)
+            TabIndicator::new(self.options.layout.tab_indicator)
+                .extra_size(1, self.scale)
+                .orient(self.is_vertical())
         } else {
             Size::from((0., 0.))
         };
@@ -569,7 +582,8 @@ impl<W: LayoutElement> ScrollingSpace<W> {
             full_height
         };
 
-        Size::from((width, max(height.floor() as i32, 1)))
+        // With the vertical orientation, the column width is the window height, and vice versa.
+        Size::from((width, max(height.floor() as i32, 1))).orient(self.is_vertical())
     }
 
     pub fn is_centering_focused_column(&self) -> bool {
@@ -577,6 +591,11 @@ impl<W: LayoutElement> ScrollingSpace<W> {
             || (self.options.layout.always_center_single_column && self.columns.len() <= 1)
     }
 
+    /// Returns whether column space is transposed relative to the screen.
+    pub fn is_vertical(&self) -> bool {
+        self.options.layout.orientation == Orientation::Vertical
+    }
+
     fn compute_new_view_offset_fit(
         &self,
         target_x: Option<f64>,
@@ -838,6 +857,7 @@ impl<W: LayoutElement> ScrollingSpace<W> {
             return InsertPosition::NewColumn(0);
         }
 
+        let pos = pos.orient(self.is_vertical());
         let x = pos.x + self.view_pos();
 
         // Aim for the center of the gap.
@@ -1071,6 +1091,31 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         self.remove_tile_by_idx(column_idx, tile_idx, transaction, None)
     }
 
+    /// Puts a tile in place of a window, keeping the column and the tile height.
+    ///
+    /// Returns the replaced tile.
+    pub fn replace_tile(&mut self, window: &W::Id, tile: Tile<W>) -> Tile<W> {
+        let column_idx = self
+            .columns
+            .iter()
+            .position(|col| col.contains(window))
+            .unwrap();
+        let column = &mut self.columns[column_idx];
+
+        let tile_idx = column.position(window).unwrap();
+        let old = column.replace_tile(tile_idx, tile);
+        self.data[column_idx].update(column);
+
+        // Stop interactive resize.
+        if let Some(resize) = &self.interactive_resize {
+            if old.window().id() == &resize.window {
+                self.interactive_resize = None;
+            }
+        }
+
+        old
+    }
+
     pub fn remove_tile_by_idx(
         &mut self,
         column_idx: usize,
@@ -1098,8 +1143,14 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         // FIXME: tiles can move by X too, in a centered or resizing layout with one window smaller
         // than the others.
         let offset_y = column.tile_offset(tile_idx + 1).y - column.tile_offset(tile_idx).y;
+        let vertical = column.is_vertical();
         for tile in &mut column.tiles[tile_idx + 1..] {
-            tile.animate_move_y_from(offset_y);
+            animate_tile_move_along_column_from(
+                tile,
+                vertical,
+                offset_y,
+                self.options.animations.window_movement.0,
+            );
         }
 
         if column.display_mode == ColumnDisplay::Tabbed && tile_idx != column.active_tile_idx {
@@ -1360,6 +1411,7 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         let new_origin = column.tiles_origin();
         let origin_delta = prev_origin - new_origin;
         if origin_delta != Point::new(0., 0.) {
+            let origin_delta = origin_delta.orient(column.is_vertical());
             for (tile, _pos) in column.tiles_mut() {
                 tile.animate_move_from(origin_delta);
             }
@@ -1377,6 +1429,7 @@ impl<W: LayoutElement> ScrollingSpace<W> {
                 // If this is an interactive resize commit of an active window, then we need to
                 // either preserve the view offset or adjust it accordingly.
                 let centered = self.is_centering_focused_column();
+                let edges = resize.edges.orient(self.is_vertical());
 
                 let width = self.data[col_idx].width;
                 let offset = if centered {
@@ -1384,7 +1437,7 @@ impl<W: LayoutElement> ScrollingSpace<W> {
                     let new_offset =
                         -(self.working_area.size.w - width) / 2. - self.working_area.loc.x;
                     new_offset - self.view_offset.target()
-                } else if resize.edges.contains(ResizeEdge::LEFT) {
+                } else if edges.contains(ResizeEdge::LEFT) {
                     -offset
                 } else {
                     0.
@@ -1481,7 +1534,8 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         window: &W::Id,
         blocker: TransactionBlocker,
     ) {
-        let (tile, mut tile_pos) = self
+        let vertical = self.is_vertical();
+        let (tile, tile_pos) = self
             .tiles_with_render_positions_mut(false)
             .find(|(tile, _)| tile.window().id() == window)
             .unwrap();
@@ -1491,6 +1545,7 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         };
 
         let tile_size = tile.tile_size();
+        let mut tile_pos = tile_pos.orient(vertical);
 
         let (col_idx, tile_idx) = self
             .columns
@@ -1533,6 +1588,7 @@ impl<W: LayoutElement> ScrollingSpace<W> {
             tile_pos.x -= offset;
         }
 
+        let tile_pos = tile_pos.orient(vertical);
         self.start_close_animation_for_tile(renderer, snapshot, tile_size, tile_pos, blocker);
     }
 
@@ -1797,6 +1853,8 @@ impl<W: LayoutElement> ScrollingSpace<W> {
             return;
         }
 
+        let vertical = self.is_vertical();
+
         let (source_col_idx, source_tile_idx) = if let Some(window) = window {
             self.columns
                 .iter_mut()
@@ -1864,7 +1922,7 @@ impl<W: LayoutElement> ScrollingSpace<W> {
             offset += prev_off - target_column.tile_offset(target_column.tiles.len() - 1);
 
             let new_tile = target_column.tiles.last_mut().unwrap();
-            new_tile.animate_move_from(offset);
+            new_tile.animate_move_from(offset.orient(vertical));
         } else {
             // Move out of column.
             let mut offset = source_column.render_offset();
@@ -1896,7 +1954,7 @@ impl<W: LayoutElement> ScrollingSpace<W> {
 
             let new_col = &mut self.columns[target_column_idx];
             offset += prev_off - new_col.tile_offset(0);
-            new_col.tiles[0].animate_move_from(offset);
+            new_col.tiles[0].animate_move_from(offset.orient(vertical));
         }
     }
 
@@ -1905,6 +1963,8 @@ impl<W: LayoutElement> ScrollingSpace<W> {
             return;
         }
 
+        let vertical = self.is_vertical();
+
         let (source_col_idx, source_tile_idx) = if let Some(window) = window {
             self.columns
                 .iter_mut()
@@ -1959,7 +2019,7 @@ impl<W: LayoutElement> ScrollingSpace<W> {
             offset += prev_off - target_column.tile_offset(target_column.tiles.len() - 1);
 
             let new_tile = target_column.tiles.last_mut().unwrap();
-            new_tile.animate_move_from(offset);
+            new_tile.animate_move_from(offset.orient(vertical));
         } else {
             // Move out of column.
             let prev_width = self.data[source_col_idx].width;
@@ -1988,7 +2048,7 @@ impl<W: LayoutElement> ScrollingSpace<W> {
 
             let new_col = &mut self.columns[target_column_idx];
             offset += prev_off - new_col.tile_offset(0);
-            new_col.tiles[0].animate_move_from(offset);
+            new_col.tiles[0].animate_move_from(offset.orient(vertical));
         }
     }
 
@@ -1997,6 +2057,8 @@ impl<W: LayoutElement> ScrollingSpace<W> {
             return;
         }
 
+        let vertical = self.is_vertical();
+
         if self.active_column_idx == self.columns.len() - 1 {
             return;
         }
@@ -2017,7 +2079,7 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         offset -= target_column.render_offset();
 
         let new_tile = target_column.tiles.last_mut().unwrap();
-        new_tile.animate_move_from(offset);
+        new_tile.animate_move_from(offset.orient(vertical));
     }
 
     pub fn expel_from_column(&mut self) {
@@ -2025,6 +2087,8 @@ impl<W: LayoutElement> ScrollingSpace<W> {
             return;
         }
 
+        let vertical = self.is_vertical();
+
         let source_col_idx = self.active_column_idx;
         let target_col_idx = self.active_column_idx + 1;
         let cur_x = self.column_x(source_col_idx);
@@ -2055,7 +2119,7 @@ impl<W: LayoutElement> ScrollingSpace<W> {
 
         let new_col = &mut self.columns[target_col_idx];
         offset += prev_off - new_col.tile_offset(0);
-        new_col.tiles[0].animate_move_from(offset);
+        new_col.tiles[0].animate_move_from(offset.orient(vertical));
     }
 
     pub fn swap_window_in_direction(&mut self, direction: ScrollDirection) {
@@ -2063,6 +2127,8 @@ impl<W: LayoutElement> ScrollingSpace<W> {
             return;
         }
 
+        let vertical = self.is_vertical();
+
         // if this is the first (resp. last column), then this operation is equivalent
         // to an `consume_or_expel_window_left` (resp. `consume_or_expel_window_right`)
         match direction {
@@ -2170,7 +2236,7 @@ impl<W: LayoutElement> ScrollingSpace<W> {
 
         // Animations
         self.columns[target_column_idx].tiles[target_tile_idx]
-            .animate_move_from(source_pt - target_pt);
+            .animate_move_from((source_pt - target_pt).orient(vertical));
         self.columns[target_column_idx].tiles[target_tile_idx].ensure_alpha_animates_to_1();
 
         // FIXME: this stop_move_animations() causes the target tile animation to "reset" when
@@ -2180,7 +2246,7 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         // cancel all ongoing target tile animations.
         self.columns[source_column_idx].tiles[source_tile_idx].stop_move_animations();
         self.columns[source_column_idx].tiles[source_tile_idx]
-            .animate_move_from(target_pt - source_pt);
+            .animate_move_from((target_pt - source_pt).orient(vertical));
         self.columns[source_column_idx].tiles[source_tile_idx].ensure_alpha_animates_to_1();
 
         self.activate_column(target_column_idx);
@@ -2205,7 +2271,49 @@ impl<W: LayoutElement> ScrollingSpace<W> {
             return;
         }
 
-        let col = &mut self.columns[self.active_column_idx];
+        self.set_column_display_at(self.active_column_idx, display);
+    }
+
+    /// Sets the requested height of a window as is, for example when restoring a saved layout.
+    pub fn set_window_restored_height(&mut self, window: &W::Id, height: WindowHeight) {
+        let Some(col) = self.columns.iter_mut().find(|col| col.contains(window)) else {
+            return;
+        };
+        let tile_idx = col.position(window).unwrap();
+
+        // Presets may have changed since the height was saved.
+        let height = match height {
+            WindowHeight::Preset(idx) if idx >= self.options.layout.preset_window_heights.len() => {
+                WindowHeight::auto_1()
+            }
+            // A single window always has a weight of 1.
+            WindowHeight::Auto { .. } if col.tiles.len() == 1 => WindowHeight::auto_1(),
+            // Only one window in a column can have a fixed height.
+            WindowHeight::Fixed(_)
+                if col.data.iter().enumerate().any(|(idx, data)| {
+                    idx != tile_idx && matches!(data.height, WindowHeight::Fixed(_))
+                }) =>
+            {
+                WindowHeight::auto_1()
+            }
+            height => height,
+        };
+
+        col.data[tile_idx].height = height;
+        col.update_tile_sizes(false);
+    }
+
+    /// Sets the display mode of the column containing `window`.
+    pub fn set_window_column_display(&mut self, window: &W::Id, display: ColumnDisplay) {
+        let Some(col_idx) = self.columns.iter().position(|col| col.contains(window)) else {
+            return;
+        };
+
+        self.set_column_display_at(col_idx, display);
+    }
+
+    fn set_column_display_at(&mut self, col_idx: usize, display: ColumnDisplay) {
+        let col = &mut self.columns[col_idx];
         if col.display_mode == display {
             return;
         }
@@ -2214,7 +2322,7 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         col.set_column_display(display);
 
         // With place_within_column, the tab indicator changes the column size immediately.
-        self.data[self.active_column_idx].update(col);
+        self.data[col_idx].update(col);
         col.update_tile_sizes(true);
 
         // Disable fullscreen if needed.
@@ -2406,11 +2514,12 @@ impl<W: LayoutElement> ScrollingSpace<W> {
     pub fn columns_with_render_positions(
         &self,
     ) -> impl Iterator<Item = (&Column<W>, Point<f64, Logical>)> {
+        let vertical = self.is_vertical();
         let view_off = Point::from((-self.view_pos(), 0.));
         self.columns_in_render_order().map(move |(col, col_x)| {
             let col_off = Point::from((col_x, 0.));
             let col_render_off = col.render_offset();
-            let pos = view_off + col_off + col_render_off;
+            let pos = (view_off + col_off + col_render_off).orient(vertical);
             (col, pos)
         })
     }
@@ -2418,11 +2527,12 @@ impl<W: LayoutElement> ScrollingSpace<W> {
     pub fn columns_with_render_positions_mut(
         &mut self,
     ) -> impl Iterator<Item = (&mut Column<W>, Point<f64, Logical>)> {
+        let vertical = self.is_vertical();
         let view_off = Point::from((-self.view_pos(), 0.));
         self.columns_in_render_order_mut().map(move |(col, col_x)| {
             let col_off = Point::from((col_x, 0.));
             let col_render_off = col.render_offset();
-            let pos = view_off + col_off + col_render_off;
+            let pos = (view_off + col_off + col_render_off).orient(vertical);
             (col, pos)
         })
     }
@@ -2431,11 +2541,12 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         &self,
     ) -> impl Iterator<Item = (&Tile<W>, Point<f64, Logical>, bool)> {
         let scale = self.scale;
+        let vertical = self.is_vertical();
         self.columns_with_render_positions()
             .flat_map(move |(col, col_pos)| {
                 col.tiles_in_render_order()
                     .map(move |(tile, tile_off, visible)| {
-                        let pos = col_pos + tile_off + tile.render_offset();
+                        let pos = col_pos + tile_off.orient(vertical) + tile.render_offset();
                         // Round to physical pixels.
                         let pos = pos.to_physical_precise_round(scale).to_logical(scale);
                         (tile, pos, visible)
@@ -2448,11 +2559,12 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         round: bool,
     ) -> impl Iterator<Item = (&mut Tile<W>, Point<f64, Logical>)> {
         let scale = self.scale;
+        let vertical = self.is_vertical();
         self.columns_with_render_positions_mut()
             .flat_map(move |(col, col_pos)| {
                 col.tiles_in_render_order_mut()
                     .map(move |(tile, tile_off)| {
-                        let mut pos = col_pos + tile_off + tile.render_offset();
+                        let mut pos = col_pos + tile_off.orient(vertical) + tile.render_offset();
                         // Round to physical pixels.
                         if round {
                             pos = pos.to_physical_precise_round(scale).to_logical(scale);
@@ -2552,7 +2664,7 @@ impl<W: LayoutElement> ScrollingSpace<W> {
                 // Adjust for place-within-column tab indicator.
                 let origin_x = col.tiles_origin().x;
                 let extra_w = if is_tabbed && col.sizing_mode().is_normal() {
-                    col.tab_indicator.extra_size(col.tiles.len(), col.scale).w
+                    col.extra_size().w
                 } else {
                     0.
                 };
@@ -2561,7 +2673,7 @@ impl<W: LayoutElement> ScrollingSpace<W> {
                 let loc = Point::from((self.column_x(column_index) + origin_x, y));
                 Rectangle::new(loc, size)
             }
-            InsertPosition::Floating => return None,
+            InsertPosition::Floating | InsertPosition::SnapZone(_) => return None,
         };
 
         // First window on an empty workspace will cancel out any view offset. Replicate this
@@ -2582,7 +2694,7 @@ impl<W: LayoutElement> ScrollingSpace<W> {
             hint_area.loc.x -= self.view_pos();
         }
 
-        Some(hint_area)
+        Some(hint_area.orient(self.is_vertical()))
     }
 
     /// Returns the geometry of the active window relative to and clamped to the view.
@@ -2591,36 +2703,38 @@ impl<W: LayoutElement> ScrollingSpace<W> {
     pub fn active_window_visual_rectangle(&self) -> Option<Rectangle<f64, Logical>> {
         let col = self.columns.get(self.active_column_idx)?;
 
+        let vertical = self.is_vertical();
         let final_view_offset = self.view_offset.target();
         let view_off = Point::from((-final_view_offset, 0.));
 
         let (tile, tile_off) = col.tiles().nth(col.active_tile_idx).unwrap();
 
-        let window_pos = view_off + tile_off + tile.window_loc();
+        let window_pos = (view_off + tile_off).orient(vertical) + tile.window_loc();
         let window_size = tile.window_size();
         let window_rect = Rectangle::new(window_pos, window_size);
 
-        let view = Rectangle::from_size(self.view_size);
+        let view = Rectangle::from_size(self.view_size.orient(vertical));
         view.intersection(window_rect)
     }
 
     pub fn popup_target_rect(&self, id: &W::Id) -> Option<Rectangle<f64, Logical>> {
+        let vertical = self.is_vertical();
         for col in &self.columns {
             for (tile, pos) in col.tiles() {
                 if tile.window().id() == id {
                     // In the scrolling layout, we try to position popups horizontally within the
                     // window geometry (so they remain visible even if the window scrolls flush with
                     // the left/right edge of the screen), and vertically within the whole parent
-                    // working area.
-                    let width = tile.window_size().w;
+                    // working area. With the vertical orientation, the axes are swapped.
+                    let width = tile.window_size().orient(vertical).w;
                     let height = self.parent_area.size.h;
 
                     let mut target = Rectangle::from_size(Size::from((width, height)));
                     target.loc.y += self.parent_area.loc.y;
                     target.loc.y -= pos.y;
-                    target.loc.y -= tile.window_loc().y;
+                    target.loc.y -= tile.window_loc().orient(vertical).y;
 
-                    return Some(target);
+                    return Some(target.orient(vertical));
                 }
             }
         }
@@ -2955,7 +3069,8 @@ impl<W: LayoutElement> ScrollingSpace<W> {
 
         // Draw the closing windows on top of the other windows.
         if layer.is_normal() {
-            let view_rect = Rectangle::new(Point::from((self.view_pos(), 0.)), self.view_size);
+            let view_rect = Rectangle::new(Point::from((self.view_pos(), 0.)), self.view_size)
+                .orient(self.is_vertical());
             for closing in self.closing_windows.iter().rev() {
                 let elem = closing.render(ctx.as_gles(), view_rect, scale);
                 push(elem.into());
@@ -2967,6 +3082,7 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         }
 
         let mut first = true;
+        let vertical = self.is_vertical();
 
         // This matches self.tiles_with_render_positions().
         for (col, col_pos) in self.columns_with_render_positions() {
@@ -2984,7 +3100,7 @@ impl<W: LayoutElement> ScrollingSpace<W> {
             }
 
             for (tile, tile_off, visible) in col.tiles_in_render_order() {
-                let tile_pos = col_pos + tile_off + tile.render_offset();
+                let tile_pos = col_pos + tile_off.orient(vertical) + tile.render_offset();
                 // Round to physical pixels.
                 let tile_pos = tile_pos.to_physical_precise_round(scale).to_logical(scale);
 
@@ -3015,6 +3131,7 @@ impl<W: LayoutElement> ScrollingSpace<W> {
     pub fn window_under(&self, pos: Point<f64, Logical>) -> Option<(&W, HitType)> {
         // This matches self.tiles_with_render_positions().
         let scale = self.scale;
+        let vertical = self.is_vertical();
         for (col, col_pos) in self.columns_with_render_positions() {
             // Hit the tab indicator.
             if col.display_mode == ColumnDisplay::Tabbed && col.sizing_mode().is_normal() {
@@ -3038,7 +3155,7 @@ impl<W: LayoutElement> ScrollingSpace<W> {
                     continue;
                 }
 
-                let tile_pos = col_pos + tile_off + tile.render_offset();
+                let tile_pos = col_pos + tile_off.orient(vertical) + tile.render_offset();
                 // Round to physical pixels.
                 let tile_pos = tile_pos.to_physical_precise_round(scale).to_logical(scale);
 
@@ -3561,6 +3678,8 @@ impl<W: LayoutElement> ScrollingSpace<W> {
             return false;
         }
 
+        let vertical = self.is_vertical();
+
         let col = self
             .columns
             .iter_mut()
@@ -3577,7 +3696,8 @@ impl<W: LayoutElement> ScrollingSpace<W> {
             .find(|tile| tile.window().id() == &window)
             .unwrap();
 
-        let original_window_size = tile.window_size();
+        // Stored in column space.
+        let original_window_size = tile.window_size().orient(vertical);
 
         let resize = InteractiveResize {
             window,
@@ -3606,6 +3726,11 @@ impl<W: LayoutElement> ScrollingSpace<W> {
 
         let is_centering = self.is_centering_focused_column();
 
+        // Resize edges and the delta come in screen space.
+        let vertical = self.is_vertical();
+        let edges = resize.data.edges.orient(vertical);
+        let delta = delta.orient(vertical);
+
         let col = self
             .columns
             .iter_mut()
@@ -3618,9 +3743,9 @@ impl<W: LayoutElement> ScrollingSpace<W> {
             .position(|tile| tile.window().id() == window)
             .unwrap();
 
-        if resize.data.edges.intersects(ResizeEdge::LEFT_RIGHT) {
+        if edges.intersects(ResizeEdge::LEFT_RIGHT) {
             let mut dx = delta.x;
-            if resize.data.edges.contains(ResizeEdge::LEFT) {
+            if edges.contains(ResizeEdge::LEFT) {
                 dx = -dx;
             };
 
@@ -3632,12 +3757,12 @@ impl<W: LayoutElement> ScrollingSpace<W> {
             col.set_column_width(SizeChange::SetFixed(window_width), Some(tile_idx), false);
         }
 
-        if resize.data.edges.intersects(ResizeEdge::TOP_BOTTOM) {
+        if edges.intersects(ResizeEdge::TOP_BOTTOM) {
             // Prevent the simplest case of weird resizing (top edge when this is the topmost
             // window).
-            if !(resize.data.edges.contains(ResizeEdge::TOP) && tile_idx == 0) {
+            if !(edges.contains(ResizeEdge::TOP) && tile_idx == 0) {
                 let mut dy = delta.y;
-                if resize.data.edges.contains(ResizeEdge::TOP) {
+                if edges.contains(ResizeEdge::TOP) {
                     dy = -dy;
                 };
 
@@ -3672,6 +3797,7 @@ impl<W: LayoutElement> ScrollingSpace<W> {
     }
 
     pub fn refresh(&mut self, is_active: bool, is_focused: bool) {
+        let vertical = self.is_vertical();
         for (col_idx, col) in self.columns.iter_mut().enumerate() {
             let mut col_resize_data = None;
             if let Some(resize) = &self.interactive_resize {
@@ -3715,6 +3841,7 @@ impl<W: LayoutElement> ScrollingSpace<W> {
                 let active_in_column = col.active_tile_idx == tile_idx;
                 win.set_active_in_column(active_in_column);
                 win.set_floating(false);
+                win.set_sticky(false);
 
                 let mut active = is_active && self.active_column_idx == col_idx;
                 if self.options.deactivate_unfocused_windows {
@@ -3734,7 +3861,8 @@ impl<W: LayoutElement> ScrollingSpace<W> {
                     self.working_area.size,
                     extra_size,
                     self.options.layout.gaps,
-                );
+                )
+                .orient(vertical);
                 win.set_bounds(bounds);
 
                 let intent = if individual_throttling {
@@ -3757,12 +3885,12 @@ impl<W: LayoutElement> ScrollingSpace<W> {
 
     #[cfg(test)]
     pub fn view_size(&self) -> Size<f64, Logical> {
-        self.view_size
+        self.view_size.orient(self.is_vertical())
     }
 
     #[cfg(test)]
     pub fn parent_area(&self) -> Rectangle<f64, Logical> {
-        self.parent_area
+        self.parent_area.orient(self.is_vertical())
     }
 
     #[cfg(test)]
@@ -3792,9 +3920,15 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         assert!(self.scale > 0.);
         assert!(self.scale.is_finite());
         assert_eq!(self.columns.len(), self.data.len());
+        let vertical = self.is_vertical();
         assert_eq!(
             self.working_area,
-            compute_working_area(self.parent_area, self.scale, self.options.layout.struts)
+            compute_working_area(
+                self.parent_area.orient(vertical),
+                self.scale,
+                self.options.layout.struts
+            )
+            .orient(vertical)
         );
 
         if !self.columns.is_empty() {
@@ -3943,11 +4077,12 @@ impl TileData {
     }
 
     pub fn update<W: LayoutElement>(&mut self, tile: &Tile<W>) {
-        self.size = tile.tile_size();
+        let vertical = tile.options.layout.orientation == Orientation::Vertical;
+        self.size = tile.tile_size().orient(vertical);
         self.interactively_resizing_by_left_edge = tile
             .window()
             .interactive_resize_data()
-            .is_some_and(|data| data.edges.contains(ResizeEdge::LEFT));
+            .is_some_and(|data| data.edges.orient(vertical).contains(ResizeEdge::LEFT));
     }
 }
 
@@ -3961,7 +4096,7 @@ impl From<PresetSize> for ColumnWidth {
 }
 
 impl WindowHeight {
-    const fn auto_1() -> Self {
+    pub(super) const fn auto_1() -> Self {
         Self::Auto { weight: 1. }
     }
 }
@@ -4093,8 +4228,13 @@ impl<W: LayoutElement> Column<W> {
             update_sizes = true;
         }
 
+        if self.options.layout.orientation != options.layout.orientation {
+            update_sizes = true;
+        }
+
+        let vertical = options.layout.orientation == Orientation::Vertical;
         for (tile, data) in zip(&mut self.tiles, &mut self.data) {
-            tile.update_config(view_size, scale, options.clone());
+            tile.update_config(view_size.orient(vertical), scale, options.clone());
             data.update(tile);
         }
 
@@ -4180,13 +4320,15 @@ impl<W: LayoutElement> Column<W> {
                 .any(|tile| tile.is_moving_between_workspaces())
     }
 
+    /// Updates the render elements; `view_rect` is relative to the column, in screen space.
     pub fn update_render_elements(&mut self, is_active: bool, view_rect: Rectangle<f64, Logical>) {
         let active_idx = self.active_tile_idx;
+        let vertical = self.is_vertical();
         for (tile_idx, (tile, tile_off)) in self.tiles_mut().enumerate() {
             let is_active = is_active && tile_idx == active_idx;
 
             let mut tile_view_rect = view_rect;
-            tile_view_rect.loc -= tile_off + tile.render_offset();
+            tile_view_rect.loc -= tile_off.orient(vertical) + tile.render_offset();
             tile.update_render_elements(is_active, tile_view_rect);
         }
 
@@ -4197,7 +4339,7 @@ impl<W: LayoutElement> Column<W> {
             .map(|(tile_idx, (tile, tile_off))| {
                 let is_active = tile_idx == active_idx;
                 let is_urgent = tile.window().is_urgent();
-                let tile_pos = tile_off + tile.render_offset();
+                let tile_pos = tile_off.orient(vertical) + tile.render_offset();
                 TabInfo::from_tile(tile, tile_pos, is_active, is_urgent, &config)
             });
 
@@ -4253,11 +4395,13 @@ impl<W: LayoutElement> Column<W> {
         self.animate_move_from_with_config(from, self.options.animations.window_movement.0);
     }
 
+    /// Animates the column movement from an offset given in screen space.
     pub fn animate_move_from_with_config(
         &mut self,
         from: Point<f64, Logical>,
         config: niri_config::Animation,
     ) {
+        let from = from.orient(self.is_vertical());
         self.animate_move_x_from_with_config(from.x, config);
         self.animate_move_y_from_with_config(from.y, config);
     }
@@ -4324,7 +4468,13 @@ impl<W: LayoutElement> Column<W> {
     }
 
     pub fn set_anim_y_between_workspaces(&mut self) {
-        if let Some(anim) = &mut self.move_y_animation {
+        // Workspaces are always stacked vertically on the screen.
+        let anim = if self.is_vertical() {
+            &mut self.move_x_animation
+        } else {
+            &mut self.move_y_animation
+        };
+        if let Some(anim) = anim {
             anim.is_between_workspaces = true;
         }
     }
@@ -4408,6 +4558,29 @@ impl<W: LayoutElement> Column<W> {
             .position(|win| win.id() == window)
     }
 
+    /// Desired width of this column, as restored upon leaving full-width or fullscreen.
+    pub fn desired_width(&self) -> ColumnWidth {
+        self.width
+    }
+
+    pub fn is_full_width(&self) -> bool {
+        self.is_full_width
+    }
+
+    pub fn display_mode(&self) -> ColumnDisplay {
+        self.display_mode
+    }
+
+    /// Returns the requested heights of the windows, in the same order as `tiles()`.
+    pub fn window_heights(&self) -> impl Iterator<Item = WindowHeight> + '_ {
+        self.data.iter().map(|data| data.height)
+    }
+
+    /// Consumes the column, returning its tiles and the index of the active tile.
+    pub fn into_tiles(self) -> (Vec<Tile<W>>, usize) {
+        (self.tiles, self.active_tile_idx)
+    }
+
     fn activate_idx(&mut self, idx: usize) -> bool {
         if self.active_tile_idx == idx {
             return false;
@@ -4426,7 +4599,12 @@ impl<W: LayoutElement> Column<W> {
     }
 
     fn add_tile_at(&mut self, idx: usize, mut tile: Tile<W>) {
-        tile.update_config(self.view_size, self.scale, self.options.clone());
+        let vertical = self.is_vertical();
+        tile.update_config(
+            self.view_size.orient(vertical),
+            self.scale,
+            self.options.clone(),
+        );
 
         // Inserting a tile pushes down all tiles below it, but also in always-centering mode it
         // will affect the X position of all tiles in the column.
@@ -4450,10 +4628,26 @@ impl<W: LayoutElement> Column<W> {
                 continue;
             }
 
-            tile.animate_move_from(prev - offset);
+            tile.animate_move_from((prev - offset).orient(vertical));
         }
     }
 
+    fn replace_tile(&mut self, idx: usize, mut tile: Tile<W>) -> Tile<W> {
+        tile.update_config(
+            self.view_size.orient(self.is_vertical()),
+            self.scale,
+            self.options.clone(),
+        );
+
+        // Keep the height of the replaced tile so that the rest of the column stays in place.
+        let height = self.data[idx].height;
+        self.data[idx] = TileData::new(&tile, height);
+        let old = mem::replace(&mut self.tiles[idx], tile);
+        self.update_tile_sizes(false);
+
+        old
+    }
+
     fn update_window(&mut self, window: &W::Id) {
         let (tile_idx, tile) = self
             .tiles
@@ -4470,6 +4664,7 @@ impl<W: LayoutElement> Column<W> {
         let offset = prev_height - self.data[tile_idx].size.h;
 
         let is_tabbed = self.display_mode == ColumnDisplay::Tabbed;
+        let vertical = self.is_vertical();
 
         // Move windows below in tandem with resizing.
         //
@@ -4483,7 +4678,9 @@ impl<W: LayoutElement> Column<W> {
                 // tile.update_window()), then the apparent size change is smooth with no sudden
                 // jumps. This corresponds to adding an Y animation to tiles below.
                 for tile in &mut self.tiles[tile_idx + 1..] {
-                    tile.animate_move_y_from_with_config(
+                    animate_tile_move_along_column_from(
+                        tile,
+                        vertical,
                         offset,
                         self.options.animations.window_resize.anim,
                     );
@@ -4504,16 +4701,26 @@ impl<W: LayoutElement> Column<W> {
                 // Notably, this is necessary to fix the animation jump when resizing height back
                 // and forth in quick succession (in a way that cancels the resize animation).
                 for tile in &mut self.tiles[tile_idx + 1..] {
-                    tile.offset_move_y_anim_current(offset);
+                    if vertical {
+                        tile.offset_move_x_anim_current(offset);
+                    } else {
+                        tile.offset_move_y_anim_current(offset);
+                    }
                 }
             }
         }
     }
 
+    fn is_vertical(&self) -> bool {
+        self.options.layout.orientation == Orientation::Vertical
+    }
+
     /// Extra size taken up by elements in the column such as the tab indicator.
     fn extra_size(&self) -> Size<f64, Logical> {
         if self.display_mode == ColumnDisplay::Tabbed {
-            self.tab_indicator.extra_size(self.tiles.len(), self.scale)
+            self.tab_indicator
+                .extra_size(self.tiles.len(), self.scale)
+                .orient(self.is_vertical())
         } else {
             Size::from((0., 0.))
         }
@@ -4547,6 +4754,7 @@ impl<W: LayoutElement> Column<W> {
     }
 
     fn update_tile_sizes_with_transaction(&mut self, animate: bool, transaction: Transaction) {
+        let vertical = self.is_vertical();
         let sizing_mode = self.pending_sizing_mode();
         if matches!(sizing_mode, SizingMode::Fullscreen | SizingMode::Maximized) {
             for (tile_idx, tile) in self.tiles.iter_mut().enumerate() {
@@ -4561,7 +4769,11 @@ impl<W: LayoutElement> Column<W> {
                 if matches!(sizing_mode, SizingMode::Fullscreen) {
                     tile.request_fullscreen(animate, transaction);
                 } else {
-                    tile.request_maximized(self.parent_area.size, animate, transaction);
+                    tile.request_maximized(
+                        self.parent_area.size.orient(vertical),
+                        animate,
+                        transaction,
+                    );
                 }
             }
             return;
@@ -4572,7 +4784,7 @@ impl<W: LayoutElement> Column<W> {
         let min_size: Vec<_> = self
             .tiles
             .iter()
-            .map(Tile::min_size_nonfullscreen)
+            .map(|tile| tile.min_size_nonfullscreen().orient(vertical))
             .map(|mut size| {
                 size.w = size.w.max(1.);
                 size.h = size.h.max(1.);
@@ -4582,7 +4794,7 @@ impl<W: LayoutElement> Column<W> {
         let max_size: Vec<_> = self
             .tiles
             .iter()
-            .map(Tile::max_size_nonfullscreen)
+            .map(|tile| tile.max_size_nonfullscreen().orient(vertical))
             .collect();
 
         // Compute the column width.
@@ -4640,7 +4852,7 @@ impl<W: LayoutElement> Column<W> {
                 let height_left = max_tile_height - min_height_taken;
                 max_non_auto_window_height = Some(f64::max(
                     1.,
-                    tile.window_height_for_tile_height(height_left).round(),
+                    window_height_for_tile_height(tile, vertical, height_left).round(),
                 ));
             }
         }
@@ -4655,16 +4867,21 @@ impl<W: LayoutElement> Column<W> {
                         window_height = f64::min(window_height, max);
                     } else {
                         // In any case, clamp to the working area height.
-                        let max = tile.window_height_for_tile_height(max_tile_height).round();
+                        let max =
+                            window_height_for_tile_height(tile, vertical, max_tile_height).round();
                         window_height = f64::min(window_height, max);
                     }
 
-                    WindowHeight::Fixed(tile.tile_height_for_window_height(window_height))
+                    WindowHeight::Fixed(tile_height_for_window_height(
+                        tile,
+                        vertical,
+                        window_height,
+                    ))
                 }
                 WindowHeight::Preset(idx) => {
                     let preset = self.options.layout.preset_window_heights[idx];
                     let window_height = match self.resolve_preset_height(preset) {
-                        ResolvedSize::Tile(h) => tile.window_height_for_tile_height(h),
+                        ResolvedSize::Tile(h) => window_height_for_tile_height(tile, vertical, h),
                         ResolvedSize::Window(h) => h,
                     };
 
@@ -4673,7 +4890,7 @@ impl<W: LayoutElement> Column<W> {
                         window_height = f64::min(window_height, max);
                     }
 
-                    let tile_height = tile.tile_height_for_window_height(window_height);
+                    let tile_height = tile_height_for_window_height(tile, vertical, window_height);
                     WindowHeight::Fixed(tile_height)
                 }
             })
@@ -4789,8 +5006,12 @@ impl<W: LayoutElement> Column<W> {
                     continue 'outer;
                 }
 
-                auto = tile.tile_height_for_window_height(
-                    tile.window_height_for_tile_height(auto).round().max(1.),
+                auto = tile_height_for_window_height(
+                    tile,
+                    vertical,
+                    window_height_for_tile_height(tile, vertical, auto)
+                        .round()
+                        .max(1.),
                 );
 
                 height_left_2 -= auto;
@@ -4808,8 +5029,12 @@ impl<W: LayoutElement> Column<W> {
 
                 // Compute the current auto height.
                 let auto = height_left * factor;
-                let auto = tile.tile_height_for_window_height(
-                    tile.window_height_for_tile_height(auto).round().max(1.),
+                let auto = tile_height_for_window_height(
+                    tile,
+                    vertical,
+                    window_height_for_tile_height(tile, vertical, auto)
+                        .round()
+                        .max(1.),
                 );
 
                 *h = WindowHeight::Fixed(auto);
@@ -4826,7 +5051,7 @@ impl<W: LayoutElement> Column<W> {
                 unreachable!()
             };
 
-            let size = Size::from((width, height));
+            let size = Size::from((width, height)).orient(vertical);
 
             // In tabbed mode, only the visible window participates in the transaction.
             let is_active = tile_idx == self.active_tile_idx;
@@ -4850,7 +5075,7 @@ impl<W: LayoutElement> Column<W> {
             .unwrap();
 
         if self.display_mode == ColumnDisplay::Tabbed && self.sizing_mode().is_normal() {
-            let extra_size = self.tab_indicator.extra_size(self.tiles.len(), self.scale);
+            let extra_size = self.extra_size();
             tiles_width += extra_size.w;
         }
 
@@ -4894,9 +5119,20 @@ impl<W: LayoutElement> Column<W> {
         self.active_tile_idx = new_idx;
 
         // Animate the movement.
+        let vertical = self.is_vertical();
         let new_active_y = self.tile_offset(new_idx).y;
-        self.tiles[new_idx].animate_move_y_from(active_y - new_active_y);
-        self.tiles[new_idx + 1].animate_move_y_from(active_y - next_y);
+        animate_tile_move_along_column_from(
+            &mut self.tiles[new_idx],
+            vertical,
+            active_y - new_active_y,
+            self.options.animations.window_movement.0,
+        );
+        animate_tile_move_along_column_from(
+            &mut self.tiles[new_idx + 1],
+            vertical,
+            active_y - next_y,
+            self.options.animations.window_movement.0,
+        );
 
         true
     }
@@ -4917,14 +5153,26 @@ impl<W: LayoutElement> Column<W> {
         self.active_tile_idx = new_idx;
 
         // Animate the movement.
+        let vertical = self.is_vertical();
         let new_active_y = self.tile_offset(new_idx).y;
-        self.tiles[new_idx].animate_move_y_from(active_y - new_active_y);
-        self.tiles[new_idx - 1].animate_move_y_from(next_y - active_y);
+        animate_tile_move_along_column_from(
+            &mut self.tiles[new_idx],
+            vertical,
+            active_y - new_active_y,
+            self.options.animations.window_movement.0,
+        );
+        animate_tile_move_along_column_from(
+            &mut self.tiles[new_idx - 1],
+            vertical,
+            next_y - active_y,
+            self.options.animations.window_movement.0,
+        );
 
         true
     }
 
     fn toggle_width(&mut self, tile_idx: Option<usize>, forwards: bool) {
+        let vertical = self.is_vertical();
         let tile_idx = tile_idx.unwrap_or(self.active_tile_idx);
 
         let preset_idx = if self.is_full_width || self.is_pending_maximized {
@@ -4938,8 +5186,8 @@ impl<W: LayoutElement> Column<W> {
             (idx + if forwards { 1 } else { len - 1 }) % len
         } else {
             let tile = &self.tiles[tile_idx];
-            let current_window = tile.window_expected_or_current_size().w;
-            let current_tile = tile.tile_expected_or_current_size().w;
+            let current_window = tile.window_expected_or_current_size().orient(vertical).w;
+            let current_tile = tile.tile_expected_or_current_size().orient(vertical).w;
 
             let mut it = self
                 .options
@@ -4988,6 +5236,7 @@ impl<W: LayoutElement> Column<W> {
     }
 
     fn set_column_width(&mut self, change: SizeChange, tile_idx: Option<usize>, animate: bool) {
+        let vertical = self.is_vertical();
         let current = if self.is_full_width || self.is_pending_maximized {
             ColumnWidth::Proportion(1.)
         } else {
@@ -5008,8 +5257,7 @@ impl<W: LayoutElement> Column<W> {
                 let tile_idx = tile_idx.unwrap_or(self.active_tile_idx);
                 let tile = &self.tiles[tile_idx];
                 ColumnWidth::Fixed(
-                    tile.tile_width_for_window_width(f64::from(fixed))
-                        .clamp(1., MAX_PX),
+                    tile_width_for_window_width(tile, vertical, f64::from(fixed)).clamp(1., MAX_PX),
                 )
             }
             (_, SizeChange::SetProportion(proportion)) => {
@@ -5043,6 +5291,7 @@ impl<W: LayoutElement> Column<W> {
     }
 
     fn set_window_height(&mut self, change: SizeChange, tile_idx: Option<usize>, animate: bool) {
+        let vertical = self.is_vertical();
         let tile_idx = tile_idx.unwrap_or(self.active_tile_idx);
 
         // Start by converting all heights to automatic, since only one window in the column can be
@@ -5057,10 +5306,12 @@ impl<W: LayoutElement> Column<W> {
         let current = self.data[tile_idx].height;
         let tile = &self.tiles[tile_idx];
         let current_window_px = match current {
-            WindowHeight::Auto { .. } | WindowHeight::Preset(_) => tile.window_size().h,
+            WindowHeight::Auto { .. } | WindowHeight::Preset(_) => {
+                tile.window_size().orient(vertical).h
+            }
             WindowHeight::Fixed(height) => height,
         };
-        let current_tile_px = tile.tile_height_for_window_height(current_window_px);
+        let current_tile_px = tile_height_for_window_height(tile, vertical, current_window_px);
 
         let working_size = self.working_area.size.h;
         let gaps = self.options.layout.gaps;
@@ -5079,13 +5330,13 @@ impl<W: LayoutElement> Column<W> {
             SizeChange::SetFixed(fixed) => f64::from(fixed),
             SizeChange::SetProportion(proportion) => {
                 let tile_height = (working_size - gaps) * (proportion / 100.) - gaps - extra_size;
-                tile.window_height_for_tile_height(tile_height)
+                window_height_for_tile_height(tile, vertical, tile_height)
             }
             SizeChange::AdjustFixed(delta) => current_window_px + f64::from(delta),
             SizeChange::AdjustProportion(delta) => {
                 let proportion = current_prop + delta / 100.;
                 let tile_height = (working_size - gaps) * proportion - gaps - extra_size;
-                tile.window_height_for_tile_height(tile_height)
+                window_height_for_tile_height(tile, vertical, tile_height)
             }
         };
 
@@ -5097,17 +5348,22 @@ impl<W: LayoutElement> Column<W> {
                 .iter()
                 .enumerate()
                 .filter(|(idx, _)| *idx != tile_idx)
-                .map(|(_, tile)| f64::max(1., tile.min_size_nonfullscreen().h) + gaps)
+                .map(|(_, tile)| {
+                    f64::max(1., tile.min_size_nonfullscreen().orient(vertical).h) + gaps
+                })
                 .sum::<f64>()
         };
         let height_left = working_size - extra_size - gaps - min_height_taken - gaps;
-        let height_left = f64::max(1., tile.window_height_for_tile_height(height_left));
+        let height_left = f64::max(
+            1.,
+            window_height_for_tile_height(tile, vertical, height_left),
+        );
         window_height = f64::min(height_left, window_height);
 
         // Clamp it against the window height constraints.
         let win = &self.tiles[tile_idx].window();
-        let min_h = win.min_size().h;
-        let max_h = win.max_size().h;
+        let min_h = win.min_size().orient(vertical).h;
+        let max_h = win.max_size().orient(vertical).h;
 
         if max_h > 0 {
             window_height = f64::min(window_height, f64::from(max_h));
@@ -5137,6 +5393,7 @@ impl<W: LayoutElement> Column<W> {
     }
 
     fn toggle_window_height(&mut self, tile_idx: Option<usize>, forwards: bool) {
+        let vertical = self.is_vertical();
         let tile_idx = tile_idx.unwrap_or(self.active_tile_idx);
 
         // Start by converting all heights to automatic, since only one window in the column can be
@@ -5165,10 +5422,16 @@ impl<W: LayoutElement> Column<W> {
                     .copied()
                     .map(|preset| {
                         let window_height = match self.resolve_preset_height(preset) {
-                            ResolvedSize::Tile(h) => tile.window_height_for_tile_height(h),
+                            ResolvedSize::Tile(h) => {
+                                window_height_for_tile_height(tile, vertical, h)
+                            }
                             ResolvedSize::Window(h) => h,
                         };
-                        tile.tile_height_for_window_height(window_height.round().clamp(1., 100000.))
+                        tile_height_for_window_height(
+                            tile,
+                            vertical,
+                            window_height.round().clamp(1., 100000.),
+                        )
                     });
 
                 if forwards {
@@ -5199,7 +5462,12 @@ impl<W: LayoutElement> Column<W> {
     /// One case where apparent heights will not be preserved is when the column is taller than the
     /// working area.
     fn convert_heights_to_auto(&mut self) {
-        let heights: Vec<_> = self.tiles.iter().map(|tile| tile.tile_size().h).collect();
+        let vertical = self.is_vertical();
+        let heights: Vec<_> = self
+            .tiles
+            .iter()
+            .map(|tile| tile.tile_size().orient(vertical).h)
+            .collect();
 
         // Weights are invariant to multiplication: a column with weights 2, 2, 1 is equivalent to
         // a column with weights 4, 4, 2. So we find the median window height and use that as 1.
@@ -5251,6 +5519,7 @@ impl<W: LayoutElement> Column<W> {
         //
         // Doing it this way to avoid storing all tile positions in a vector. If more display modes
         // are added it might be simpler to just collect everything into a smallvec.
+        let vertical = self.is_vertical();
         let prev_origin = self.tiles_origin();
         self.display_mode = display;
         let new_origin = self.tiles_origin();
@@ -5268,7 +5537,7 @@ impl<W: LayoutElement> Column<W> {
 
             let mut delta = origin_delta;
             delta.y += y_delta;
-            tile.animate_move_from(delta);
+            tile.animate_move_from(delta.orient(vertical));
         }
 
         // Animate the opacity.
@@ -5314,7 +5583,8 @@ impl<W: LayoutElement> Column<W> {
         if self.display_mode == ColumnDisplay::Tabbed {
             origin += self
                 .tab_indicator
-                .content_offset(self.tiles.len(), self.scale);
+                .content_offset(self.tiles.len(), self.scale)
+                .orient(self.is_vertical());
         }
 
         origin
@@ -5447,15 +5717,18 @@ impl<W: LayoutElement> Column<W> {
         // fixed. Third, the animation for making a column tabbed moves tiles vertically, and using
         // the active tile's animated size in this case only works for the topmost tile, and looks
         // broken otherwise.
+        //
+        // The tab indicator works in screen space, so the area is converted back from column space.
+        let vertical = self.is_vertical();
         let mut max_height = 0.;
         for tile in &self.tiles {
-            max_height = f64::max(max_height, tile.tile_size().h);
+            max_height = f64::max(max_height, tile.tile_size().orient(vertical).h);
         }
 
         let tile = &self.tiles[self.active_tile_idx];
-        let area_size = Size::from((tile.animated_tile_size().w, max_height));
+        let area_size = Size::from((tile.animated_tile_size().orient(vertical).w, max_height));
 
-        Rectangle::new(self.tiles_origin(), area_size)
+        Rectangle::new(self.tiles_origin(), area_size).orient(vertical)
     }
 
     pub fn start_open_animation(&mut self, id: &W::Id) -> bool {
@@ -5508,6 +5781,7 @@ impl<W: LayoutElement> Column<W> {
             }
         }
 
+        let vertical = self.is_vertical();
         let working_size = self.working_area.size;
         let extra_size = self.extra_size();
         let gaps = self.options.layout.gaps;
@@ -5523,7 +5797,7 @@ impl<W: LayoutElement> Column<W> {
                 self.pending_sizing_mode(),
                 tile.window().pending_sizing_mode()
             );
-            assert_eq!(self.view_size, tile.view_size());
+            assert_eq!(self.view_size.orient(vertical), tile.view_size());
             tile.verify_invariants();
 
             let mut data2 = *data;
@@ -5542,10 +5816,10 @@ impl<W: LayoutElement> Column<W> {
                 assert!(self.options.layout.preset_window_heights.len() > idx);
             }
 
-            let requested_size = tile.window().requested_size().unwrap();
+            let requested_size = tile.window().requested_size().unwrap().orient(vertical);
             let requested_tile_height =
-                tile.tile_height_for_window_height(f64::from(requested_size.h));
-            let min_tile_height = f64::max(1., tile.min_size_nonfullscreen().h);
+                tile_height_for_window_height(tile, vertical, f64::from(requested_size.h));
+            let min_tile_height = f64::max(1., tile.min_size_nonfullscreen().orient(vertical).h);
 
             if !is_tabbed
                 && self.pending_sizing_mode().is_normal()
@@ -5585,6 +5859,110 @@ impl<W: LayoutElement> Column<W> {
     }
 }
 
+/// Conversion between screen space and column space.
+///
+/// The scrolling layout does all of its computations in column space, where columns go
+/// left-to-right and tiles within a column go top-to-bottom. With the vertical orientation, column
+/// space is the screen space reflected across the diagonal. Since the reflection is its own
+/// inverse, the same conversion goes both ways.
+trait Orient: Sized {
+    fn transposed(self) -> Self;
+
+    fn orient(self, vertical: bool) -> Self {
+        if vertical {
+            self.transposed()
+        } else {
+            self
+        }
+    }
+}
+
+impl<N: Coordinate, Kind> Orient for Point<N, Kind> {
+    fn transposed(self) -> Self {
+        Point::from((self.y, self.x))
+    }
+}
+
+impl<N: Coordinate, Kind> Orient for Size<N, Kind> {
+    fn transposed(self) -> Self {
+        Size::from((self.h, self.w))
+    }
+}
+
+impl<N: Coordinate, Kind> Orient for Rectangle<N, Kind> {
+    fn transposed(self) -> Self {
+        Rectangle::new(self.loc.transposed(), self.size.transposed())
+    }
+}
+
+impl Orient for ResizeEdge {
+    fn transposed(self) -> Self {
+        let mut rv = ResizeEdge::empty();
+        if self.contains(ResizeEdge::TOP) {
+            rv |= ResizeEdge::LEFT;
+        }
+        if self.contains(ResizeEdge::BOTTOM) {
+            rv |= ResizeEdge::RIGHT;
+        }
+        if self.contains(ResizeEdge::LEFT) {
+            rv |= ResizeEdge::TOP;
+        }
+        if self.contains(ResizeEdge::RIGHT) {
+            rv |= ResizeEdge::BOTTOM;
+        }
+        rv
+    }
+}
+
+/// Converts a window height to a tile height along the column axis.
+fn tile_height_for_window_height<W: LayoutElement>(
+    tile: &Tile<W>,
+    vertical: bool,
+    size: f64,
+) -> f64 {
+    if vertical {
+        tile.tile_width_for_window_width(size)
+    } else {
+        tile.tile_height_for_window_height(size)
+    }
+}
+
+/// Converts a tile height to a window height along the column axis.
+fn window_height_for_tile_height<W: LayoutElement>(
+    tile: &Tile<W>,
+    vertical: bool,
+    size: f64,
+) -> f64 {
+    if vertical {
+        tile.window_width_for_tile_width(size)
+    } else {
+        tile.window_height_for_tile_height(size)
+    }
+}
+
+/// Converts a window width to a tile width across the column axis.
+fn tile_width_for_window_width<W: LayoutElement>(tile: &Tile<W>, vertical: bool, size: f64) -> f64 {
+    if vertical {
+        tile.tile_height_for_window_height(size)
+    } else {
+        tile.tile_width_for_window_width(size)
+    }
+}
+
+/// Animates a tile movement along the column axis, given in column space.
+fn animate_tile_move_along_column_from<W: LayoutElement>(
+    tile: &mut Tile<W>,
+    vertical: bool,
+    from: f64,
+    config: niri_config::Animation,
+) {
+    if vertical {
+        tile.animate_move_x_from_with_config(from, config);
+    } else {
+        tile.animate_move_y_from_with_config(from, config);
+    }
+}
+
 fn compute_new_view_offset(
     cur_x: f64,
     view_width: f64,
@@ -5619,7 +5997,7 @@ fn compute_new_view_offset(
     }
 }
 
-fn compute_working_area(
+pub(super) fn compute_working_area(
     parent_area: Rectangle<f64, Logical>,
     scale: f64,
     struts: Struts,
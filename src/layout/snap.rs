@@ -0,0 +1,215 @@
+//! Snapping of floating windows to nearby edges.
+//!
+//! While a floating window is moved or resized, its edges stick to output edges, working area
+//! edges and the edges of other floating windows that come within the snap distance. A guide
+//! line shows the edge that the window snapped to.
+//!
+//! Dragging a floating window with the pointer to an output edge or corner activates a snap zone
+//! instead, and dropping the window there sizes it to that half or quarter of the working area.
+
+use niri_config::SnapZones;
+use niri_ipc::SnapZone;
+use smithay::backend::renderer::element::Kind;
+use smithay::utils::{Logical, Point, Rectangle, Size};
+
+use crate::render_helpers::solid_color::{SolidColorBuffer, SolidColorRenderElement};
+
+/// Width of the snap guide lines.
+const GUIDE_WIDTH: f64 = 2.;
+
+/// Edges that floating windows can snap to.
+#[derive(Debug, Default, Clone)]
+pub struct SnapEdges {
+    /// Vertical edges as x coordinates.
+    pub x: Vec<f64>,
+    /// Horizontal edges as y coordinates.
+    pub y: Vec<f64>,
+}
+
+/// Result of snapping a rectangle.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Snap {
+    /// Offset to move the rectangle by to line it up with the edges.
+    pub offset: Point<f64, Logical>,
+    /// Vertical edge that the rectangle snapped to.
+    pub x: Option<f64>,
+    /// Horizontal edge that the rectangle snapped to.
+    pub y: Option<f64>,
+}
+
+/// Guide lines for the edges that a window snapped to.
+#[derive(Debug, Default)]
+pub struct SnapGuides {
+    snap: Snap,
+    vertical: SolidColorBuffer,
+    horizontal: SolidColorBuffer,
+}
+
+impl SnapEdges {
+    pub fn add_rect(&mut self, rect: Rectangle<f64, Logical>) {
+        self.x.push(rect.loc.x);
+        self.x.push(rect.loc.x + rect.size.w);
+        self.y.push(rect.loc.y);
+        self.y.push(rect.loc.y + rect.size.h);
+    }
+
+    pub fn offset(mut self, offset: Point<f64, Logical>) -> Self {
+        for x in &mut self.x {
+            *x += offset.x;
+        }
+        for y in &mut self.y {
+            *y += offset.y;
+        }
+        self
+    }
+
+    /// Snaps either side of the rectangle to the closest edges within `distance`.
+    pub fn snap_rect(&self, rect: Rectangle<f64, Logical>, distance: f64) -> Snap {
+        let x = closest(&self.x, &[rect.loc.x, rect.loc.x + rect.size.w], distance);
+        let y = closest(&self.y, &[rect.loc.y, rect.loc.y + rect.size.h], distance);
+
+        Snap {
+            offset: Point::from((x.map_or(0., |(d, _)| d), y.map_or(0., |(d, _)| d))),
+            x: x.map(|(_, edge)| edge),
+            y: y.map(|(_, edge)| edge),
+        }
+    }
+
+    /// Snaps a vertical edge at `x` to the closest edge within `distance`.
+    pub fn snap_x(&self, x: f64, distance: f64) -> Option<f64> {
+        closest(&self.x, &[x], distance).map(|(_, edge)| edge)
+    }
+
+    /// Snaps a horizontal edge at `y` to the closest edge within `distance`.
+    pub fn snap_y(&self, y: f64, distance: f64) -> Option<f64> {
+        closest(&self.y, &[y], distance).map(|(_, edge)| edge)
+    }
+}
+
+impl SnapGuides {
+    pub fn snap(&self) -> Snap {
+        self.snap
+    }
+
+    pub fn set_snap(&mut self, snap: Snap) {
+        self.snap = snap;
+    }
+
+    pub fn clear(&mut self) {
+        self.snap = Snap::default();
+    }
+
+    pub fn update_render_elements(&mut self, view_size: Size<f64, Logical>, color: [f32; 4]) {
+        self.vertical.update((GUIDE_WIDTH, view_size.h), color);
+        self.horizontal.update((view_size.w, GUIDE_WIDTH), color);
+    }
+
+    pub fn render(
+        &self,
+        location: Point<f64, Logical>,
+        push: &mut dyn FnMut(SolidColorRenderElement),
+    ) {
+        if let Some(x) = self.snap.x {
+            let loc = location + Point::from((x - GUIDE_WIDTH / 2., 0.));
+            let elem =
+                SolidColorRenderElement::from_buffer(&self.vertical, loc, 1., Kind::Unspecified);
+            push(elem);
+        }
+
+        if let Some(y) = self.snap.y {
+            let loc = location + Point::from((0., y - GUIDE_WIDTH / 2.));
+            let elem =
+                SolidColorRenderElement::from_buffer(&self.horizontal, loc, 1., Kind::Unspecified);
+            push(elem);
+        }
+    }
+}
+
+/// Returns the snap zone that the pointer at `pos` within an output activates.
+pub fn snap_zone_at(
+    pos: Point<f64, Logical>,
+    output_size: Size<f64, Logical>,
+    config: &SnapZones,
+) -> Option<SnapZone> {
+    let distance = config.distance;
+    let corner = config.corner_size;
+
+    let at_left = pos.x < distance;
+    let at_right = pos.x >= output_size.w - distance;
+    let at_top = pos.y < distance;
+    let at_bottom = pos.y >= output_size.h - distance;
+
+    let near_left = pos.x < corner;
+    let near_right = pos.x >= output_size.w - corner;
+    let near_top = pos.y < corner;
+    let near_bottom = pos.y >= output_size.h - corner;
+
+    let zone = if (at_left && near_top) || (at_top && near_left) {
+        SnapZone::TopLeft
+    } else if (at_right && near_top) || (at_top && near_right) {
+        SnapZone::TopRight
+    } else if (at_left && near_bottom) || (at_bottom && near_left) {
+        SnapZone::BottomLeft
+    } else if (at_right && near_bottom) || (at_bottom && near_right) {
+        SnapZone::BottomRight
+    } else if at_left {
+        SnapZone::Left
+    } else if at_right {
+        SnapZone::Right
+    } else if at_top {
+        SnapZone::Top
+    } else if at_bottom {
+        SnapZone::Bottom
+    } else {
+        return None;
+    };
+
+    Some(zone)
+}
+
+/// Returns the part of the working area that a snap zone covers, with gaps around it.
+pub fn snap_zone_area(
+    zone: SnapZone,
+    working_area: Rectangle<f64, Logical>,
+    gaps: f64,
+) -> Rectangle<f64, Logical> {
+    let x = working_area.loc.x + gaps;
+    let y = working_area.loc.y + gaps;
+    let width = f64::max(working_area.size.w - gaps * 2., 0.);
+    let height = f64::max(working_area.size.h - gaps * 2., 0.);
+    let half_width = f64::max((width - gaps) / 2., 0.);
+    let half_height = f64::max((height - gaps) / 2., 0.);
+
+    let (x, width) = match zone {
+        SnapZone::Left | SnapZone::TopLeft | SnapZone::BottomLeft => (x, half_width),
+        SnapZone::Right | SnapZone::TopRight | SnapZone::BottomRight => {
+            (x + width - half_width, half_width)
+        }
+        SnapZone::Top | SnapZone::Bottom => (x, width),
+    };
+    let (y, height) = match zone {
+        SnapZone::Top | SnapZone::TopLeft | SnapZone::TopRight => (y, half_height),
+        SnapZone::Bottom | SnapZone::BottomLeft | SnapZone::BottomRight => {
+            (y + height - half_height, half_height)
+        }
+        SnapZone::Left | SnapZone::Right => (y, height),
+    };
+
+    Rectangle::new(Point::from((x, y)), Size::from((width, height)))
+}
+
+/// Finds the smallest shift within `distance` that puts one of `values` onto one of `edges`.
+///
+/// Returns the shift and the edge.
+fn closest(edges: &[f64], values: &[f64], distance: f64) -> Option<(f64, f64)> {
+    let mut best: Option<(f64, f64)> = None;
+    for &edge in edges {
+        for &value in values {
+            let delta = edge - value;
+            if delta.abs() <= distance && best.is_none_or(|(d, _)| delta.abs() < d.abs()) {
+                best = Some((delta, edge));
+            }
+        }
+    }
+    best
+}
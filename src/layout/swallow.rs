@@ -0,0 +1,130 @@
+//! Storage for windows swallowed by their child windows.
+//!
+//! A window opening with the `swallow-parent` rule takes over the tile of a window that belongs to
+//! one of its parent processes, usually the terminal it was started from. The swallowed window is
+//! kept here, hidden, until the child window closes, and then returns into the child's tile.
+
+use smithay::reexports::wayland_server::protocol::wl_surface::WlSurface;
+
+use super::tile::Tile;
+use super::LayoutElement;
+
+#[derive(Debug)]
+pub struct Swallowed<W: LayoutElement> {
+    entries: Vec<SwallowedTile<W>>,
+}
+
+#[derive(Debug)]
+struct SwallowedTile<W: LayoutElement> {
+    /// Window that swallowed this tile and will give it back on closing.
+    child: W::Id,
+    /// The hidden tile.
+    tile: Tile<W>,
+}
+
+impl<W: LayoutElement> Default for Swallowed<W> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl<W: LayoutElement> Swallowed<W> {
+    pub fn has_window(&self, window: &W::Id) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| entry.tile.window().id() == window)
+    }
+
+    /// Returns the window that swallowed this window, if any.
+    pub fn child_of(&self, window: &W::Id) -> Option<&W::Id> {
+        self.entries
+            .iter()
+            .find(|entry| entry.tile.window().id() == window)
+            .map(|entry| &entry.child)
+    }
+
+    /// Hides a tile until `child` closes.
+    pub fn insert(&mut self, child: W::Id, tile: Tile<W>) {
+        self.entries.push(SwallowedTile { child, tile });
+    }
+
+    /// Takes the tile swallowed by `child`, if any.
+    pub fn take_for_child(&mut self, child: &W::Id) -> Option<Tile<W>> {
+        let idx = self
+            .entries
+            .iter()
+            .position(|entry| &entry.child == child)?;
+        Some(self.entries.remove(idx).tile)
+    }
+
+    /// Removes a swallowed tile, forgetting about it.
+    pub fn remove(&mut self, window: &W::Id) -> Option<Tile<W>> {
+        let idx = self
+            .entries
+            .iter()
+            .position(|entry| entry.tile.window().id() == window)?;
+        let entry = self.entries.remove(idx);
+
+        // A window swallowed by the removed one now waits for the removed window's child instead.
+        for other in &mut self.entries {
+            if &other.child == window {
+                other.child = entry.child.clone();
+            }
+        }
+
+        Some(entry.tile)
+    }
+
+    pub fn tiles(&self) -> impl Iterator<Item = &Tile<W>> + '_ {
+        self.entries.iter().map(|entry| &entry.tile)
+    }
+
+    pub fn tiles_mut(&mut self) -> impl Iterator<Item = &mut Tile<W>> + '_ {
+        self.entries.iter_mut().map(|entry| &mut entry.tile)
+    }
+
+    pub fn find_wl_surface(&self, wl_surface: &WlSurface) -> Option<&W> {
+        self.tiles()
+            .map(Tile::window)
+            .find(|win| win.is_wl_surface(wl_surface))
+    }
+
+    pub fn find_wl_surface_mut(&mut self, wl_surface: &WlSurface) -> Option<&mut W> {
+        self.tiles_mut()
+            .map(Tile::window_mut)
+            .find(|win| win.is_wl_surface(wl_surface))
+    }
+
+    pub fn refresh(&mut self) {
+        for tile in self.tiles_mut() {
+            let win = tile.window_mut();
+
+            win.set_active_in_column(true);
+            win.set_floating(false);
+            win.set_sticky(false);
+            win.set_activated(false);
+            win.set_interactive_resize(None);
+
+            win.send_pending_configure();
+            win.refresh();
+        }
+    }
+
+    #[cfg(test)]
+    pub fn verify_invariants(&self) {
+        for (idx, entry) in self.entries.iter().enumerate() {
+            let id = entry.tile.window().id();
+            assert!(&entry.child != id, "windows cannot swallow themselves");
+            assert!(
+                !self
+                    .entries
+                    .iter()
+                    .skip(idx + 1)
+                    .any(|other| other.tile.window().id() == id || other.child == entry.child),
+                "swallowed windows and their children must be unique"
+            );
+        }
+    }
+}
@@ -1,10 +1,11 @@
 use std::cell::{Cell, OnceCell, RefCell};
 
+use niri_config::animations::{Curve, EasingParams, Kind};
 use niri_config::utils::Flag;
 use niri_config::workspace::WorkspaceName;
 use niri_config::{
-    CenterFocusedColumn, FloatOrInt, OutputName, Struts, TabIndicatorLength, TabIndicatorPosition,
-    WorkspaceReference,
+    CenterFocusedColumn, FloatOrInt, Orientation, OutputName, Struts, TabIndicatorLength,
+    TabIndicatorPosition, WorkspaceReference,
 };
 use proptest::prelude::*;
 use proptest_derive::Arbitrary;
@@ -40,6 +41,7 @@ struct TestWindowInner {
     is_pending_windowed_fullscreen: Cell<bool>,
     animate_next_configure: Cell<bool>,
     animation_snapshot: RefCell<Option<LayoutElementRenderSnapshot>>,
+    transaction: RefCell<Option<Transaction>>,
     rules: ResolvedWindowRules,
 }
 
@@ -92,6 +94,7 @@ impl TestWindow {
             is_pending_windowed_fullscreen: Cell::new(false),
             animate_next_configure: Cell::new(false),
             animation_snapshot: RefCell::new(None),
+            transaction: RefCell::new(None),
             rules: params.rules.unwrap_or_default(),
         }))
     }
@@ -173,7 +176,7 @@ impl LayoutElement for TestWindow {
         size: Size<i32, Logical>,
         mode: SizingMode,
         _animate: bool,
-        _transaction: Option<Transaction>,
+        transaction: Option<Transaction>,
     ) {
         if self.0.requested_size.get() != Some(size) {
             self.0.requested_size.set(Some(size));
@@ -185,6 +188,16 @@ impl LayoutElement for TestWindow {
         if mode.is_fullscreen() {
             self.0.is_pending_windowed_fullscreen.set(false);
         }
+
+        if transaction.is_some() {
+            self.0.transaction.replace(transaction);
+        }
+    }
+
+    fn share_transaction(&mut self, transaction: &Transaction) {
+        if let Some(current) = &mut *self.0.transaction.borrow_mut() {
+            *current = transaction.clone();
+        }
     }
 
     fn min_size(&self) -> Size<i32, Logical> {
@@ -225,12 +238,18 @@ impl LayoutElement for TestWindow {
         ConfigureIntent::CanSend
     }
 
-    fn send_pending_configure(&mut self) {}
+    fn send_pending_configure(&mut self) {
+        self.0.transaction.take();
+    }
 
     fn set_active_in_column(&mut self, _active: bool) {}
 
     fn set_floating(&mut self, _floating: bool) {}
 
+    fn set_sticky(&mut self, _sticky: bool) {}
+
+    fn set_minimized(&mut self, _minimized: bool) {}
+
     fn sizing_mode(&self) -> SizingMode {
         self.0.sizing_mode.get()
     }
@@ -282,6 +301,10 @@ impl LayoutElement for TestWindow {
     fn is_urgent(&self) -> bool {
         false
     }
+
+    fn mark(&self) -> Option<&str> {
+        None
+    }
 }
 
 fn arbitrary_size() -> impl Strategy<Value = Size<i32, Logical>> {
@@ -404,6 +427,27 @@ fn arbitrary_column_display() -> impl Strategy<Value = ColumnDisplay> {
     prop_oneof![Just(ColumnDisplay::Normal), Just(ColumnDisplay::Tabbed)]
 }
 
+fn arbitrary_layout_mode() -> impl Strategy<Value = LayoutMode> {
+    prop_oneof![
+        Just(LayoutMode::Scrolling),
+        Just(LayoutMode::MasterStack),
+        Just(LayoutMode::Dwindle),
+    ]
+}
+
+fn arbitrary_snap_zone() -> impl Strategy<Value = SnapZone> {
+    prop_oneof![
+        Just(SnapZone::Left),
+        Just(SnapZone::Right),
+        Just(SnapZone::Top),
+        Just(SnapZone::Bottom),
+        Just(SnapZone::TopLeft),
+        Just(SnapZone::TopRight),
+        Just(SnapZone::BottomLeft),
+        Just(SnapZone::BottomRight),
+    ]
+}
+
 #[derive(Debug, Clone, Arbitrary)]
 enum Op {
     AddOutput(#[proptest(strategy = "1..=5usize")] usize),
@@ -511,6 +555,7 @@ enum Op {
     SwapWindowInDirection(#[proptest(strategy = "arbitrary_scroll_direction()")] ScrollDirection),
     ToggleColumnTabbedDisplay,
     SetColumnDisplay(#[proptest(strategy = "arbitrary_column_display()")] ColumnDisplay),
+    SetLayoutMode(#[proptest(strategy = "arbitrary_layout_mode()")] LayoutMode),
     CenterColumn,
     CenterWindow {
         #[proptest(strategy = "proptest::option::of(1..=5usize)")]
@@ -634,6 +679,43 @@ enum Op {
         y: PositionChange,
         animate: bool,
     },
+    ToggleWindowSticky {
+        #[proptest(strategy = "proptest::option::of(1..=5usize)")]
+        id: Option<usize>,
+    },
+    ToggleScratchpad,
+    SwallowWindow {
+        params: TestWindowParams,
+        #[proptest(strategy = "1..=5usize")]
+        parent_id: usize,
+    },
+    MoveWindowToScratchpad {
+        #[proptest(strategy = "proptest::option::of(1..=5usize)")]
+        id: Option<usize>,
+    },
+    MinimizeWindow {
+        #[proptest(strategy = "proptest::option::of(1..=5usize)")]
+        id: Option<usize>,
+    },
+    RestoreMinimizedWindow {
+        #[proptest(strategy = "proptest::option::of(1..=5usize)")]
+        id: Option<usize>,
+    },
+    UndoLayoutChange,
+    RedoLayoutChange,
+    SwapWindows {
+        #[proptest(strategy = "proptest::option::of(1..=5usize)")]
+        id: Option<usize>,
+        #[proptest(strategy = "1..=5usize")]
+        other: usize,
+    },
+    SnapFloatingWindow {
+        #[proptest(strategy = "proptest::option::of(1..=5usize)")]
+        id: Option<usize>,
+        #[proptest(strategy = "arbitrary_snap_zone()")]
+        zone: SnapZone,
+        animate: bool,
+    },
     SetParent {
         #[proptest(strategy = "1..=5usize")]
         id: usize,
@@ -894,7 +976,11 @@ impl Op {
                 layout.unset_workspace_name(ws_ref);
             }
             Op::AddWindow { mut params } => {
-                if layout.has_window(&params.id) {
+                if layout.has_window(&params.id)
+                    || layout.scratchpad_has_window(&params.id)
+                    || layout.is_swallowed(&params.id)
+                    || layout.is_minimized(&params.id)
+                {
                     return;
                 }
                 if let Some(parent_id) = params.parent_id {
@@ -921,6 +1007,13 @@ impl Op {
             } => {
                 let mut found_next_to = false;
 
+                if layout.scratchpad_has_window(&params.id)
+                    || layout.is_swallowed(&params.id)
+                    || layout.is_minimized(&params.id)
+                {
+                    return;
+                }
+
                 if let Some(InteractiveMoveState::Moving(move_)) = &layout.interactive_move {
                     let win_id = move_.tile.window().0.id;
                     if win_id == params.id {
@@ -991,6 +1084,13 @@ impl Op {
                 let ws_name = format!("ws{ws_name}");
                 let mut ws_id = None;
 
+                if layout.scratchpad_has_window(&params.id)
+                    || layout.is_swallowed(&params.id)
+                    || layout.is_minimized(&params.id)
+                {
+                    return;
+                }
+
                 if let Some(InteractiveMoveState::Moving(move_)) = &layout.interactive_move {
                     if move_.tile.window().0.id == params.id {
                         return;
@@ -1059,6 +1159,7 @@ impl Op {
                 );
             }
             Op::CloseWindow(id) => {
+                layout.unswallow_window(&id);
                 layout.remove_window(&id, Transaction::new());
             }
             Op::FullscreenWindow(id) => {
@@ -1173,6 +1274,7 @@ impl Op {
             Op::SwapWindowInDirection(direction) => layout.swap_window_in_direction(direction),
             Op::ToggleColumnTabbedDisplay => layout.toggle_column_tabbed_display(),
             Op::SetColumnDisplay(display) => layout.set_column_display(display),
+            Op::SetLayoutMode(mode) => layout.set_layout_mode(mode),
             Op::CenterColumn => layout.center_column(),
             Op::CenterWindow { id } => {
                 let id = id.filter(|id| layout.has_window(id));
@@ -1368,6 +1470,60 @@ impl Op {
                 let id = id.filter(|id| layout.has_window(id));
                 layout.move_floating_window(id.as_ref(), x, y, animate);
             }
+            Op::ToggleWindowSticky { id } => {
+                let id = id.filter(|id| layout.has_window(id));
+                layout.toggle_window_sticky(id.as_ref());
+            }
+            Op::ToggleScratchpad => {
+                layout.toggle_scratchpad();
+            }
+            Op::SwallowWindow { params, parent_id } => {
+                if layout.has_window(&params.id)
+                    || layout.scratchpad_has_window(&params.id)
+                    || layout.is_swallowed(&params.id)
+                    || layout.is_minimized(&params.id)
+                {
+                    return;
+                }
+
+                let win = TestWindow::new(params);
+                if let Err(win) = layout.swallow_window(&parent_id, win, ActivateWindow::default())
+                {
+                    layout.add_window(
+                        win,
+                        AddWindowTarget::Auto,
+                        None,
+                        None,
+                        false,
+                        false,
+                        ActivateWindow::default(),
+                    );
+                }
+            }
+            Op::MoveWindowToScratchpad { id } => {
+                let id = id.filter(|id| layout.has_window(id));
+                layout.move_window_to_scratchpad(id.as_ref());
+            }
+            Op::MinimizeWindow { id } => {
+                let id = id.filter(|id| layout.has_window(id));
+                layout.minimize_window(id.as_ref());
+            }
+            Op::RestoreMinimizedWindow { id } => {
+                let id = id.filter(|id| layout.is_minimized(id));
+                layout.restore_minimized_window(id.as_ref());
+            }
+            Op::UndoLayoutChange => layout.undo_layout_change(),
+            Op::RedoLayoutChange => layout.redo_layout_change(),
+            Op::SwapWindows { id, other } => {
+                let id = id.filter(|id| layout.has_window(id));
+                if layout.has_window(&other) {
+                    layout.swap_windows(id.as_ref(), &other);
+                }
+            }
+            Op::SnapFloatingWindow { id, zone, animate } => {
+                let id = id.filter(|id| layout.has_window(id));
+                layout.snap_floating_window(id.as_ref(), zone, animate);
+            }
             Op::SetParent {
                 id,
                 mut new_parent_id,
@@ -1987,6 +2143,411 @@ fn window_closed_on_previous_workspace() {
     check_ops(ops);
 }
 
+#[test]
+fn switching_layout_mode_moves_tiled_windows() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(0),
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(2),
+        },
+        Op::FullscreenWindow(1),
+        Op::SetLayoutMode(LayoutMode::MasterStack),
+        Op::Communicate(1),
+        Op::MoveColumnRight,
+        Op::SetLayoutMode(LayoutMode::Dwindle),
+        Op::ToggleWindowFloating { id: Some(0) },
+        Op::ToggleWindowFloating { id: Some(0) },
+    ];
+
+    let mut layout = check_ops(ops);
+
+    let ws = layout.active_workspace().unwrap();
+    assert_eq!(ws.layout_mode(), LayoutMode::Dwindle);
+    assert!(ws.scrolling().is_empty());
+    assert_eq!(ws.tiling().tiles().count(), 3);
+
+    check_ops_on_layout(&mut layout, [Op::SetLayoutMode(LayoutMode::Scrolling)]);
+
+    let ws = layout.active_workspace().unwrap();
+    assert!(ws.tiling().is_empty());
+    assert_eq!(ws.scrolling().columns().count(), 3);
+}
+
+#[test]
+fn restoring_saved_layout_puts_windows_back() {
+    fn hints(window: &TestWindow) -> WindowHints {
+        WindowHints {
+            title: Some(window.id().to_string()),
+            ..Default::default()
+        }
+    }
+
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(0),
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::ConsumeOrExpelWindowLeft { id: None },
+        Op::SetWindowHeight {
+            id: Some(0),
+            change: SizeChange::SetFixed(300),
+        },
+        Op::SetColumnDisplay(ColumnDisplay::Tabbed),
+        Op::AddWindow {
+            params: TestWindowParams::new(2),
+        },
+        Op::AddWindow {
+            params: TestWindowParams {
+                is_floating: true,
+                ..TestWindowParams::new(3)
+            },
+        },
+        Op::MoveFloatingWindow {
+            id: None,
+            x: PositionChange::SetFixed(100.),
+            y: PositionChange::SetFixed(200.),
+            animate: false,
+        },
+    ];
+
+    let layout = check_ops(ops);
+    let saved = layout.saved_layout(hints).unwrap();
+
+    let mut layout = check_ops([Op::AddOutput(1)]);
+    layout.set_pending_restore(saved.clone());
+
+    // Map the windows in a different order from how they were opened.
+    for id in [2, 3, 1, 0] {
+        let window = TestWindow::new(TestWindowParams::new(id));
+        let slot = layout.find_restore_slot(&hints(&window)).unwrap();
+        layout.add_window_restored(window, slot, ActivateWindow::default());
+    }
+    layout.verify_invariants();
+
+    assert!(!layout.has_pending_restore());
+    assert_eq!(layout.saved_layout(hints), Some(saved));
+
+    let window = TestWindow::new(TestWindowParams::new(0));
+    assert_eq!(layout.find_restore_slot(&hints(&window)), None);
+}
+
+#[test]
+fn scratchpad_keeps_floating_position() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(0),
+        },
+        Op::AddWindow {
+            params: TestWindowParams {
+                is_floating: true,
+                ..TestWindowParams::new(1)
+            },
+        },
+        Op::MoveFloatingWindow {
+            id: None,
+            x: PositionChange::SetFixed(100.),
+            y: PositionChange::SetFixed(200.),
+            animate: false,
+        },
+    ];
+
+    let mut layout = check_ops(ops);
+    let ws = layout.active_workspace().unwrap();
+    let (_, pos) = ws.floating().tiles_with_offsets().next().unwrap();
+
+    check_ops_on_layout(&mut layout, [Op::MoveWindowToScratchpad { id: None }]);
+    assert!(layout.scratchpad_has_window(&1));
+    assert!(!layout.has_window(&1));
+
+    check_ops_on_layout(&mut layout, [Op::ToggleScratchpad]);
+    assert!(!layout.scratchpad_has_window(&1));
+    assert_eq!(layout.focus().map(|win| win.0.id), Some(1));
+    let ws = layout.active_workspace().unwrap();
+    assert!(ws.is_floating(&1));
+    let (_, new_pos) = ws.floating().tiles_with_offsets().next().unwrap();
+    assert_eq!(new_pos, pos);
+
+    // Toggling again while the summoned window is focused hides it.
+    check_ops_on_layout(&mut layout, [Op::ToggleScratchpad]);
+    assert!(layout.scratchpad_has_window(&1));
+    assert_eq!(layout.focus().map(|win| win.0.id), Some(0));
+}
+
+#[test]
+fn sticky_window_follows_active_workspace() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(0),
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::ToggleWindowSticky { id: None },
+        Op::FocusWorkspaceDown,
+    ];
+
+    let mut layout = check_ops(ops);
+    let ws = layout.active_workspace().unwrap();
+    assert!(!ws.has_window(&0));
+    assert!(ws.is_sticky(&1));
+    assert!(ws.floating_is_active());
+    let (_, pos) = ws.floating().tiles_with_offsets().next().unwrap();
+    assert_eq!(layout.focus().map(|win| win.0.id), Some(1));
+
+    check_ops_on_layout(&mut layout, [Op::FocusWorkspaceUp]);
+    let ws = layout.active_workspace().unwrap();
+    assert!(ws.has_window(&0));
+    assert!(ws.is_sticky(&1));
+    assert!(ws.floating_is_active());
+    assert_eq!(layout.focus().map(|win| win.0.id), Some(1));
+    let (_, new_pos) = ws.floating().tiles_with_offsets().next().unwrap();
+    assert_eq!(new_pos, pos);
+
+    // Moving the window to the tiling layout makes it no longer sticky.
+    check_ops_on_layout(&mut layout, [Op::ToggleWindowFloating { id: Some(1) }]);
+    assert!(!layout.active_workspace().unwrap().is_sticky(&1));
+}
+
+#[test]
+fn swallowed_window_returns_into_its_tile() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(0),
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::FocusColumnLeft,
+        Op::SetColumnWidth(SizeChange::SetFixed(300)),
+        Op::ConsumeWindowIntoColumn,
+        Op::FocusWindowDown,
+        Op::SwallowWindow {
+            params: TestWindowParams::new(2),
+            parent_id: 1,
+        },
+    ];
+
+    let mut layout = check_ops(ops);
+    assert!(layout.is_swallowed(&1));
+    assert!(!layout.has_window(&1));
+    assert_eq!(layout.focus().unwrap().id(), &2);
+
+    let column_ids = |layout: &Layout<TestWindow>| {
+        let ws = layout.active_workspace().unwrap();
+        let col = ws.scrolling().columns().next().unwrap();
+        let ids: Vec<_> = col.tiles().map(|(tile, _)| *tile.window().id()).collect();
+        (ids, col.desired_width())
+    };
+    let (ids, width) = column_ids(&layout);
+    assert_eq!(ids, [0, 2]);
+
+    // Closing the swallowing window puts the swallowed one back in the same place.
+    check_ops_on_layout(&mut layout, [Op::CloseWindow(2)]);
+    assert!(!layout.is_swallowed(&1));
+    let (ids, new_width) = column_ids(&layout);
+    assert_eq!(ids, [0, 1]);
+    assert_eq!(new_width, width);
+}
+
+#[test]
+fn minimized_window_restores_on_its_workspace() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(0),
+        },
+        Op::AddWindow {
+            params: TestWindowParams {
+                is_floating: true,
+                ..TestWindowParams::new(1)
+            },
+        },
+        Op::MinimizeWindow { id: None },
+    ];
+
+    let mut layout = check_ops(ops);
+    assert!(layout.is_minimized(&1));
+    assert!(!layout.has_window(&1));
+    assert_eq!(layout.focus().map(|win| win.0.id), Some(0));
+
+    // The workspace holding a minimized window is kept around.
+    check_ops_on_layout(
+        &mut layout,
+        [Op::MinimizeWindow { id: None }, Op::FocusWorkspaceDown],
+    );
+    assert!(layout.is_minimized(&0));
+    let ws_ids: Vec<_> = layout.workspaces().map(|(_, _, ws)| ws.id()).collect();
+    assert_eq!(ws_ids.len(), 2);
+
+    // Restoring by id switches back to the window's workspace.
+    check_ops_on_layout(&mut layout, [Op::RestoreMinimizedWindow { id: Some(1) }]);
+    assert!(!layout.is_minimized(&1));
+    assert_eq!(layout.focus().map(|win| win.0.id), Some(1));
+    let ws = layout.active_workspace().unwrap();
+    assert_eq!(ws.id(), ws_ids[0]);
+    assert!(ws.is_floating(&1));
+
+    check_ops_on_layout(&mut layout, [Op::RestoreMinimizedWindow { id: None }]);
+    assert!(!layout.is_minimized(&0));
+    assert!(!layout.active_workspace().unwrap().is_floating(&0));
+}
+
+#[test]
+fn undo_and_redo_consume_into_column() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(0),
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::SetColumnWidth(SizeChange::SetFixed(300)),
+    ];
+
+    let columns = |layout: &Layout<TestWindow>| {
+        let ws = layout.active_workspace().unwrap();
+        ws.scrolling()
+            .columns()
+            .map(|col| {
+                let ids: Vec<_> = col.tiles().map(|(tile, _)| *tile.window().id()).collect();
+                (ids, col.desired_width())
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut layout = check_ops(ops);
+    let before = columns(&layout);
+    assert_eq!(before.len(), 2);
+
+    check_ops_on_layout(&mut layout, [Op::ConsumeOrExpelWindowLeft { id: None }]);
+    let after = columns(&layout);
+    assert_eq!(after.len(), 1);
+
+    check_ops_on_layout(&mut layout, [Op::UndoLayoutChange]);
+    assert_eq!(columns(&layout), before);
+    assert_eq!(layout.focus().map(|win| win.0.id), Some(1));
+
+    check_ops_on_layout(&mut layout, [Op::RedoLayoutChange]);
+    assert_eq!(columns(&layout), after);
+
+    // Undoing twice also reverts the width change.
+    check_ops_on_layout(&mut layout, [Op::UndoLayoutChange, Op::UndoLayoutChange]);
+    let widths: Vec<_> = columns(&layout).into_iter().map(|(_, w)| w).collect();
+    assert_eq!(widths[0], widths[1]);
+}
+
+#[test]
+fn undo_move_column_to_workspace() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(0),
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::MoveColumnToWorkspaceDown(true),
+    ];
+
+    let mut layout = check_ops(ops);
+    assert!(!layout.active_workspace().unwrap().has_window(&0));
+
+    check_ops_on_layout(&mut layout, [Op::UndoLayoutChange]);
+    let ws = layout.active_workspace().unwrap();
+    assert!(ws.has_window(&0));
+    assert!(ws.has_window(&1));
+    assert_eq!(layout.focus().map(|win| win.0.id), Some(1));
+}
+
+#[test]
+fn swap_windows_across_workspaces() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(0),
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::MoveColumnToWorkspaceDown(false),
+        Op::AddWindow {
+            params: TestWindowParams::new(2),
+        },
+        Op::FocusWindow(0),
+        Op::SwapWindows { id: None, other: 1 },
+    ];
+
+    let layout = check_ops(ops);
+    let ws = layout.active_workspace().unwrap();
+    assert!(ws.has_window(&0));
+    assert!(!ws.has_window(&1));
+    assert_eq!(layout.focus().map(|win| win.0.id), Some(0));
+
+    // The other window takes the column of the focused one.
+    let (_, _, ws) = layout.workspaces().next().unwrap();
+    let columns: Vec<Vec<_>> = ws
+        .scrolling()
+        .columns()
+        .map(|col| col.tiles().map(|(tile, _)| *tile.window().id()).collect())
+        .collect();
+    assert_eq!(columns, [vec![1], vec![2]]);
+}
+
+#[test]
+fn vertical_orientation_stacks_columns() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(0),
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+    ];
+
+    let options = Options {
+        layout: niri_config::Layout {
+            orientation: Orientation::Vertical,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut layout = check_ops_with_options(options, ops);
+
+    let ws = layout.active_workspace().unwrap();
+    assert_eq!(ws.scrolling().columns().count(), 2);
+    let pos = |id| {
+        ws.tiles_with_render_positions()
+            .find(|(tile, _, _)| *tile.window().id() == id)
+            .unwrap()
+            .1
+    };
+    let (first, second) = (pos(0), pos(1));
+    assert_eq!(first.x, second.x);
+    assert!(first.y < second.y);
+
+    // Directional focus follows the screen: up goes to the previous column.
+    check_ops_on_layout(&mut layout, [Op::FocusWindowUp]);
+    assert_eq!(layout.focus().unwrap().id(), &0);
+    check_ops_on_layout(&mut layout, [Op::FocusColumnRight]);
+    assert_eq!(layout.focus().unwrap().id(), &0);
+    check_ops_on_layout(&mut layout, [Op::FocusWindowDown]);
+    assert_eq!(layout.focus().unwrap().id(), &1);
+}
+
 #[test]
 fn removing_output_must_keep_empty_focus_on_primary() {
     let ops = [
@@ -2613,6 +3174,121 @@ fn interactive_move_onto_empty_output_ewaf() {
     check_ops_with_options(options, ops);
 }
 
+#[test]
+fn interactive_move_snaps_floating_window_to_output_edge() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams {
+                is_floating: true,
+                ..TestWindowParams::new(0)
+            },
+        },
+    ];
+
+    let floating_pos = |layout: &Layout<TestWindow>| {
+        let ws = layout.active_workspace().unwrap();
+        ws.floating().tiles_with_offsets().next().unwrap().1
+    };
+
+    let mut layout = check_ops(ops);
+    let pos = floating_pos(&layout);
+    let (px, py) = (pos.x + 10., pos.y + 10.);
+
+    // Drag the window until its left edge is 5 px away from the output edge.
+    let new_px = 15.;
+    check_ops_on_layout(
+        &mut layout,
+        [
+            Op::InteractiveMoveBegin {
+                window: 0,
+                output_idx: 1,
+                px,
+                py,
+            },
+            Op::InteractiveMoveUpdate {
+                window: 0,
+                dx: 0.,
+                dy: 0.,
+                output_idx: 1,
+                px,
+                py,
+            },
+            Op::InteractiveMoveUpdate {
+                window: 0,
+                dx: new_px - px,
+                dy: 0.,
+                output_idx: 1,
+                px: new_px,
+                py,
+            },
+            Op::InteractiveMoveEnd { window: 0 },
+        ],
+    );
+
+    assert_eq!(floating_pos(&layout).x, 0.);
+}
+
+#[test]
+fn interactive_move_to_output_edge_snaps_floating_window_to_zone() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams {
+                is_floating: true,
+                ..TestWindowParams::new(0)
+            },
+        },
+    ];
+
+    let mut layout = check_ops(ops);
+    let ws = layout.active_workspace().unwrap();
+    let pos = ws.floating().tiles_with_offsets().next().unwrap().1;
+    let (px, py) = (pos.x + 10., pos.y + 10.);
+
+    // Drag the pointer onto the left output edge, away from the corners.
+    check_ops_on_layout(
+        &mut layout,
+        [
+            Op::InteractiveMoveBegin {
+                window: 0,
+                output_idx: 1,
+                px,
+                py,
+            },
+            Op::InteractiveMoveUpdate {
+                window: 0,
+                dx: 0.,
+                dy: 0.,
+                output_idx: 1,
+                px,
+                py,
+            },
+            Op::InteractiveMoveUpdate {
+                window: 0,
+                dx: -px,
+                dy: 360. - py,
+                output_idx: 1,
+                px: 0.,
+                py: 360.,
+            },
+        ],
+    );
+
+    let Some(InteractiveMoveState::Moving(move_)) = &layout.interactive_move else {
+        unreachable!()
+    };
+    assert_eq!(move_.snap_zone, Some(SnapZone::Left));
+
+    check_ops_on_layout(&mut layout, [Op::InteractiveMoveEnd { window: 0 }]);
+
+    // The window fills the left half of the working area, with gaps around it.
+    let ws = layout.active_workspace().unwrap();
+    let (tile, pos) = ws.floating().tiles_with_offsets().next().unwrap();
+    assert_eq!(pos, Point::from((16., 16.)));
+    assert_eq!(tile.window().requested_size(), Some(Size::from((616, 688))));
+}
+
 #[test]
 fn interactive_move_onto_last_workspace() {
     let ops = [
@@ -3732,6 +4408,105 @@ fn workspace_render_geo_at_fractional_scale() {
     );
 }
 
+#[test]
+fn actions_in_a_row_animate_only_the_end_state() {
+    let mut options = Options {
+        layout: niri_config::Layout {
+            center_focused_column: CenterFocusedColumn::Always,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    options.animations.horizontal_view_movement.0.kind = Kind::Easing(EasingParams {
+        duration_ms: 1000,
+        curve: Curve::Linear,
+    });
+
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(2),
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(3),
+        },
+        Op::CompleteAnimations,
+    ];
+    let mut layout = check_ops_with_options(options, ops);
+    let view_pos = |layout: &Layout<TestWindow>| {
+        let ws = layout.active_workspace().unwrap();
+        (ws.scrolling().view_pos(), ws.scrolling().target_view_pos())
+    };
+    let (start, _) = view_pos(&layout);
+
+    // Like in an action sequence, no time passes between the actions. The view passes over the
+    // first column on the way, but that should never show up on screen.
+    let ops = [
+        Op::FocusColumnLeft,
+        Op::FocusColumnLeft,
+        Op::FocusColumnRight,
+    ];
+    check_ops_on_layout(&mut layout, ops);
+
+    let (pos, end) = view_pos(&layout);
+    assert_eq!(pos, start);
+    assert_ne!(end, start);
+
+    // The view moves straight from the start to the end.
+    Op::AdvanceAnimations { msec_delta: 500 }.apply(&mut layout);
+    let (pos, _) = view_pos(&layout);
+    assert!(
+        (pos - (start + end) / 2.).abs() < 0.001,
+        "{pos} is off the way to {end}"
+    );
+
+    Op::AdvanceAnimations { msec_delta: 500 }.apply(&mut layout);
+    let (pos, _) = view_pos(&layout);
+    assert_eq!(pos, end);
+}
+
+#[test]
+fn share_pending_transactions_joins_separate_resizes() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(2),
+        },
+        // Send the configures for adding the windows.
+        Op::Refresh { is_active: true },
+        // Each resize starts its own transaction.
+        Op::SetColumnWidth(SizeChange::SetProportion(25.)),
+        Op::FocusColumnLeft,
+        Op::SetColumnWidth(SizeChange::SetProportion(75.)),
+    ];
+    let mut layout = check_ops(ops);
+    let is_only_holder = |layout: &Layout<TestWindow>, id| {
+        let (_, win) = layout.windows().find(|(_, win)| win.0.id == id).unwrap();
+        let transaction = win.0.transaction.borrow();
+        transaction.as_ref().unwrap().is_last()
+    };
+    assert!(is_only_holder(&layout, 1));
+    assert!(is_only_holder(&layout, 2));
+
+    layout.share_pending_transactions();
+    assert!(!is_only_holder(&layout, 1));
+    assert!(!is_only_holder(&layout, 2));
+
+    // Once one window sends its configure, the other one holds the transaction alone.
+    for (_, win) in layout.windows() {
+        if win.0.id == 1 {
+            win.0.transaction.take();
+        }
+    }
+    assert!(is_only_holder(&layout, 2));
+}
+
 fn parent_id_causes_loop(layout: &Layout<TestWindow>, id: usize, mut parent_id: usize) -> bool {
     if parent_id == id {
         return true;
@@ -3801,6 +4576,10 @@ fn arbitrary_center_focused_column() -> impl Strategy<Value = CenterFocusedColum
     ]
 }
 
+fn arbitrary_orientation() -> impl Strategy<Value = Orientation> {
+    prop_oneof![Just(Orientation::Horizontal), Just(Orientation::Vertical)]
+}
+
 fn arbitrary_tab_indicator_position() -> impl Strategy<Value = TabIndicatorPosition> {
     prop_oneof![
         Just(TabIndicatorPosition::Left),
@@ -3888,6 +4667,8 @@ prop_compose! {
         center_focused_column in prop::option::of(arbitrary_center_focused_column()),
         always_center_single_column in prop::option::of(any::<bool>().prop_map(Flag)),
         empty_workspace_above_first in prop::option::of(any::<bool>().prop_map(Flag)),
+        layout_mode in prop::option::of(arbitrary_layout_mode()),
+        orientation in prop::option::of(arbitrary_orientation()),
     ) -> niri_config::LayoutPart {
         niri_config::LayoutPart {
             gaps,
@@ -3899,6 +4680,8 @@ prop_compose! {
             border,
             shadow,
             tab_indicator,
+            layout_mode,
+            orientation,
             ..Default::default()
         }
     }
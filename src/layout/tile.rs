@@ -9,6 +9,7 @@ use smithay::backend::renderer::gles::GlesRenderer;
 use smithay::utils::{Logical, Point, Rectangle, Scale, Size};
 
 use super::focus_ring::{FocusRing, FocusRingRenderElement};
+use super::mark_label::MarkLabel;
 use super::opening_window::{OpenAnimation, OpeningWindowRenderElement};
 use super::shadow::Shadow;
 use super::{
@@ -23,6 +24,7 @@ use crate::render_helpers::border::BorderRenderElement;
 use crate::render_helpers::clipped_surface::{ClippedSurfaceRenderElement, RoundedCornerDamage};
 use crate::render_helpers::damage::ExtraDamage;
 use crate::render_helpers::offscreen::{OffscreenBuffer, OffscreenRenderElement};
+use crate::render_helpers::primary_gpu_texture::PrimaryGpuTextureRenderElement;
 use crate::render_helpers::renderer::NiriRenderer;
 use crate::render_helpers::resize::ResizeRenderElement;
 use crate::render_helpers::shadow::ShadowRenderElement;
@@ -50,6 +52,9 @@ pub struct Tile<W: LayoutElement> {
     /// The shadow around the window.
     shadow: Shadow,
 
+    /// The label showing the window mark.
+    mark_label: MarkLabel,
+
     /// This tile's current sizing mode.
     ///
     /// This will update only when the `window` actually goes maximized or fullscreen, rather than
@@ -75,6 +80,9 @@ pub struct Tile<W: LayoutElement> {
     /// the window starts out in the tiling layout.
     pub(super) floating_pos: Option<Point<f64, SizeFrac>>,
 
+    /// Whether the tile follows the active workspace of its monitor while floating.
+    pub(super) is_sticky: bool,
+
     /// Currently selected preset width index when this tile is floating.
     pub(super) floating_preset_width_idx: Option<usize>,
 
@@ -133,6 +141,7 @@ niri_render_elements! {
         Offscreen = OffscreenRenderElement,
         ExtraDamage = ExtraDamage,
         BackgroundEffect = BackgroundEffectElement,
+        MarkLabel = PrimaryGpuTextureRenderElement,
     }
 }
 
@@ -197,11 +206,13 @@ impl<W: LayoutElement> Tile<W> {
             border: FocusRing::new(border_config.into()),
             focus_ring: FocusRing::new(focus_ring_config),
             shadow: Shadow::new(shadow_config),
+            mark_label: MarkLabel::default(),
             sizing_mode,
             fullscreen_backdrop: SolidColorBuffer::new((0., 0.), [0., 0., 0., 1.]),
             restore_to_floating: false,
             floating_window_size: None,
             floating_pos: None,
+            is_sticky: rules.open_sticky == Some(true),
             floating_preset_width_idx: None,
             floating_preset_height_idx: None,
             open_animation: None,
@@ -647,6 +658,17 @@ impl<W: LayoutElement> Tile<W> {
         });
     }
 
+    pub fn offset_move_x_anim_current(&mut self, offset: f64) {
+        if let Some(move_) = self.move_x_animation.as_mut() {
+            // If the anim is almost done, there's little point trying to offset it; we can let
+            // things jump. If it turns out like a bad idea, we could restart the anim instead.
+            let value = move_.anim.value();
+            if value > 0.001 {
+                move_.from += offset / value;
+            }
+        }
+    }
+
     pub fn offset_move_y_anim_current(&mut self, offset: f64) {
         if let Some(move_) = self.move_y_animation.as_mut() {
             // If the anim is almost done, there's little point trying to offset it; we can let
@@ -710,6 +732,10 @@ impl<W: LayoutElement> Tile<W> {
         &mut self.window
     }
 
+    pub fn into_window(self) -> W {
+        self.window
+    }
+
     pub fn sizing_mode(&self) -> SizingMode {
         self.sizing_mode
     }
@@ -1122,6 +1148,17 @@ impl<W: LayoutElement> Tile<W> {
             &mut |elem| push(elem.into()),
         );
 
+        if self.options.layout.show_window_marks {
+            if let Some(mark) = self.window.mark() {
+                if let Some(elem) =
+                    self.mark_label
+                        .render(ctx.renderer, mark, self.scale, window_render_loc)
+                {
+                    push(elem.into());
+                }
+            }
+        }
+
         // If we're resizing, try to render a shader, or a fallback.
         let mut pushed_resize = false;
         if let Some(resize) = &self.resize_animation {
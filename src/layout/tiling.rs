@@ -0,0 +1,1208 @@
+use std::cmp::{max, min};
+use std::iter::zip;
+use std::mem;
+use std::rc::Rc;
+
+use niri_config::utils::MergeWith as _;
+use niri_config::PresetSize;
+use niri_ipc::{LayoutMode, SizeChange, WindowLayout};
+use smithay::backend::renderer::gles::GlesRenderer;
+use smithay::utils::{Logical, Point, Rectangle, Scale, Serial, Size};
+
+use super::closing_window::{ClosingWindow, ClosingWindowRenderElement};
+use super::scrolling::{compute_working_area, ColumnWidth};
+use super::tile::{Tile, TileRenderElement, TileRenderSnapshot};
+use super::{ConfigureIntent, HitType, LayoutElement, Options, RemovedTile};
+use crate::animation::{Animation, Clock};
+use crate::layout::RenderLayer;
+use crate::niri_render_elements;
+use crate::render_helpers::renderer::NiriRenderer;
+use crate::render_helpers::xray::XrayPos;
+use crate::render_helpers::RenderCtx;
+use crate::utils::round_logical_in_physical;
+use crate::utils::transaction::{Transaction, TransactionBlocker};
+use crate::window::ResolvedWindowRules;
+
+/// Default share of the available size given to the first part of a split.
+const DEFAULT_SPLIT_RATIO: f64 = 0.5;
+
+/// Limits for the split ratio, so that no part of a split can disappear completely.
+const MIN_SPLIT_RATIO: f64 = 0.05;
+const MAX_SPLIT_RATIO: f64 = 0.95;
+
+/// Moves up to this logical pixel distance are not animated.
+const ANIMATION_THRESHOLD_SQ: f64 = 10. * 10.;
+
+/// Algorithm that a [`TilingSpace`] arranges its windows with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TilingMode {
+    /// The first window takes the left part of the screen, the rest are stacked on the right.
+    MasterStack,
+    /// Every window takes a part of the remaining area, alternating the split direction.
+    Dwindle,
+}
+
+impl TilingMode {
+    pub fn from_layout_mode(mode: LayoutMode) -> Option<Self> {
+        match mode {
+            LayoutMode::Scrolling => None,
+            LayoutMode::MasterStack => Some(Self::MasterStack),
+            LayoutMode::Dwindle => Some(Self::Dwindle),
+        }
+    }
+}
+
+/// Space for windows arranged by a classic tiling algorithm.
+///
+/// This is an alternative to the [`ScrollingSpace`](super::scrolling::ScrollingSpace): all
+/// windows always fit in the working area, and their positions and sizes are fully determined by
+/// their order and the tiling mode.
+#[derive(Debug)]
+pub struct TilingSpace<W: LayoutElement> {
+    /// Tiles in layout order.
+    ///
+    /// In the master-stack mode, the first tile is the master.
+    tiles: Vec<Tile<W>>,
+
+    /// Extra per-tile data.
+    ///
+    /// Must have the same number of elements as `tiles`.
+    data: Vec<Data>,
+
+    /// Index of the active tile.
+    ///
+    /// Only meaningful when `tiles` isn't empty.
+    active_tile_idx: usize,
+
+    /// Algorithm used to arrange the tiles.
+    mode: TilingMode,
+
+    /// Share of the available size taken by the first part of every split.
+    split_ratio: f64,
+
+    /// Windows in the closing animation.
+    closing_windows: Vec<ClosingWindow>,
+
+    /// View size for this space.
+    view_size: Size<f64, Logical>,
+
+    /// Working area for this space.
+    ///
+    /// Takes into account layer-shell exclusive zones and niri struts.
+    working_area: Rectangle<f64, Logical>,
+
+    /// Working area for this space excluding struts.
+    ///
+    /// Used as the target area for maximized windows.
+    parent_area: Rectangle<f64, Logical>,
+
+    /// Scale of the output the space is on (and rounds its sizes to).
+    scale: f64,
+
+    /// Clock for driving animations.
+    clock: Clock,
+
+    /// Configurable properties of the layout.
+    options: Rc<Options>,
+}
+
+niri_render_elements! {
+    TilingSpaceRenderElement<R> => {
+        Tile = TileRenderElement<R>,
+        ClosingWindow = ClosingWindowRenderElement,
+    }
+}
+
+/// Extra per-tile data.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Data {
+    /// Position of the tile in the workspace view.
+    ///
+    /// Not including the animated render offset.
+    pos: Point<f64, Logical>,
+
+    /// Whether the tile is pending fullscreen.
+    is_pending_fullscreen: bool,
+
+    /// Whether the tile is pending maximized.
+    is_pending_maximized: bool,
+}
+
+impl<W: LayoutElement> TilingSpace<W> {
+    pub fn new(
+        view_size: Size<f64, Logical>,
+        parent_area: Rectangle<f64, Logical>,
+        scale: f64,
+        clock: Clock,
+        options: Rc<Options>,
+    ) -> Self {
+        let working_area = compute_working_area(parent_area, scale, options.layout.struts);
+
+        Self {
+            tiles: Vec::new(),
+            data: Vec::new(),
+            active_tile_idx: 0,
+            mode: TilingMode::MasterStack,
+            split_ratio: DEFAULT_SPLIT_RATIO,
+            closing_windows: Vec::new(),
+            view_size,
+            working_area,
+            parent_area,
+            scale,
+            clock,
+            options,
+        }
+    }
+
+    pub fn update_config(
+        &mut self,
+        view_size: Size<f64, Logical>,
+        parent_area: Rectangle<f64, Logical>,
+        scale: f64,
+        options: Rc<Options>,
+    ) {
+        let working_area = compute_working_area(parent_area, scale, options.layout.struts);
+
+        for tile in &mut self.tiles {
+            tile.update_config(view_size, scale, options.clone());
+        }
+
+        self.view_size = view_size;
+        self.working_area = working_area;
+        self.parent_area = parent_area;
+        self.scale = scale;
+        self.options = options;
+
+        self.arrange(false, Transaction::new());
+    }
+
+    pub fn update_shaders(&mut self) {
+        for tile in &mut self.tiles {
+            tile.update_shaders();
+        }
+    }
+
+    pub fn advance_animations(&mut self) {
+        for tile in &mut self.tiles {
+            tile.advance_animations();
+        }
+
+        self.closing_windows.retain_mut(|closing| {
+            closing.advance_animations();
+            closing.are_animations_ongoing()
+        });
+    }
+
+    pub fn are_animations_ongoing(&self) -> bool {
+        self.tiles.iter().any(Tile::are_animations_ongoing) || !self.closing_windows.is_empty()
+    }
+
+    pub fn are_transitions_ongoing(&self) -> bool {
+        self.tiles.iter().any(Tile::are_transitions_ongoing) || !self.closing_windows.is_empty()
+    }
+
+    pub fn update_render_elements(&mut self, is_active: bool, layer: RenderLayer) {
+        let view_rect = Rectangle::from_size(self.view_size);
+        let active_idx = self.active_tile_idx;
+
+        for (idx, (tile, pos)) in self.tiles_with_offsets_mut().enumerate() {
+            // Skip tiles belonging to a different render layer.
+            if layer.is_normal() == tile.is_moving_between_workspaces() {
+                continue;
+            }
+
+            let is_active = is_active && idx == active_idx;
+
+            let mut tile_view_rect = view_rect;
+            tile_view_rect.loc -= pos + tile.render_offset();
+            tile.update_render_elements(is_active, tile_view_rect);
+        }
+    }
+
+    pub fn mode(&self) -> TilingMode {
+        self.mode
+    }
+
+    pub fn set_mode(&mut self, mode: TilingMode) {
+        if self.mode == mode {
+            return;
+        }
+
+        self.mode = mode;
+        self.arrange(true, Transaction::new());
+    }
+
+    pub fn tiles(&self) -> impl Iterator<Item = &Tile<W>> + '_ {
+        self.tiles.iter()
+    }
+
+    pub fn tiles_mut(&mut self) -> impl Iterator<Item = &mut Tile<W>> + '_ {
+        self.tiles.iter_mut()
+    }
+
+    pub fn tiles_with_offsets(&self) -> impl Iterator<Item = (&Tile<W>, Point<f64, Logical>)> + '_ {
+        let offsets = self.data.iter().map(|d| d.pos);
+        zip(&self.tiles, offsets)
+    }
+
+    pub fn tiles_with_offsets_mut(
+        &mut self,
+    ) -> impl Iterator<Item = (&mut Tile<W>, Point<f64, Logical>)> + '_ {
+        let offsets = self.data.iter().map(|d| d.pos);
+        zip(&mut self.tiles, offsets)
+    }
+
+    pub fn tiles_with_render_positions(
+        &self,
+    ) -> impl Iterator<Item = (&Tile<W>, Point<f64, Logical>)> {
+        let scale = self.scale;
+        self.tiles_with_offsets().map(move |(tile, offset)| {
+            let pos = offset + tile.render_offset();
+            // Round to physical pixels.
+            let pos = pos.to_physical_precise_round(scale).to_logical(scale);
+            (tile, pos)
+        })
+    }
+
+    pub fn tiles_with_render_positions_mut(
+        &mut self,
+        round: bool,
+    ) -> impl Iterator<Item = (&mut Tile<W>, Point<f64, Logical>)> {
+        let scale = self.scale;
+        self.tiles_with_offsets_mut().map(move |(tile, offset)| {
+            let mut pos = offset + tile.render_offset();
+            // Round to physical pixels.
+            if round {
+                pos = pos.to_physical_precise_round(scale).to_logical(scale);
+            }
+            (tile, pos)
+        })
+    }
+
+    pub fn tiles_with_ipc_layouts(&self) -> impl Iterator<Item = (&Tile<W>, WindowLayout)> {
+        let scale = self.scale;
+        self.tiles_with_offsets().map(move |(tile, offset)| {
+            // Do not include animated render offset here to avoid IPC spam.
+            let pos = offset;
+            // Round to physical pixels.
+            let pos = pos.to_physical_precise_round(scale).to_logical(scale);
+
+            let layout = WindowLayout {
+                tile_pos_in_workspace_view: Some(pos.into()),
+                ..tile.ipc_layout_template()
+            };
+            (tile, layout)
+        })
+    }
+
+    /// Returns tile indices in the order they should be rendered, from top to bottom.
+    ///
+    /// The active tile goes first, so that it is drawn on top, for example when it is fullscreen.
+    fn render_order(&self) -> impl Iterator<Item = usize> {
+        let active = self.active_tile_idx;
+        (0..self.tiles.len()).map(move |i| {
+            if i == 0 {
+                active
+            } else if i <= active {
+                i - 1
+            } else {
+                i
+            }
+        })
+    }
+
+    fn tile_render_pos(&self, idx: usize) -> Point<f64, Logical> {
+        let pos = self.data[idx].pos + self.tiles[idx].render_offset();
+        // Round to physical pixels.
+        pos.to_physical_precise_round(self.scale)
+            .to_logical(self.scale)
+    }
+
+    fn tile_center(&self, idx: usize) -> Point<f64, Logical> {
+        self.data[idx].pos + self.tiles[idx].tile_size().to_point().downscale(2.)
+    }
+
+    pub fn new_window_toplevel_bounds(&self, rules: &ResolvedWindowRules) -> Size<i32, Logical> {
+        let border_config = self.options.layout.border.merged_with(&rules.border);
+        compute_toplevel_bounds(
+            border_config,
+            self.working_area.size,
+            self.options.layout.gaps,
+        )
+    }
+
+    /// Computes the size for a new window that will be added with default parameters.
+    pub fn new_window_size(&self, rules: &ResolvedWindowRules) -> Size<i32, Logical> {
+        let idx = self.default_insert_idx();
+        let rects = self.compute_rects(self.tiles.len() + 1);
+        let mut size = rects[idx].size;
+
+        let border = self.options.layout.border.merged_with(&rules.border);
+        if !border.off {
+            size.w -= border.width * 2.;
+            size.h -= border.width * 2.;
+        }
+
+        Size::from((max(1, size.w.floor() as i32), max(1, size.h.floor() as i32)))
+    }
+
+    /// Returns the geometry of the active window relative to and clamped to the working area.
+    ///
+    /// During animations, assumes the final tile position.
+    pub fn active_window_visual_rectangle(&self) -> Option<Rectangle<f64, Logical>> {
+        if self.tiles.is_empty() {
+            return None;
+        }
+
+        let tile = &self.tiles[self.active_tile_idx];
+        let window_pos = self.data[self.active_tile_idx].pos + tile.window_loc();
+        let window_size = tile.window_size();
+        let window_rect = Rectangle::new(window_pos, window_size);
+
+        self.working_area.intersection(window_rect)
+    }
+
+    pub fn popup_target_rect(&self, id: &W::Id) -> Option<Rectangle<f64, Logical>> {
+        for (tile, pos) in self.tiles_with_offsets() {
+            if tile.window().id() == id {
+                // Position within the working area.
+                let mut target = self.working_area;
+                target.loc -= pos;
+                target.loc -= tile.window_loc();
+
+                return Some(target);
+            }
+        }
+        None
+    }
+
+    fn idx_of(&self, id: &W::Id) -> Option<usize> {
+        self.tiles.iter().position(|tile| tile.window().id() == id)
+    }
+
+    pub fn active_window(&self) -> Option<&W> {
+        self.tiles.get(self.active_tile_idx).map(Tile::window)
+    }
+
+    pub fn active_window_mut(&mut self) -> Option<&mut W> {
+        self.tiles
+            .get_mut(self.active_tile_idx)
+            .map(Tile::window_mut)
+    }
+
+    pub fn active_tile_mut(&mut self) -> Option<&mut Tile<W>> {
+        self.tiles.get_mut(self.active_tile_idx)
+    }
+
+    pub fn has_window(&self, id: &W::Id) -> bool {
+        self.idx_of(id).is_some()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    pub fn is_active_pending_fullscreen(&self) -> bool {
+        self.data
+            .get(self.active_tile_idx)
+            .is_some_and(|data| data.is_pending_fullscreen)
+    }
+
+    pub fn is_pending_fullscreen(&self, id: &W::Id) -> bool {
+        let idx = self.idx_of(id).unwrap();
+        self.data[idx].is_pending_fullscreen
+    }
+
+    pub fn is_pending_maximized(&self, id: &W::Id) -> bool {
+        let idx = self.idx_of(id).unwrap();
+        self.data[idx].is_pending_maximized
+    }
+
+    fn default_insert_idx(&self) -> usize {
+        if self.tiles.is_empty() {
+            0
+        } else {
+            self.active_tile_idx + 1
+        }
+    }
+
+    /// Adds a tile at the given index, or right after the active tile.
+    pub fn add_tile(&mut self, idx: Option<usize>, mut tile: Tile<W>, activate: bool) {
+        tile.update_config(self.view_size, self.scale, self.options.clone());
+
+        let was_empty = self.tiles.is_empty();
+        let idx = idx
+            .unwrap_or_else(|| self.default_insert_idx())
+            .min(self.tiles.len());
+
+        // Keep the sizing mode that the tile had in its previous layout.
+        let sizing_mode = tile.window().pending_sizing_mode();
+        let data = Data {
+            pos: Point::from((0., 0.)),
+            is_pending_fullscreen: sizing_mode.is_fullscreen(),
+            is_pending_maximized: sizing_mode.is_maximized(),
+        };
+
+        self.tiles.insert(idx, tile);
+        self.data.insert(idx, data);
+
+        if activate || was_empty {
+            self.active_tile_idx = idx;
+        } else if idx <= self.active_tile_idx {
+            self.active_tile_idx += 1;
+        }
+
+        // Put the new tile right at its place so that it doesn't fly in from the corner.
+        let rects = self.compute_rects(self.tiles.len());
+        let pos = self.target_pos(idx, rects[idx]);
+        self.data[idx].pos = pos;
+
+        self.arrange(true, Transaction::new());
+    }
+
+    /// Adds a tile right after the given one.
+    pub fn add_tile_after(&mut self, after: &W::Id, tile: Tile<W>, activate: bool) {
+        let idx = self.idx_of(after).unwrap();
+        self.add_tile(Some(idx + 1), tile, activate);
+    }
+
+    pub fn remove_tile(&mut self, id: &W::Id, transaction: Transaction) -> RemovedTile<W> {
+        let idx = self.idx_of(id).unwrap();
+
+        let tile = self.tiles.remove(idx);
+        self.data.remove(idx);
+
+        if idx < self.active_tile_idx {
+            self.active_tile_idx -= 1;
+        }
+        self.active_tile_idx = min(self.active_tile_idx, self.tiles.len().saturating_sub(1));
+
+        self.arrange(true, transaction);
+
+        let width = ColumnWidth::Fixed(tile.tile_expected_or_current_size().w);
+        RemovedTile {
+            tile,
+            width,
+            is_full_width: false,
+            is_floating: false,
+        }
+    }
+
+    /// Removes all tiles, in layout order.
+    pub fn take_tiles(&mut self) -> Vec<Tile<W>> {
+        self.data.clear();
+        self.active_tile_idx = 0;
+        mem::take(&mut self.tiles)
+    }
+
+    pub fn start_close_animation_for_window(
+        &mut self,
+        renderer: &mut GlesRenderer,
+        id: &W::Id,
+        blocker: TransactionBlocker,
+    ) {
+        let (tile, tile_pos) = self
+            .tiles_with_render_positions_mut(false)
+            .find(|(tile, _)| tile.window().id() == id)
+            .unwrap();
+
+        let Some(snapshot) = tile.take_unmap_snapshot() else {
+            return;
+        };
+
+        let tile_size = tile.tile_size();
+
+        self.start_close_animation_for_tile(renderer, snapshot, tile_size, tile_pos, blocker);
+    }
+
+    pub fn start_close_animation_for_tile(
+        &mut self,
+        renderer: &mut GlesRenderer,
+        snapshot: TileRenderSnapshot,
+        tile_size: Size<f64, Logical>,
+        tile_pos: Point<f64, Logical>,
+        blocker: TransactionBlocker,
+    ) {
+        let anim = Animation::new(
+            self.clock.clone(),
+            0.,
+            1.,
+            0.,
+            self.options.animations.window_close.anim,
+        );
+
+        let blocker = if self.options.disable_transactions {
+            TransactionBlocker::completed()
+        } else {
+            blocker
+        };
+
+        let scale = Scale::from(self.scale);
+        let res = ClosingWindow::new(
+            renderer, snapshot, scale, tile_size, tile_pos, blocker, anim,
+        );
+        match res {
+            Ok(closing) => {
+                self.closing_windows.push(closing);
+            }
+            Err(err) => {
+                warn!("error creating a closing window animation: {err:?}");
+            }
+        }
+    }
+
+    pub fn start_open_animation(&mut self, id: &W::Id) -> bool {
+        let Some(idx) = self.idx_of(id) else {
+            return false;
+        };
+
+        self.tiles[idx].start_open_animation();
+        true
+    }
+
+    pub fn activate_window(&mut self, id: &W::Id) -> bool {
+        let Some(idx) = self.idx_of(id) else {
+            return false;
+        };
+
+        self.active_tile_idx = idx;
+        true
+    }
+
+    /// Finds the closest tile in the given direction from the active tile.
+    ///
+    /// The direction is a unit vector along one of the axes.
+    fn idx_in_direction(&self, dir: Point<f64, Logical>) -> Option<usize> {
+        if self.tiles.is_empty() {
+            return None;
+        }
+
+        let active_idx = self.active_tile_idx;
+        let center = self.tile_center(active_idx);
+
+        (0..self.tiles.len())
+            .filter(|idx| *idx != active_idx)
+            .filter_map(|idx| {
+                let diff = self.tile_center(idx) - center;
+                let along = diff.x * dir.x + diff.y * dir.y;
+                let across = (diff.x * dir.y - diff.y * dir.x).abs();
+                (along > 0.).then_some((idx, along + across))
+            })
+            .min_by(|(_, dist_a), (_, dist_b)| f64::total_cmp(dist_a, dist_b))
+            .map(|(idx, _)| idx)
+    }
+
+    fn focus_directional(&mut self, dir: Point<f64, Logical>) -> bool {
+        let Some(idx) = self.idx_in_direction(dir) else {
+            return false;
+        };
+
+        self.active_tile_idx = idx;
+        true
+    }
+
+    pub fn focus_left(&mut self) -> bool {
+        self.focus_directional(Point::from((-1., 0.)))
+    }
+
+    pub fn focus_right(&mut self) -> bool {
+        self.focus_directional(Point::from((1., 0.)))
+    }
+
+    pub fn focus_up(&mut self) -> bool {
+        self.focus_directional(Point::from((0., -1.)))
+    }
+
+    pub fn focus_down(&mut self) -> bool {
+        self.focus_directional(Point::from((0., 1.)))
+    }
+
+    pub fn focus_first(&mut self) {
+        if self.tiles.is_empty() {
+            return;
+        }
+
+        self.active_tile_idx = 0;
+    }
+
+    pub fn focus_last(&mut self) {
+        if self.tiles.is_empty() {
+            return;
+        }
+
+        self.active_tile_idx = self.tiles.len() - 1;
+    }
+
+    pub fn focus_index(&mut self, index: usize) {
+        if self.tiles.is_empty() {
+            return;
+        }
+
+        self.active_tile_idx = index.saturating_sub(1).min(self.tiles.len() - 1);
+    }
+
+    pub fn focus_topmost(&mut self) {
+        let result = (0..self.tiles.len())
+            .min_by(|a, b| f64::total_cmp(&self.data[*a].pos.y, &self.data[*b].pos.y));
+        if let Some(idx) = result {
+            self.active_tile_idx = idx;
+        }
+    }
+
+    pub fn focus_bottommost(&mut self) {
+        let result = (0..self.tiles.len())
+            .max_by(|a, b| f64::total_cmp(&self.data[*a].pos.y, &self.data[*b].pos.y));
+        if let Some(idx) = result {
+            self.active_tile_idx = idx;
+        }
+    }
+
+    fn move_directional(&mut self, dir: Point<f64, Logical>) -> bool {
+        let Some(idx) = self.idx_in_direction(dir) else {
+            return false;
+        };
+
+        // Data moves together with the tile so that the animation starts from the old position.
+        self.tiles.swap(self.active_tile_idx, idx);
+        self.data.swap(self.active_tile_idx, idx);
+        self.active_tile_idx = idx;
+
+        self.arrange(true, Transaction::new());
+        true
+    }
+
+    pub fn move_left(&mut self) -> bool {
+        self.move_directional(Point::from((-1., 0.)))
+    }
+
+    pub fn move_right(&mut self) -> bool {
+        self.move_directional(Point::from((1., 0.)))
+    }
+
+    pub fn move_up(&mut self) -> bool {
+        self.move_directional(Point::from((0., -1.)))
+    }
+
+    pub fn move_down(&mut self) -> bool {
+        self.move_directional(Point::from((0., 1.)))
+    }
+
+    fn move_to(&mut self, new_idx: usize) {
+        if self.active_tile_idx == new_idx {
+            return;
+        }
+
+        let tile = self.tiles.remove(self.active_tile_idx);
+        let data = self.data.remove(self.active_tile_idx);
+        self.tiles.insert(new_idx, tile);
+        self.data.insert(new_idx, data);
+        self.active_tile_idx = new_idx;
+
+        self.arrange(true, Transaction::new());
+    }
+
+    pub fn move_to_first(&mut self) {
+        if self.tiles.is_empty() {
+            return;
+        }
+
+        self.move_to(0);
+    }
+
+    pub fn move_to_last(&mut self) {
+        if self.tiles.is_empty() {
+            return;
+        }
+
+        self.move_to(self.tiles.len() - 1);
+    }
+
+    pub fn move_to_index(&mut self, index: usize) {
+        if self.tiles.is_empty() {
+            return;
+        }
+
+        self.move_to(index.saturating_sub(1).min(self.tiles.len() - 1));
+    }
+
+    /// Changes the split ratio.
+    ///
+    /// The split ratio is shared by all tiles, so this works the same regardless of which tile is
+    /// focused.
+    pub fn set_split_ratio(&mut self, change: SizeChange) {
+        let available = self.available_width();
+
+        const MAX_F: f64 = 10000.;
+
+        let ratio = match change {
+            SizeChange::SetFixed(width) => f64::from(width) / available,
+            SizeChange::SetProportion(prop) => (prop / 100.).clamp(0., MAX_F),
+            SizeChange::AdjustFixed(delta) => self.split_ratio + f64::from(delta) / available,
+            SizeChange::AdjustProportion(delta) => self.split_ratio + delta / 100.,
+        };
+        let ratio = ratio.clamp(MIN_SPLIT_RATIO, MAX_SPLIT_RATIO);
+
+        if self.split_ratio == ratio {
+            return;
+        }
+
+        self.split_ratio = ratio;
+        self.arrange(true, Transaction::new());
+    }
+
+    /// Switches the split ratio to the next or previous preset column width.
+    pub fn toggle_split_ratio(&mut self, forwards: bool) {
+        let available = self.available_width();
+        let current = self.split_ratio;
+
+        let presets: Vec<f64> = self
+            .options
+            .layout
+            .preset_column_widths
+            .iter()
+            .map(|preset| match *preset {
+                PresetSize::Proportion(prop) => prop,
+                PresetSize::Fixed(width) => f64::from(width) / available,
+            })
+            .collect();
+
+        // Some allowance for rounding.
+        const EPS: f64 = 0.001;
+
+        let ratio = if forwards {
+            presets
+                .iter()
+                .copied()
+                .find(|ratio| current + EPS < *ratio)
+                .or_else(|| presets.first().copied())
+        } else {
+            presets
+                .iter()
+                .copied()
+                .rfind(|ratio| *ratio + EPS < current)
+                .or_else(|| presets.last().copied())
+        };
+
+        if let Some(ratio) = ratio {
+            self.set_split_ratio(SizeChange::SetProportion(ratio * 100.));
+        }
+    }
+
+    fn available_width(&self) -> f64 {
+        f64::max(1., self.working_area.size.w - self.options.layout.gaps * 3.)
+    }
+
+    pub fn set_fullscreen(&mut self, id: &W::Id, is_fullscreen: bool) {
+        let idx = self.idx_of(id).unwrap();
+        if self.data[idx].is_pending_fullscreen == is_fullscreen {
+            return;
+        }
+
+        self.data[idx].is_pending_fullscreen = is_fullscreen;
+        self.arrange(true, Transaction::new());
+    }
+
+    pub fn set_maximized(&mut self, id: &W::Id, maximize: bool) {
+        let idx = self.idx_of(id).unwrap();
+        if self.data[idx].is_pending_maximized == maximize {
+            return;
+        }
+
+        self.data[idx].is_pending_maximized = maximize;
+        self.arrange(true, Transaction::new());
+    }
+
+    pub fn update_window(&mut self, id: &W::Id, serial: Option<Serial>) -> bool {
+        let Some(idx) = self.idx_of(id) else {
+            return false;
+        };
+
+        let tile = &mut self.tiles[idx];
+
+        // Do this before calling update_window() so it can get up-to-date info.
+        if let Some(serial) = serial {
+            tile.window_mut().on_commit(serial);
+        }
+
+        tile.update_window();
+        true
+    }
+
+    pub fn render<R: NiriRenderer>(
+        &self,
+        mut ctx: RenderCtx<R>,
+        xray_pos: XrayPos,
+        focus_ring: bool,
+        layer: RenderLayer,
+        push: &mut dyn FnMut(TilingSpaceRenderElement<R>),
+    ) {
+        let scale = Scale::from(self.scale);
+
+        // Draw the closing windows on top of the other windows.
+        if layer.is_normal() {
+            let view_rect = Rectangle::from_size(self.view_size);
+            for closing in self.closing_windows.iter().rev() {
+                let elem = closing.render(ctx.as_gles(), view_rect, scale);
+                push(elem.into());
+            }
+        }
+
+        for idx in self.render_order() {
+            let tile = &self.tiles[idx];
+
+            // Skip tiles belonging to a different render layer.
+            if layer.is_normal() == tile.is_moving_between_workspaces() {
+                continue;
+            }
+
+            let tile_pos = self.tile_render_pos(idx);
+
+            // For the active tile, draw the focus ring.
+            let focus_ring = focus_ring && idx == self.active_tile_idx;
+
+            let xray_pos = xray_pos.offset(tile_pos);
+            tile.render(ctx.r(), tile_pos, xray_pos, focus_ring, &mut |elem| {
+                push(elem.into())
+            });
+        }
+    }
+
+    pub fn render_above_top_layer(&self) -> bool {
+        // Render above the top layer if we're on a fullscreen window.
+        self.tiles
+            .get(self.active_tile_idx)
+            .is_some_and(|tile| tile.sizing_mode().is_fullscreen())
+    }
+
+    pub fn window_under(&self, pos: Point<f64, Logical>) -> Option<(&W, HitType)> {
+        // This matches the render order.
+        self.render_order().find_map(|idx| {
+            let tile_pos = self.tile_render_pos(idx);
+            HitType::hit_tile(&self.tiles[idx], tile_pos, pos)
+        })
+    }
+
+    /// Returns the index where a tile dropped at `pos` should be inserted.
+    pub fn insert_position(&self, pos: Point<f64, Logical>) -> usize {
+        self.tiles_with_offsets()
+            .position(|(tile, tile_pos)| Rectangle::new(tile_pos, tile.tile_size()).contains(pos))
+            .unwrap_or(self.tiles.len())
+    }
+
+    pub fn insert_hint_area(&self, idx: usize) -> Option<Rectangle<f64, Logical>> {
+        self.compute_rects(self.tiles.len() + 1).get(idx).copied()
+    }
+
+    pub fn refresh(&mut self, is_active: bool, is_focused: bool) {
+        for (idx, tile) in self.tiles.iter_mut().enumerate() {
+            let win = tile.window_mut();
+
+            win.set_active_in_column(true);
+            win.set_floating(false);
+            win.set_sticky(false);
+
+            let mut is_active = is_active && idx == self.active_tile_idx;
+            if self.options.deactivate_unfocused_windows {
+                is_active &= is_focused;
+            }
+            win.set_activated(is_active);
+
+            win.set_interactive_resize(None);
+
+            let border_config = self.options.layout.border.merged_with(&win.rules().border);
+            let bounds = compute_toplevel_bounds(
+                border_config,
+                self.working_area.size,
+                self.options.layout.gaps,
+            );
+            win.set_bounds(bounds);
+
+            // If transactions are disabled, also disable combined throttling, for more
+            // intuitive behavior.
+            let intent = if self.options.disable_resize_throttling {
+                ConfigureIntent::CanSend
+            } else {
+                win.configure_intent()
+            };
+
+            if matches!(
+                intent,
+                ConfigureIntent::CanSend | ConfigureIntent::ShouldSend
+            ) {
+                win.send_pending_configure();
+            }
+
+            win.refresh();
+        }
+    }
+
+    /// Computes the tile rectangles for the given number of tiles in the current mode.
+    fn compute_rects(&self, count: usize) -> Vec<Rectangle<f64, Logical>> {
+        let gaps = self.options.layout.gaps;
+
+        let mut area = self.working_area;
+        area.loc += Point::from((gaps, gaps));
+        area.size.w = f64::max(0., area.size.w - gaps * 2.);
+        area.size.h = f64::max(0., area.size.h - gaps * 2.);
+
+        let rects = match self.mode {
+            TilingMode::MasterStack => master_stack(area, count, gaps, self.split_ratio),
+            TilingMode::Dwindle => dwindle(area, count, gaps, self.split_ratio),
+        };
+
+        let scale = self.scale;
+        rects
+            .into_iter()
+            .map(|mut rect| {
+                // Round to physical pixels.
+                rect.loc.x = round_logical_in_physical(scale, rect.loc.x);
+                rect.loc.y = round_logical_in_physical(scale, rect.loc.y);
+                rect
+            })
+            .collect()
+    }
+
+    fn target_pos(&self, idx: usize, rect: Rectangle<f64, Logical>) -> Point<f64, Logical> {
+        let data = &self.data[idx];
+        if data.is_pending_fullscreen {
+            Point::from((0., 0.))
+        } else if data.is_pending_maximized {
+            self.parent_area.loc
+        } else {
+            rect.loc
+        }
+    }
+
+    /// Requests the tile sizes and moves the tiles to match the current tiling mode.
+    fn arrange(&mut self, animate: bool, transaction: Transaction) {
+        let rects = self.compute_rects(self.tiles.len());
+
+        for (idx, rect) in rects.into_iter().enumerate() {
+            let new_pos = self.target_pos(idx, rect);
+
+            let tile = &mut self.tiles[idx];
+            let data = &mut self.data[idx];
+
+            if data.is_pending_fullscreen {
+                tile.request_fullscreen(animate, Some(transaction.clone()));
+            } else if data.is_pending_maximized {
+                tile.request_maximized(self.parent_area.size, animate, Some(transaction.clone()));
+            } else {
+                tile.request_tile_size(rect.size, animate, Some(transaction.clone()));
+            }
+
+            let prev_pos = mem::replace(&mut data.pos, new_pos);
+            let diff = prev_pos - new_pos;
+            if animate && diff.x * diff.x + diff.y * diff.y > ANIMATION_THRESHOLD_SQ {
+                tile.animate_move_from(diff);
+            }
+        }
+    }
+
+    #[cfg(test)]
+    pub fn view_size(&self) -> Size<f64, Logical> {
+        self.view_size
+    }
+
+    #[cfg(test)]
+    pub fn parent_area(&self) -> Rectangle<f64, Logical> {
+        self.parent_area
+    }
+
+    #[cfg(test)]
+    pub fn clock(&self) -> &Clock {
+        &self.clock
+    }
+
+    #[cfg(test)]
+    pub fn options(&self) -> &Rc<Options> {
+        &self.options
+    }
+
+    #[cfg(test)]
+    pub fn verify_invariants(&self) {
+        use crate::layout::SizingMode;
+
+        assert!(self.scale > 0.);
+        assert!(self.scale.is_finite());
+        assert_eq!(self.tiles.len(), self.data.len());
+
+        assert!((MIN_SPLIT_RATIO..=MAX_SPLIT_RATIO).contains(&self.split_ratio));
+
+        if self.tiles.is_empty() {
+            assert_eq!(self.active_tile_idx, 0);
+        } else {
+            assert!(self.active_tile_idx < self.tiles.len());
+        }
+
+        let working_area =
+            compute_working_area(self.parent_area, self.scale, self.options.layout.struts);
+        assert_eq!(self.working_area, working_area);
+
+        let rects = self.compute_rects(self.tiles.len());
+        for (idx, (tile, data)) in zip(&self.tiles, &self.data).enumerate() {
+            assert!(Rc::ptr_eq(&self.options, &tile.options));
+            assert_eq!(self.view_size, tile.view_size());
+            assert_eq!(self.clock, tile.clock);
+            assert_eq!(self.scale, tile.scale());
+            tile.verify_invariants();
+
+            assert_eq!(
+                data.pos,
+                self.target_pos(idx, rects[idx]),
+                "tile positions must be up to date"
+            );
+
+            let expected_sizing_mode = if data.is_pending_fullscreen {
+                SizingMode::Fullscreen
+            } else if data.is_pending_maximized {
+                SizingMode::Maximized
+            } else {
+                SizingMode::Normal
+            };
+            assert_eq!(
+                tile.window().pending_sizing_mode(),
+                expected_sizing_mode,
+                "tile sizing mode must match the data"
+            );
+        }
+    }
+}
+
+/// Splits the area in two, leaving a gap in between.
+fn split(
+    area: Rectangle<f64, Logical>,
+    ratio: f64,
+    gap: f64,
+    horizontal: bool,
+) -> (Rectangle<f64, Logical>, Rectangle<f64, Logical>) {
+    if horizontal {
+        let available = f64::max(0., area.size.w - gap);
+        let width = available * ratio;
+
+        let first = Rectangle::new(area.loc, Size::from((width, area.size.h)));
+        let second = Rectangle::new(
+            Point::from((area.loc.x + width + gap, area.loc.y)),
+            Size::from((available - width, area.size.h)),
+        );
+        (first, second)
+    } else {
+        let available = f64::max(0., area.size.h - gap);
+        let height = available * ratio;
+
+        let first = Rectangle::new(area.loc, Size::from((area.size.w, height)));
+        let second = Rectangle::new(
+            Point::from((area.loc.x, area.loc.y + height + gap)),
+            Size::from((area.size.w, available - height)),
+        );
+        (first, second)
+    }
+}
+
+/// Master window on the left, the rest split evenly in a stack on the right.
+fn master_stack(
+    area: Rectangle<f64, Logical>,
+    count: usize,
+    gap: f64,
+    ratio: f64,
+) -> Vec<Rectangle<f64, Logical>> {
+    match count {
+        0 => Vec::new(),
+        1 => vec![area],
+        _ => {
+            let (master, stack) = split(area, ratio, gap, true);
+
+            let stack_count = count - 1;
+            let available = f64::max(0., stack.size.h - gap * (stack_count - 1) as f64);
+            let height = available / stack_count as f64;
+
+            let mut rects = Vec::with_capacity(count);
+            rects.push(master);
+            for i in 0..stack_count {
+                let y = stack.loc.y + (height + gap) * i as f64;
+                let loc = Point::from((stack.loc.x, y));
+                rects.push(Rectangle::new(loc, Size::from((stack.size.w, height))));
+            }
+            rects
+        }
+    }
+}
+
+/// Every window takes a part of the remaining area, alternating between horizontal and vertical
+/// splits. The last window takes the rest.
+fn dwindle(
+    mut area: Rectangle<f64, Logical>,
+    count: usize,
+    gap: f64,
+    ratio: f64,
+) -> Vec<Rectangle<f64, Logical>> {
+    let mut rects = Vec::with_capacity(count);
+    for i in 0..count {
+        if i + 1 == count {
+            rects.push(area);
+            break;
+        }
+
+        let (first, rest) = split(area, ratio, gap, i % 2 == 0);
+        rects.push(first);
+        area = rest;
+    }
+    rects
+}
+
+fn compute_toplevel_bounds(
+    border_config: niri_config::Border,
+    working_area_size: Size<f64, Logical>,
+    gaps: f64,
+) -> Size<i32, Logical> {
+    let mut border = 0.;
+    if !border_config.off {
+        border = border_config.width * 2.;
+    }
+
+    Size::from((
+        f64::max(working_area_size.w - gaps * 2. - border, 1.),
+        f64::max(working_area_size.h - gaps * 2. - border, 1.),
+    ))
+    .to_i32_floor()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: f64, y: f64, w: f64, h: f64) -> Rectangle<f64, Logical> {
+        Rectangle::new(Point::from((x, y)), Size::from((w, h)))
+    }
+
+    #[test]
+    fn master_stack_rects() {
+        let area = rect(0., 0., 1010., 500.);
+
+        assert_eq!(master_stack(area, 0, 10., 0.5), vec![]);
+        assert_eq!(master_stack(area, 1, 10., 0.5), vec![area]);
+        assert_eq!(
+            master_stack(area, 3, 10., 0.5),
+            vec![
+                rect(0., 0., 500., 500.),
+                rect(510., 0., 500., 245.),
+                rect(510., 255., 500., 245.),
+            ]
+        );
+    }
+
+    #[test]
+    fn dwindle_rects() {
+        let area = rect(0., 0., 1010., 510.);
+
+        assert_eq!(dwindle(area, 1, 10., 0.5), vec![area]);
+        assert_eq!(
+            dwindle(area, 3, 10., 0.5),
+            vec![
+                rect(0., 0., 500., 510.),
+                rect(510., 0., 500., 250.),
+                rect(510., 260., 500., 250.),
+            ]
+        );
+    }
+}
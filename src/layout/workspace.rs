@@ -1,4 +1,5 @@
 use std::cmp::max;
+use std::mem;
 use std::rc::Rc;
 use std::time::Duration;
 
@@ -6,7 +7,7 @@ use niri_config::utils::MergeWith as _;
 use niri_config::{
     CenterFocusedColumn, CornerRadius, OutputName, PresetSize, Workspace as WorkspaceConfig,
 };
-use niri_ipc::{ColumnDisplay, PositionChange, SizeChange, WindowLayout};
+use niri_ipc::{ColumnDisplay, LayoutMode, PositionChange, SizeChange, SnapZone, WindowLayout};
 use smithay::backend::renderer::element::Kind;
 use smithay::backend::renderer::gles::GlesRenderer;
 use smithay::desktop::{layer_map_for_output, Window};
@@ -23,6 +24,7 @@ use super::scrolling::{
 };
 use super::shadow::Shadow;
 use super::tile::{Tile, TileRenderSnapshot};
+use super::tiling::{TilingMode, TilingSpace, TilingSpaceRenderElement};
 use super::{
     ActivateWindow, HitType, InsertPosition, InteractiveResizeData, LayoutElement, Options,
     RemovedTile, SizeFrac,
@@ -48,12 +50,27 @@ pub struct Workspace<W: LayoutElement> {
     /// The scrollable-tiling layout.
     scrolling: ScrollingSpace<W>,
 
+    /// The layout used instead of the scrolling layout in the non-scrolling layout modes.
+    tiling: TilingSpace<W>,
+
+    /// Layout mode currently used for the tiled windows.
+    ///
+    /// When this is [`LayoutMode::Scrolling`], the tiled windows are in `scrolling`, otherwise
+    /// they are in `tiling`.
+    layout_mode: LayoutMode,
+
+    /// Layout mode set at runtime, overriding the one from the config.
+    layout_mode_override: Option<LayoutMode>,
+
     /// The floating layout.
     floating: FloatingSpace<W>,
 
     /// Whether the floating layout is active instead of the scrolling layout.
     floating_is_active: FloatingActive,
 
+    /// Windows minimized on this workspace, most recently minimized last.
+    minimized: Vec<RemovedTile<W>>,
+
     /// The original output of this workspace.
     ///
     /// Most of the time this will be the workspace's current output, however, after an output
@@ -146,6 +163,7 @@ impl WorkspaceId {
 niri_render_elements! {
     WorkspaceRenderElement<R> => {
         Scrolling = ScrollingSpaceRenderElement<R>,
+        Tiling = TilingSpaceRenderElement<R>,
         Floating = FloatingSpaceRenderElement<R>,
     }
 }
@@ -243,6 +261,18 @@ impl<W: LayoutElement> Workspace<W> {
             options.clone(),
         );
 
+        let mut tiling = TilingSpace::new(
+            view_size,
+            working_area,
+            scale.fractional_scale(),
+            clock.clone(),
+            options.clone(),
+        );
+        let layout_mode = options.layout.layout_mode;
+        if let Some(mode) = TilingMode::from_layout_mode(layout_mode) {
+            tiling.set_mode(mode);
+        }
+
         let floating = FloatingSpace::new(
             view_size,
             working_area,
@@ -256,6 +286,9 @@ impl<W: LayoutElement> Workspace<W> {
 
         Self {
             scrolling,
+            tiling,
+            layout_mode,
+            layout_mode_override: None,
             floating,
             floating_is_active: FloatingActive::No,
             original_output,
@@ -271,6 +304,7 @@ impl<W: LayoutElement> Workspace<W> {
             options,
             name: config.map(|c| c.name.0),
             layout_config,
+            minimized: Vec::new(),
             id: WorkspaceId::next(),
         }
     }
@@ -307,6 +341,18 @@ impl<W: LayoutElement> Workspace<W> {
             options.clone(),
         );
 
+        let mut tiling = TilingSpace::new(
+            view_size,
+            working_area,
+            scale.fractional_scale(),
+            clock.clone(),
+            options.clone(),
+        );
+        let layout_mode = options.layout.layout_mode;
+        if let Some(mode) = TilingMode::from_layout_mode(layout_mode) {
+            tiling.set_mode(mode);
+        }
+
         let floating = FloatingSpace::new(
             view_size,
             working_area,
@@ -320,6 +366,9 @@ impl<W: LayoutElement> Workspace<W> {
 
         Self {
             scrolling,
+            tiling,
+            layout_mode,
+            layout_mode_override: None,
             floating,
             floating_is_active: FloatingActive::No,
             output: None,
@@ -335,6 +384,7 @@ impl<W: LayoutElement> Workspace<W> {
             options,
             name: config.map(|c| c.name.0),
             layout_config,
+            minimized: Vec::new(),
             id: WorkspaceId::next(),
         }
     }
@@ -365,20 +415,27 @@ impl<W: LayoutElement> Workspace<W> {
 
     pub fn advance_animations(&mut self) {
         self.scrolling.advance_animations();
+        self.tiling.advance_animations();
         self.floating.advance_animations();
     }
 
     pub fn are_animations_ongoing(&self) -> bool {
-        self.scrolling.are_animations_ongoing() || self.floating.are_animations_ongoing()
+        self.scrolling.are_animations_ongoing()
+            || self.tiling.are_animations_ongoing()
+            || self.floating.are_animations_ongoing()
     }
 
     pub fn are_transitions_ongoing(&self) -> bool {
-        self.scrolling.are_transitions_ongoing() || self.floating.are_transitions_ongoing()
+        self.scrolling.are_transitions_ongoing()
+            || self.tiling.are_transitions_ongoing()
+            || self.floating.are_transitions_ongoing()
     }
 
     pub fn update_render_elements(&mut self, is_active: bool, layer: RenderLayer) {
         self.scrolling
             .update_render_elements(is_active && !self.floating_is_active.get(), layer);
+        self.tiling
+            .update_render_elements(is_active && !self.floating_is_active.get(), layer);
 
         let view_rect = Rectangle::from_size(self.view_size);
         self.floating.update_render_elements(
@@ -413,6 +470,13 @@ impl<W: LayoutElement> Workspace<W> {
             options.clone(),
         );
 
+        self.tiling.update_config(
+            self.view_size,
+            self.working_area,
+            self.scale.fractional_scale(),
+            options.clone(),
+        );
+
         self.floating.update_config(
             self.view_size,
             self.working_area,
@@ -420,6 +484,12 @@ impl<W: LayoutElement> Workspace<W> {
             options.clone(),
         );
 
+        for minimized in &mut self.minimized {
+            minimized
+                .tile
+                .update_config(self.view_size, scale, options.clone());
+        }
+
         let shadow_config =
             compute_workspace_shadow_config(options.overview.workspace_shadow, self.view_size);
         self.shadow.update_config(shadow_config);
@@ -427,8 +497,18 @@ impl<W: LayoutElement> Workspace<W> {
         self.background_buffer
             .set_color(options.layout.background_color);
 
+        let prev_layout_mode = self.options.layout.layout_mode;
+        let new_layout_mode = options.layout.layout_mode;
+
         self.base_options = base_options;
         self.options = options;
+
+        // A change in the config takes precedence over the runtime override.
+        if prev_layout_mode != new_layout_mode {
+            self.layout_mode_override = None;
+        }
+        let layout_mode = self.layout_mode_override.unwrap_or(new_layout_mode);
+        self.apply_layout_mode(layout_mode);
     }
 
     pub fn update_layout_config(&mut self, layout_config: Option<niri_config::LayoutPart>) {
@@ -442,6 +522,7 @@ impl<W: LayoutElement> Workspace<W> {
 
     pub fn update_shaders(&mut self) {
         self.scrolling.update_shaders();
+        self.tiling.update_shaders();
         self.floating.update_shaders();
         self.shadow.update_shaders();
     }
@@ -456,14 +537,16 @@ impl<W: LayoutElement> Workspace<W> {
 
     pub fn tiles(&self) -> impl Iterator<Item = &Tile<W>> + '_ {
         let scrolling = self.scrolling.tiles();
+        let tiling = self.tiling.tiles();
         let floating = self.floating.tiles();
-        scrolling.chain(floating)
+        scrolling.chain(tiling).chain(floating)
     }
 
     pub fn tiles_mut(&mut self) -> impl Iterator<Item = &mut Tile<W>> + '_ {
         let scrolling = self.scrolling.tiles_mut();
+        let tiling = self.tiling.tiles_mut();
         let floating = self.floating.tiles_mut();
-        scrolling.chain(floating)
+        scrolling.chain(tiling).chain(floating)
     }
 
     pub fn is_floating(&self, id: &W::Id) -> bool {
@@ -477,21 +560,143 @@ impl<W: LayoutElement> Workspace<W> {
     pub fn active_window(&self) -> Option<&W> {
         if self.floating_is_active.get() {
             self.floating.active_window()
-        } else {
+        } else if self.is_scrolling_layout() {
             self.scrolling.active_window()
+        } else {
+            self.tiling.active_window()
         }
     }
 
     pub fn active_window_mut(&mut self) -> Option<&mut W> {
         if self.floating_is_active.get() {
             self.floating.active_window_mut()
-        } else {
+        } else if self.is_scrolling_layout() {
             self.scrolling.active_window_mut()
+        } else {
+            self.tiling.active_window_mut()
         }
     }
 
     pub fn is_active_pending_fullscreen(&self) -> bool {
-        self.scrolling.is_active_pending_fullscreen()
+        if self.is_scrolling_layout() {
+            self.scrolling.is_active_pending_fullscreen()
+        } else {
+            self.tiling.is_active_pending_fullscreen()
+        }
+    }
+
+    /// Returns the layout mode currently used for the tiled windows.
+    pub fn layout_mode(&self) -> LayoutMode {
+        self.layout_mode
+    }
+
+    /// Returns whether the tiled windows are in the scrolling layout.
+    pub fn is_scrolling_layout(&self) -> bool {
+        self.layout_mode == LayoutMode::Scrolling
+    }
+
+    /// Returns whether the scrolling layout stacks columns top-to-bottom.
+    ///
+    /// Directional focus and move actions follow the screen, so with vertical orientation left
+    /// and right move within a column, while up and down move between columns.
+    pub fn is_scrolling_vertical(&self) -> bool {
+        self.scrolling.is_vertical()
+    }
+
+    /// Returns whether there are no tiled (non-floating) windows.
+    fn tiled_is_empty(&self) -> bool {
+        self.scrolling.is_empty() && self.tiling.is_empty()
+    }
+
+    /// Sets the layout mode for this workspace, overriding the configured one.
+    pub fn set_layout_mode(&mut self, mode: LayoutMode) {
+        self.layout_mode_override = Some(mode);
+        self.apply_layout_mode(mode);
+    }
+
+    /// Rearranges the tiled windows into the given layout mode.
+    fn apply_layout_mode(&mut self, mode: LayoutMode) {
+        if self.layout_mode == mode {
+            return;
+        }
+
+        let prev_mode = mem::replace(&mut self.layout_mode, mode);
+
+        let Some(tiling_mode) = TilingMode::from_layout_mode(mode) else {
+            // Moving from the tiling space into the scrolling space.
+            self.move_tiles_from_tiling_to_scrolling();
+            return;
+        };
+
+        if prev_mode == LayoutMode::Scrolling {
+            // Moving from the scrolling space into the tiling space.
+            self.tiling.set_mode(tiling_mode);
+            self.move_tiles_from_scrolling_to_tiling();
+        } else {
+            // Switching between tiling algorithms.
+            self.tiling.set_mode(tiling_mode);
+        }
+    }
+
+    fn move_tiles_from_scrolling_to_tiling(&mut self) {
+        let active_id = self.scrolling.active_window().map(|win| win.id().clone());
+        let prev_positions: Vec<_> = self
+            .scrolling
+            .tiles_with_render_positions()
+            .map(|(tile, pos, _visible)| (tile.window().id().clone(), pos))
+            .collect();
+        let ids: Vec<_> = self
+            .scrolling
+            .tiles()
+            .map(|tile| tile.window().id().clone())
+            .collect();
+
+        for id in ids {
+            let mut removed = self.scrolling.remove_tile(&id, Transaction::new());
+            removed.tile.stop_move_animations();
+
+            let activate = active_id.as_ref() == Some(&id);
+            self.tiling
+                .add_tile(Some(usize::MAX), removed.tile, activate);
+        }
+
+        for (id, prev_pos) in prev_positions {
+            let (tile, pos) = self
+                .tiling
+                .tiles_with_render_positions_mut(false)
+                .find(|(tile, _)| *tile.window().id() == id)
+                .unwrap();
+            tile.animate_move_from(prev_pos - pos);
+        }
+    }
+
+    fn move_tiles_from_tiling_to_scrolling(&mut self) {
+        let active_id = self.tiling.active_window().map(|win| win.id().clone());
+        let prev_positions: Vec<_> = self
+            .tiling
+            .tiles_with_render_positions()
+            .map(|(tile, pos)| (tile.window().id().clone(), pos))
+            .collect();
+
+        for mut tile in self.tiling.take_tiles() {
+            tile.stop_move_animations();
+
+            let activate = active_id.as_ref() == Some(tile.window().id());
+            let col_idx = self.scrolling.columns().count();
+            let width = self
+                .resolve_scrolling_width(tile.window(), self.options.layout.default_column_width);
+            self.scrolling
+                .add_tile(Some(col_idx), tile, activate, width, false, None);
+        }
+
+        for (id, prev_pos) in prev_positions {
+            let (tile, pos) = self
+                .scrolling
+                .tiles_with_render_positions_mut(false)
+                .find(|(tile, _)| *tile.window().id() == id)
+                .unwrap();
+            tile.animate_move_from(prev_pos - pos);
+        }
     }
 
     pub fn set_output(&mut self, output: Option<Output>) {
@@ -569,12 +774,23 @@ impl<W: LayoutElement> Workspace<W> {
                 scale.fractional_scale(),
                 self.options.clone(),
             );
+            self.tiling.update_config(
+                size,
+                working_area,
+                scale.fractional_scale(),
+                self.options.clone(),
+            );
             self.floating.update_config(
                 size,
                 working_area,
                 scale.fractional_scale(),
                 self.options.clone(),
             );
+            for minimized in &mut self.minimized {
+                minimized
+                    .tile
+                    .update_config(size, scale.fractional_scale(), self.options.clone());
+            }
 
             let shadow_config =
                 compute_workspace_shadow_config(self.options.overview.workspace_shadow, size);
@@ -584,7 +800,10 @@ impl<W: LayoutElement> Workspace<W> {
         self.background_buffer.resize(size);
 
         if scale_transform_changed {
-            for window in self.windows() {
+            for window in self
+                .windows()
+                .chain(self.minimized_tiles().map(Tile::window))
+            {
                 window.set_preferred_scale_transform(self.scale, self.transform);
             }
         }
@@ -628,12 +847,16 @@ impl<W: LayoutElement> Workspace<W> {
                 if is_floating && tile.window().pending_sizing_mode().is_normal() {
                     self.floating.add_tile(tile, activate);
 
-                    if activate || self.scrolling.is_empty() {
+                    if activate || self.tiled_is_empty() {
                         self.floating_is_active = FloatingActive::Yes;
                     }
                 } else {
-                    self.scrolling
-                        .add_tile(None, tile, activate, width, is_full_width, anim);
+                    if self.is_scrolling_layout() {
+                        self.scrolling
+                            .add_tile(None, tile, activate, width, is_full_width, anim);
+                    } else {
+                        self.tiling.add_tile(None, tile, activate);
+                    }
 
                     if activate {
                         self.floating_is_active = FloatingActive::No;
@@ -642,8 +865,18 @@ impl<W: LayoutElement> Workspace<W> {
             }
             WorkspaceAddWindowTarget::NewColumnAt(col_idx) => {
                 let activate = activate.map_smart(|| false);
-                self.scrolling
-                    .add_tile(Some(col_idx), tile, activate, width, is_full_width, anim);
+                if self.is_scrolling_layout() {
+                    self.scrolling.add_tile(
+                        Some(col_idx),
+                        tile,
+                        activate,
+                        width,
+                        is_full_width,
+                        anim,
+                    );
+                } else {
+                    self.tiling.add_tile(Some(col_idx), tile, activate);
+                }
 
                 if activate {
                     self.floating_is_active = FloatingActive::No;
@@ -659,11 +892,18 @@ impl<W: LayoutElement> Workspace<W> {
                         self.floating.add_tile_above(next_to, tile, activate);
                     } else {
                         // FIXME: use static pos
-                        let (next_to_tile, render_pos, _visible) = self
-                            .scrolling
-                            .tiles_with_render_positions()
-                            .find(|(tile, _, _)| tile.window().id() == next_to)
-                            .unwrap();
+                        let (next_to_tile, render_pos) = if self.is_scrolling_layout() {
+                            self.scrolling
+                                .tiles_with_render_positions()
+                                .find(|(tile, _, _)| tile.window().id() == next_to)
+                                .map(|(tile, pos, _visible)| (tile, pos))
+                                .unwrap()
+                        } else {
+                            self.tiling
+                                .tiles_with_render_positions()
+                                .find(|(tile, _)| tile.window().id() == next_to)
+                                .unwrap()
+                        };
 
                         // Position the new tile in the center above the next_to tile. Think a
                         // dialog opening on top of a window.
@@ -678,9 +918,19 @@ impl<W: LayoutElement> Workspace<W> {
                         self.floating.add_tile(tile, activate);
                     }
 
-                    if activate || self.scrolling.is_empty() {
+                    if activate || self.tiled_is_empty() {
                         self.floating_is_active = FloatingActive::Yes;
                     }
+                } else if !self.is_scrolling_layout() {
+                    if floating_has_window {
+                        self.tiling.add_tile(None, tile, activate);
+                    } else {
+                        self.tiling.add_tile_after(next_to, tile, activate);
+                    }
+
+                    if activate {
+                        self.floating_is_active = FloatingActive::No;
+                    }
                 } else if floating_has_window {
                     self.scrolling
                         .add_tile(None, tile, activate, width, is_full_width, anim);
@@ -714,8 +964,12 @@ impl<W: LayoutElement> Workspace<W> {
         activate: bool,
     ) {
         self.enter_output_for_window(tile.window());
-        self.scrolling
-            .add_tile_to_column(col_idx, tile_idx, tile, activate);
+        if self.is_scrolling_layout() {
+            self.scrolling
+                .add_tile_to_column(col_idx, tile_idx, tile, activate);
+        } else {
+            self.tiling.add_tile(Some(col_idx), tile, activate);
+        }
 
         if activate {
             self.floating_is_active = FloatingActive::No;
@@ -732,7 +986,15 @@ impl<W: LayoutElement> Workspace<W> {
             self.enter_output_for_window(tile.window());
         }
 
-        self.scrolling.add_column(None, column, activate, anim);
+        if self.is_scrolling_layout() {
+            self.scrolling.add_column(None, column, activate, anim);
+        } else {
+            let (tiles, active_idx) = column.into_tiles();
+            for (idx, tile) in tiles.into_iter().enumerate() {
+                self.tiling
+                    .add_tile(None, tile, activate && idx == active_idx);
+            }
+        }
 
         if activate {
             self.floating_is_active = FloatingActive::No;
@@ -746,7 +1008,7 @@ impl<W: LayoutElement> Workspace<W> {
             }
         } else {
             // Scrolling should remain focused if both are empty.
-            if self.scrolling.is_empty() && !self.floating.is_empty() {
+            if self.tiled_is_empty() && !self.floating.is_empty() {
                 self.floating_is_active = FloatingActive::Yes;
             }
         }
@@ -757,6 +1019,8 @@ impl<W: LayoutElement> Workspace<W> {
         let removed = if self.floating.has_window(id) {
             from_floating = true;
             self.floating.remove_tile(id)
+        } else if self.tiling.has_window(id) {
+            self.tiling.remove_tile(id, transaction)
         } else {
             self.scrolling.remove_tile(id, transaction)
         };
@@ -770,9 +1034,24 @@ impl<W: LayoutElement> Workspace<W> {
         removed
     }
 
+    /// Puts a tile in place of a window in the scrolling layout.
+    ///
+    /// Returns the replaced tile.
+    pub fn replace_scrolling_tile(&mut self, id: &W::Id, tile: Tile<W>) -> Tile<W> {
+        self.enter_output_for_window(tile.window());
+
+        let old = self.scrolling.replace_tile(id, tile);
+
+        if let Some(output) = &self.output {
+            old.window().output_leave(output);
+        }
+
+        old
+    }
+
     pub fn remove_active_column(&mut self) -> Option<Column<W>> {
         let from_floating = self.floating_is_active.get();
-        if from_floating {
+        if from_floating || !self.is_scrolling_layout() {
             return None;
         }
 
@@ -826,8 +1105,10 @@ impl<W: LayoutElement> Workspace<W> {
     ) -> Size<i32, Logical> {
         let mut size = if is_floating {
             self.floating.new_window_size(width, height, rules)
-        } else {
+        } else if self.is_scrolling_layout() {
             self.scrolling.new_window_size(width, height, rules)
+        } else {
+            self.tiling.new_window_size(rules)
         };
 
         // If the window has a fixed size, or we're picking some fixed size, apply min and max
@@ -878,8 +1159,10 @@ impl<W: LayoutElement> Workspace<W> {
 
             if is_floating {
                 state.bounds = Some(self.floating.new_window_toplevel_bounds(rules));
-            } else {
+            } else if self.is_scrolling_layout() {
                 state.bounds = Some(self.scrolling.new_window_toplevel_bounds(rules));
+            } else {
+                state.bounds = Some(self.tiling.new_window_toplevel_bounds(rules));
             }
         });
     }
@@ -910,32 +1193,48 @@ impl<W: LayoutElement> Workspace<W> {
     pub fn focus_left(&mut self) -> bool {
         if self.floating_is_active.get() {
             self.floating.focus_left()
+        } else if self.is_scrolling_layout() {
+            if self.is_scrolling_vertical() {
+                self.scrolling.focus_up()
+            } else {
+                self.scrolling.focus_left()
+            }
         } else {
-            self.scrolling.focus_left()
+            self.tiling.focus_left()
         }
     }
 
     pub fn focus_right(&mut self) -> bool {
         if self.floating_is_active.get() {
             self.floating.focus_right()
+        } else if self.is_scrolling_layout() {
+            if self.is_scrolling_vertical() {
+                self.scrolling.focus_down()
+            } else {
+                self.scrolling.focus_right()
+            }
         } else {
-            self.scrolling.focus_right()
+            self.tiling.focus_right()
         }
     }
 
     pub fn focus_column_first(&mut self) {
         if self.floating_is_active.get() {
             self.floating.focus_leftmost();
-        } else {
+        } else if self.is_scrolling_layout() {
             self.scrolling.focus_column_first();
+        } else {
+            self.tiling.focus_first();
         }
     }
 
     pub fn focus_column_last(&mut self) {
         if self.floating_is_active.get() {
             self.floating.focus_rightmost();
-        } else {
+        } else if self.is_scrolling_layout() {
             self.scrolling.focus_column_last();
+        } else {
+            self.tiling.focus_last();
         }
     }
 
@@ -955,11 +1254,15 @@ impl<W: LayoutElement> Workspace<W> {
         if self.floating_is_active.get() {
             self.focus_tiling();
         }
-        self.scrolling.focus_column(index);
+        if self.is_scrolling_layout() {
+            self.scrolling.focus_column(index);
+        } else {
+            self.tiling.focus_index(index);
+        }
     }
 
     pub fn focus_window_in_column(&mut self, index: u8) {
-        if self.floating_is_active.get() {
+        if self.floating_is_active.get() || !self.is_scrolling_layout() {
             return;
         }
         self.scrolling.focus_window_in_column(index);
@@ -968,64 +1271,88 @@ impl<W: LayoutElement> Workspace<W> {
     pub fn focus_down(&mut self) -> bool {
         if self.floating_is_active.get() {
             self.floating.focus_down()
+        } else if self.is_scrolling_layout() {
+            if self.is_scrolling_vertical() {
+                self.scrolling.focus_right()
+            } else {
+                self.scrolling.focus_down()
+            }
         } else {
-            self.scrolling.focus_down()
+            self.tiling.focus_down()
         }
     }
 
     pub fn focus_up(&mut self) -> bool {
         if self.floating_is_active.get() {
             self.floating.focus_up()
+        } else if self.is_scrolling_layout() {
+            if self.is_scrolling_vertical() {
+                self.scrolling.focus_left()
+            } else {
+                self.scrolling.focus_up()
+            }
         } else {
-            self.scrolling.focus_up()
+            self.tiling.focus_up()
         }
     }
 
     pub fn focus_down_or_left(&mut self) {
         if self.floating_is_active.get() {
             self.floating.focus_down();
-        } else {
+        } else if self.is_scrolling_layout() {
             self.scrolling.focus_down_or_left();
+        } else {
+            self.tiling.focus_down();
         }
     }
 
     pub fn focus_down_or_right(&mut self) {
         if self.floating_is_active.get() {
             self.floating.focus_down();
-        } else {
+        } else if self.is_scrolling_layout() {
             self.scrolling.focus_down_or_right();
+        } else {
+            self.tiling.focus_down();
         }
     }
 
     pub fn focus_up_or_left(&mut self) {
         if self.floating_is_active.get() {
             self.floating.focus_up();
-        } else {
+        } else if self.is_scrolling_layout() {
             self.scrolling.focus_up_or_left();
+        } else {
+            self.tiling.focus_up();
         }
     }
 
     pub fn focus_up_or_right(&mut self) {
         if self.floating_is_active.get() {
             self.floating.focus_up();
-        } else {
+        } else if self.is_scrolling_layout() {
             self.scrolling.focus_up_or_right();
+        } else {
+            self.tiling.focus_up();
         }
     }
 
     pub fn focus_window_top(&mut self) {
         if self.floating_is_active.get() {
             self.floating.focus_topmost();
-        } else {
+        } else if self.is_scrolling_layout() {
             self.scrolling.focus_top();
+        } else {
+            self.tiling.focus_topmost();
         }
     }
 
     pub fn focus_window_bottom(&mut self) {
         if self.floating_is_active.get() {
             self.floating.focus_bottommost();
-        } else {
+        } else if self.is_scrolling_layout() {
             self.scrolling.focus_bottom();
+        } else {
+            self.tiling.focus_bottommost();
         }
     }
 
@@ -1045,8 +1372,14 @@ impl<W: LayoutElement> Workspace<W> {
         if self.floating_is_active.get() {
             self.floating.move_left();
             true
+        } else if self.is_scrolling_layout() {
+            if self.is_scrolling_vertical() {
+                self.scrolling.move_up()
+            } else {
+                self.scrolling.move_left()
+            }
         } else {
-            self.scrolling.move_left()
+            self.tiling.move_left()
         }
     }
 
@@ -1054,8 +1387,14 @@ impl<W: LayoutElement> Workspace<W> {
         if self.floating_is_active.get() {
             self.floating.move_right();
             true
+        } else if self.is_scrolling_layout() {
+            if self.is_scrolling_vertical() {
+                self.scrolling.move_down()
+            } else {
+                self.scrolling.move_right()
+            }
         } else {
-            self.scrolling.move_right()
+            self.tiling.move_right()
         }
     }
 
@@ -1063,29 +1402,47 @@ impl<W: LayoutElement> Workspace<W> {
         if self.floating_is_active.get() {
             return;
         }
-        self.scrolling.move_column_to_first();
+        if self.is_scrolling_layout() {
+            self.scrolling.move_column_to_first();
+        } else {
+            self.tiling.move_to_first();
+        }
     }
 
     pub fn move_column_to_last(&mut self) {
         if self.floating_is_active.get() {
             return;
         }
-        self.scrolling.move_column_to_last();
+        if self.is_scrolling_layout() {
+            self.scrolling.move_column_to_last();
+        } else {
+            self.tiling.move_to_last();
+        }
     }
 
     pub fn move_column_to_index(&mut self, index: usize) {
         if self.floating_is_active.get() {
             return;
         }
-        self.scrolling.move_column_to_index(index);
+        if self.is_scrolling_layout() {
+            self.scrolling.move_column_to_index(index);
+        } else {
+            self.tiling.move_to_index(index);
+        }
     }
 
     pub fn move_down(&mut self) -> bool {
         if self.floating_is_active.get() {
             self.floating.move_down();
             true
+        } else if self.is_scrolling_layout() {
+            if self.is_scrolling_vertical() {
+                self.scrolling.move_right()
+            } else {
+                self.scrolling.move_down()
+            }
         } else {
-            self.scrolling.move_down()
+            self.tiling.move_down()
         }
     }
 
@@ -1093,8 +1450,14 @@ impl<W: LayoutElement> Workspace<W> {
         if self.floating_is_active.get() {
             self.floating.move_up();
             true
+        } else if self.is_scrolling_layout() {
+            if self.is_scrolling_vertical() {
+                self.scrolling.move_left()
+            } else {
+                self.scrolling.move_up()
+            }
         } else {
-            self.scrolling.move_up()
+            self.tiling.move_up()
         }
     }
 
@@ -1104,6 +1467,9 @@ impl<W: LayoutElement> Workspace<W> {
         }) {
             return;
         }
+        if !self.is_scrolling_layout() {
+            return;
+        }
         self.scrolling.consume_or_expel_window_left(window);
     }
 
@@ -1113,39 +1479,42 @@ impl<W: LayoutElement> Workspace<W> {
         }) {
             return;
         }
+        if !self.is_scrolling_layout() {
+            return;
+        }
         self.scrolling.consume_or_expel_window_right(window);
     }
 
     pub fn consume_into_column(&mut self) {
-        if self.floating_is_active.get() {
+        if self.floating_is_active.get() || !self.is_scrolling_layout() {
             return;
         }
         self.scrolling.consume_into_column();
     }
 
     pub fn expel_from_column(&mut self) {
-        if self.floating_is_active.get() {
+        if self.floating_is_active.get() || !self.is_scrolling_layout() {
             return;
         }
         self.scrolling.expel_from_column();
     }
 
     pub fn swap_window_in_direction(&mut self, direction: ScrollDirection) {
-        if self.floating_is_active.get() {
+        if self.floating_is_active.get() || !self.is_scrolling_layout() {
             return;
         }
         self.scrolling.swap_window_in_direction(direction);
     }
 
     pub fn toggle_column_tabbed_display(&mut self) {
-        if self.floating_is_active.get() {
+        if self.floating_is_active.get() || !self.is_scrolling_layout() {
             return;
         }
         self.scrolling.toggle_column_tabbed_display();
     }
 
     pub fn set_column_display(&mut self, display: ColumnDisplay) {
-        if self.floating_is_active.get() {
+        if self.floating_is_active.get() || !self.is_scrolling_layout() {
             return;
         }
         self.scrolling.set_column_display(display);
@@ -1154,7 +1523,7 @@ impl<W: LayoutElement> Workspace<W> {
     pub fn center_column(&mut self) {
         if self.floating_is_active.get() {
             self.floating.center_window(None);
-        } else {
+        } else if self.is_scrolling_layout() {
             self.scrolling.center_column();
         }
     }
@@ -1164,13 +1533,13 @@ impl<W: LayoutElement> Workspace<W> {
             self.floating.has_window(id)
         }) {
             self.floating.center_window(id);
-        } else {
+        } else if self.is_scrolling_layout() {
             self.scrolling.center_window(id);
         }
     }
 
     pub fn center_visible_columns(&mut self) {
-        if self.floating_is_active.get() {
+        if self.floating_is_active.get() || !self.is_scrolling_layout() {
             return;
         }
         self.scrolling.center_visible_columns();
@@ -1179,8 +1548,10 @@ impl<W: LayoutElement> Workspace<W> {
     pub fn toggle_width(&mut self, forwards: bool) {
         if self.floating_is_active.get() {
             self.floating.toggle_window_width(None, forwards);
-        } else {
+        } else if self.is_scrolling_layout() {
             self.scrolling.toggle_width(forwards);
+        } else {
+            self.tiling.toggle_split_ratio(forwards);
         }
     }
 
@@ -1190,14 +1561,19 @@ impl<W: LayoutElement> Workspace<W> {
             // to be against the left edge of the working area while it is full-width.
             return;
         }
+        if !self.is_scrolling_layout() {
+            return;
+        }
         self.scrolling.toggle_full_width();
     }
 
     pub fn set_column_width(&mut self, change: SizeChange) {
         if self.floating_is_active.get() {
             self.floating.set_window_width(None, change, true);
-        } else {
+        } else if self.is_scrolling_layout() {
             self.scrolling.set_window_width(None, change);
+        } else {
+            self.tiling.set_split_ratio(change);
         }
     }
 
@@ -1206,8 +1582,10 @@ impl<W: LayoutElement> Workspace<W> {
             self.floating.has_window(id)
         }) {
             self.floating.set_window_width(window, change, true);
-        } else {
+        } else if self.is_scrolling_layout() {
             self.scrolling.set_window_width(window, change);
+        } else {
+            self.tiling.set_split_ratio(change);
         }
     }
 
@@ -1216,7 +1594,7 @@ impl<W: LayoutElement> Workspace<W> {
             self.floating.has_window(id)
         }) {
             self.floating.set_window_height(window, change, true);
-        } else {
+        } else if self.is_scrolling_layout() {
             self.scrolling.set_window_height(window, change);
         }
     }
@@ -1227,6 +1605,9 @@ impl<W: LayoutElement> Workspace<W> {
         }) {
             return;
         }
+        if !self.is_scrolling_layout() {
+            return;
+        }
         self.scrolling.reset_window_height(window);
     }
 
@@ -1235,8 +1616,10 @@ impl<W: LayoutElement> Workspace<W> {
             self.floating.has_window(id)
         }) {
             self.floating.toggle_window_width(window, forwards);
-        } else {
+        } else if self.is_scrolling_layout() {
             self.scrolling.toggle_window_width(window, forwards);
+        } else {
+            self.tiling.toggle_split_ratio(forwards);
         }
     }
 
@@ -1245,13 +1628,13 @@ impl<W: LayoutElement> Workspace<W> {
             self.floating.has_window(id)
         }) {
             self.floating.toggle_window_height(window, forwards);
-        } else {
+        } else if self.is_scrolling_layout() {
             self.scrolling.toggle_window_height(window, forwards);
         }
     }
 
     pub fn expand_column_to_available_width(&mut self) {
-        if self.floating_is_active.get() {
+        if self.floating_is_active.get() || !self.is_scrolling_layout() {
             return;
         }
         self.scrolling.expand_column_to_available_width();
@@ -1272,17 +1655,25 @@ impl<W: LayoutElement> Workspace<W> {
             // The window is in the scrolling layout and we're requesting an unfullscreen. If it is
             // indeed fullscreen (i.e. this isn't a duplicate unfullscreen request), then we may
             // need to unfullscreen into floating.
-            let col = self
-                .scrolling
-                .columns()
-                .find(|col| col.contains(window))
-                .unwrap();
+            let (is_pending_fullscreen, is_pending_maximized) = if self.is_scrolling_layout() {
+                let col = self
+                    .scrolling
+                    .columns()
+                    .find(|col| col.contains(window))
+                    .unwrap();
+                (col.is_pending_fullscreen(), col.is_pending_maximized())
+            } else {
+                (
+                    self.tiling.is_pending_fullscreen(window),
+                    self.tiling.is_pending_maximized(window),
+                )
+            };
 
             // When going from fullscreen to maximized, don't consider restore_to_floating yet.
-            if col.is_pending_fullscreen() && !col.is_pending_maximized() {
-                let (tile, _) = col
+            if is_pending_fullscreen && !is_pending_maximized {
+                let tile = self
                     .tiles()
-                    .find(|(tile, _)| tile.window().id() == window)
+                    .find(|tile| tile.window().id() == window)
                     .unwrap();
                 if tile.restore_to_floating {
                     // Unfullscreen and float in one call so it has a chance to notice and request a
@@ -1294,17 +1685,19 @@ impl<W: LayoutElement> Workspace<W> {
         }
 
         let tile = self
-            .scrolling
             .tiles()
             .find(|tile| tile.window().id() == window)
             .unwrap();
         let was_normal = tile.window().pending_sizing_mode().is_normal();
 
-        self.scrolling.set_fullscreen(window, is_fullscreen);
+        if self.is_scrolling_layout() {
+            self.scrolling.set_fullscreen(window, is_fullscreen);
+        } else {
+            self.tiling.set_fullscreen(window, is_fullscreen);
+        }
 
         // When going from normal to fullscreen, remember if we should unfullscreen to floating.
         let tile = self
-            .scrolling
             .tiles_mut()
             .find(|tile| tile.window().id() == window)
             .unwrap();
@@ -1338,7 +1731,6 @@ impl<W: LayoutElement> Workspace<W> {
             // indeed maximized (i.e. this isn't a duplicate unmaximize request), then we may
             // need to unmaximize into floating.
             let tile = self
-                .scrolling
                 .tiles()
                 .find(|tile| tile.window().id() == window)
                 .unwrap();
@@ -1353,17 +1745,19 @@ impl<W: LayoutElement> Workspace<W> {
         }
 
         let tile = self
-            .scrolling
             .tiles()
             .find(|tile| tile.window().id() == window)
             .unwrap();
         let was_normal = tile.window().pending_sizing_mode().is_normal();
 
-        self.scrolling.set_maximized(window, maximize);
+        if self.is_scrolling_layout() {
+            self.scrolling.set_maximized(window, maximize);
+        } else {
+            self.tiling.set_maximized(window, maximize);
+        }
 
         // When going from normal to maximized, remember if we should unmaximize to floating.
         let tile = self
-            .scrolling
             .tiles_mut()
             .find(|tile| tile.window().id() == window)
             .unwrap();
@@ -1382,6 +1776,8 @@ impl<W: LayoutElement> Workspace<W> {
         // In the floating layout, windows cannot be maximized.
         if let Some(col) = self.scrolling.columns().find(|col| col.contains(window)) {
             current = col.is_pending_maximized();
+        } else if self.tiling.has_window(window) {
+            current = self.tiling.is_pending_maximized(window);
         }
 
         self.set_maximized(window, !current);
@@ -1400,21 +1796,31 @@ impl<W: LayoutElement> Workspace<W> {
             .unwrap();
 
         if self.floating.has_window(&id) {
-            let removed = self.floating.remove_tile(&id);
+            let mut removed = self.floating.remove_tile(&id);
+            // Sticky windows only work in the floating layout.
+            removed.tile.is_sticky = false;
             // FIXME: compute closest pos?
-            self.scrolling.add_tile(
-                None,
-                removed.tile,
-                target_is_active,
-                removed.width,
-                removed.is_full_width,
-                None,
-            );
+            if self.is_scrolling_layout() {
+                self.scrolling.add_tile(
+                    None,
+                    removed.tile,
+                    target_is_active,
+                    removed.width,
+                    removed.is_full_width,
+                    None,
+                );
+            } else {
+                self.tiling.add_tile(None, removed.tile, target_is_active);
+            }
             if target_is_active {
                 self.floating_is_active = FloatingActive::No;
             }
         } else {
-            let mut removed = self.scrolling.remove_tile(&id, Transaction::new());
+            let mut removed = if self.is_scrolling_layout() {
+                self.scrolling.remove_tile(&id, Transaction::new())
+            } else {
+                self.tiling.remove_tile(&id, Transaction::new())
+            };
             removed.tile.stop_move_animations();
 
             // Come up with a default floating position close to the tile position.
@@ -1458,6 +1864,110 @@ impl<W: LayoutElement> Workspace<W> {
         self.toggle_window_floating(id);
     }
 
+    pub fn toggle_window_sticky(&mut self, id: Option<&W::Id>) {
+        let Some(id) = id
+            .cloned()
+            .or_else(|| self.active_window().map(|win| win.id().clone()))
+        else {
+            return;
+        };
+
+        let tile = self
+            .tiles_mut()
+            .find(|tile| *tile.window().id() == id)
+            .unwrap();
+        tile.is_sticky = !tile.is_sticky;
+
+        // Sticky windows only work in the floating layout.
+        if tile.is_sticky {
+            self.set_window_floating(Some(&id), true);
+        }
+    }
+
+    pub fn is_sticky(&self, id: &W::Id) -> bool {
+        self.floating
+            .tiles()
+            .any(|tile| tile.window().id() == id && tile.is_sticky)
+    }
+
+    /// Returns the ids of sticky windows on this workspace.
+    pub fn sticky_windows(&self) -> impl Iterator<Item = &W::Id> + '_ {
+        self.floating
+            .tiles()
+            .filter(|tile| tile.is_sticky)
+            .map(|tile| tile.window().id())
+    }
+
+    pub fn has_minimized_window(&self, id: &W::Id) -> bool {
+        self.minimized
+            .iter()
+            .any(|minimized| minimized.tile.window().id() == id)
+    }
+
+    /// Returns the minimized tiles, most recently minimized last.
+    pub fn minimized_tiles(&self) -> impl Iterator<Item = &Tile<W>> + '_ {
+        self.minimized.iter().map(|minimized| &minimized.tile)
+    }
+
+    pub fn minimized_tiles_mut(&mut self) -> impl Iterator<Item = &mut Tile<W>> + '_ {
+        self.minimized
+            .iter_mut()
+            .map(|minimized| &mut minimized.tile)
+    }
+
+    /// Takes a window out of the layout and into the minimized list.
+    pub fn minimize_window(&mut self, id: &W::Id) {
+        let mut removed = self.remove_tile(id, Transaction::new());
+        removed.tile.window_mut().set_minimized(true);
+        removed.tile.window_mut().set_activated(false);
+        self.minimized.push(removed);
+    }
+
+    /// Puts a minimized window back into the layout and activates it.
+    ///
+    /// Without an id, restores the most recently minimized window. Returns whether a window was
+    /// restored.
+    pub fn restore_minimized_window(&mut self, id: Option<&W::Id>) -> bool {
+        let idx = match id {
+            Some(id) => self
+                .minimized
+                .iter()
+                .position(|minimized| minimized.tile.window().id() == id),
+            None => self.minimized.len().checked_sub(1),
+        };
+        let Some(idx) = idx else {
+            return false;
+        };
+
+        let RemovedTile {
+            mut tile,
+            width,
+            is_full_width,
+            is_floating,
+        } = self.minimized.remove(idx);
+        tile.window_mut().set_minimized(false);
+
+        self.add_tile(
+            tile,
+            WorkspaceAddWindowTarget::Auto,
+            ActivateWindow::Yes,
+            width,
+            is_full_width,
+            is_floating,
+            None,
+        );
+        true
+    }
+
+    /// Removes a minimized window, forgetting about it.
+    pub fn remove_minimized_window(&mut self, id: &W::Id) -> Option<RemovedTile<W>> {
+        let idx = self
+            .minimized
+            .iter()
+            .position(|minimized| minimized.tile.window().id() == id)?;
+        Some(self.minimized.remove(idx))
+    }
+
     pub fn focus_floating(&mut self) {
         if !self.floating_is_active.get() {
             self.switch_focus_floating_tiling();
@@ -1474,7 +1984,7 @@ impl<W: LayoutElement> Workspace<W> {
         if self.floating.is_empty() {
             // If floating is empty, keep focus on scrolling.
             return;
-        } else if self.scrolling.is_empty() {
+        } else if self.tiled_is_empty() {
             // If floating isn't empty but scrolling is, keep focus on floating.
             return;
         }
@@ -1486,6 +1996,14 @@ impl<W: LayoutElement> Workspace<W> {
         };
     }
 
+    pub fn snap_floating_window(&mut self, id: Option<&W::Id>, zone: SnapZone, animate: bool) {
+        if id.map_or(self.floating_is_active.get(), |id| {
+            self.floating.has_window(id)
+        }) {
+            self.floating.snap_window_to_zone(id, zone, animate);
+        }
+    }
+
     pub fn move_floating_window(
         &mut self,
         id: Option<&W::Id>,
@@ -1502,12 +2020,19 @@ impl<W: LayoutElement> Workspace<W> {
             let tile = if let Some(id) = id {
                 self.scrolling
                     .tiles_mut()
+                    .chain(self.tiling.tiles_mut())
                     .find(|tile| tile.window().id() == id)
                     .unwrap()
-            } else if let Some(tile) = self.scrolling.active_tile_mut() {
+            } else if self.is_scrolling_layout() {
+                let Some(tile) = self.scrolling.active_tile_mut() else {
+                    return;
+                };
                 tile
             } else {
-                return;
+                let Some(tile) = self.tiling.active_tile_mut() else {
+                    return;
+                };
+                tile
             };
 
             let pos = self.floating.stored_or_default_tile_pos(tile);
@@ -1568,8 +2093,9 @@ impl<W: LayoutElement> Workspace<W> {
         }
     }
 
+    /// Returns whether there are any windows, including minimized ones.
     pub fn has_windows(&self) -> bool {
-        self.windows().next().is_some()
+        self.windows().next().is_some() || !self.minimized.is_empty()
     }
 
     pub fn has_window(&self, window: &W::Id) -> bool {
@@ -1577,11 +2103,25 @@ impl<W: LayoutElement> Workspace<W> {
     }
 
     pub fn find_wl_surface(&self, wl_surface: &WlSurface) -> Option<&W> {
-        self.windows().find(|win| win.is_wl_surface(wl_surface))
+        self.windows()
+            .chain(self.minimized_tiles().map(Tile::window))
+            .find(|win| win.is_wl_surface(wl_surface))
     }
 
     pub fn find_wl_surface_mut(&mut self, wl_surface: &WlSurface) -> Option<&mut W> {
-        self.windows_mut().find(|win| win.is_wl_surface(wl_surface))
+        let scrolling = self.scrolling.tiles_mut();
+        let tiling = self.tiling.tiles_mut();
+        let floating = self.floating.tiles_mut();
+        let minimized = self
+            .minimized
+            .iter_mut()
+            .map(|minimized| &mut minimized.tile);
+        scrolling
+            .chain(tiling)
+            .chain(floating)
+            .chain(minimized)
+            .map(Tile::window_mut)
+            .find(|win| win.is_wl_surface(wl_surface))
     }
 
     pub fn tiles_with_render_positions(
@@ -1589,11 +2129,14 @@ impl<W: LayoutElement> Workspace<W> {
     ) -> impl Iterator<Item = (&Tile<W>, Point<f64, Logical>, bool)> {
         let scrolling = self.scrolling.tiles_with_render_positions();
 
+        let tiling = self.tiling.tiles_with_render_positions();
+        let tiling = tiling.map(|(tile, pos)| (tile, pos, true));
+
         let floating = self.floating.tiles_with_render_positions();
         let visible = self.is_floating_visible();
         let floating = floating.map(move |(tile, pos)| (tile, pos, visible));
 
-        floating.chain(scrolling)
+        floating.chain(tiling).chain(scrolling)
     }
 
     pub fn tiles_with_render_positions_mut(
@@ -1601,27 +2144,40 @@ impl<W: LayoutElement> Workspace<W> {
         round: bool,
     ) -> impl Iterator<Item = (&mut Tile<W>, Point<f64, Logical>)> {
         let scrolling = self.scrolling.tiles_with_render_positions_mut(round);
+        let tiling = self.tiling.tiles_with_render_positions_mut(round);
         let floating = self.floating.tiles_with_render_positions_mut(round);
-        floating.chain(scrolling)
+        floating.chain(tiling).chain(scrolling)
     }
 
     pub fn tiles_with_ipc_layouts(&self) -> impl Iterator<Item = (&Tile<W>, WindowLayout)> {
         let scrolling = self.scrolling.tiles_with_ipc_layouts();
+        let tiling = self.tiling.tiles_with_ipc_layouts();
         let floating = self.floating.tiles_with_ipc_layouts();
-        floating.chain(scrolling)
+        floating.chain(tiling).chain(scrolling)
     }
 
     pub fn active_window_visual_rectangle(&self) -> Option<Rectangle<f64, Logical>> {
         if self.floating_is_active.get() {
             self.floating.active_window_visual_rectangle()
-        } else {
+        } else if self.is_scrolling_layout() {
             self.scrolling.active_window_visual_rectangle()
+        } else {
+            self.tiling.active_window_visual_rectangle()
         }
     }
 
     pub fn popup_target_rect(&self, window: &W::Id) -> Option<Rectangle<f64, Logical>> {
+        if let Some(tile) = self
+            .minimized_tiles()
+            .find(|tile| tile.window().id() == window)
+        {
+            return Some(Rectangle::from_size(tile.window_size()));
+        }
+
         if self.floating.has_window(window) {
             self.floating.popup_target_rect(window)
+        } else if self.tiling.has_window(window) {
+            self.tiling.popup_target_rect(window)
         } else {
             self.scrolling.popup_target_rect(window)
         }
@@ -1636,10 +2192,17 @@ impl<W: LayoutElement> Workspace<W> {
         push: &mut dyn FnMut(WorkspaceRenderElement<R>),
     ) {
         let scrolling_focus_ring = focus_ring && !self.floating_is_active();
-        self.scrolling
-            .render(ctx, xray_pos, scrolling_focus_ring, layer, &mut |elem| {
-                push(elem.into())
-            });
+        if self.is_scrolling_layout() {
+            self.scrolling
+                .render(ctx, xray_pos, scrolling_focus_ring, layer, &mut |elem| {
+                    push(elem.into())
+                });
+        } else {
+            self.tiling
+                .render(ctx, xray_pos, scrolling_focus_ring, layer, &mut |elem| {
+                    push(elem.into())
+                });
+        }
     }
 
     pub fn render_floating<R: NiriRenderer>(
@@ -1684,7 +2247,11 @@ impl<W: LayoutElement> Workspace<W> {
     }
 
     pub fn render_above_top_layer(&self) -> bool {
-        self.scrolling.render_above_top_layer()
+        if self.is_scrolling_layout() {
+            self.scrolling.render_above_top_layer()
+        } else {
+            self.tiling.render_above_top_layer()
+        }
     }
 
     pub fn is_floating_visible(&self) -> bool {
@@ -1739,6 +2306,9 @@ impl<W: LayoutElement> Workspace<W> {
         if self.floating.has_window(window) {
             self.floating
                 .start_close_animation_for_window(renderer, window, blocker);
+        } else if self.tiling.has_window(window) {
+            self.tiling
+                .start_close_animation_for_window(renderer, window, blocker);
         } else {
             self.scrolling
                 .start_close_animation_for_window(renderer, window, blocker);
@@ -1758,7 +2328,9 @@ impl<W: LayoutElement> Workspace<W> {
     }
 
     pub fn start_open_animation(&mut self, id: &W::Id) -> bool {
-        self.scrolling.start_open_animation(id) || self.floating.start_open_animation(id)
+        self.scrolling.start_open_animation(id)
+            || self.tiling.start_open_animation(id)
+            || self.floating.start_open_animation(id)
     }
 
     pub fn window_under(&self, pos: Point<f64, Logical>) -> Option<(&W, HitType)> {
@@ -1773,7 +2345,11 @@ impl<W: LayoutElement> Workspace<W> {
             }
         }
 
-        self.scrolling.window_under(pos)
+        if self.is_scrolling_layout() {
+            self.scrolling.window_under(pos)
+        } else {
+            self.tiling.window_under(pos)
+        }
     }
 
     pub fn resize_edges_under(&self, pos: Point<f64, Logical>) -> Option<ResizeEdge> {
@@ -1813,7 +2389,20 @@ impl<W: LayoutElement> Workspace<W> {
     }
 
     pub fn update_window(&mut self, window: &W::Id, serial: Option<Serial>) {
-        if !self.floating.update_window(window, serial) {
+        if let Some(tile) = self
+            .minimized_tiles_mut()
+            .find(|tile| tile.window().id() == window)
+        {
+            if let Some(serial) = serial {
+                tile.window_mut().on_commit(serial);
+            }
+            tile.update_window();
+            return;
+        }
+
+        if !self.floating.update_window(window, serial)
+            && !self.tiling.update_window(window, serial)
+        {
             self.scrolling.update_window(window, serial);
         }
     }
@@ -1821,12 +2410,22 @@ impl<W: LayoutElement> Workspace<W> {
     pub fn refresh(&mut self, is_active: bool, is_focused: bool) {
         self.scrolling
             .refresh(is_active && !self.floating_is_active.get(), is_focused);
+        self.tiling
+            .refresh(is_active && !self.floating_is_active.get(), is_focused);
         self.floating
             .refresh(is_active && self.floating_is_active.get(), is_focused);
+
+        for tile in self.minimized_tiles_mut() {
+            let win = tile.window_mut();
+            win.set_activated(false);
+            win.set_interactive_resize(None);
+            win.send_pending_configure();
+            win.refresh();
+        }
     }
 
     pub fn scroll_amount_to_activate(&self, window: &W::Id) -> f64 {
-        if self.floating.has_window(window) {
+        if self.floating.has_window(window) || self.tiling.has_window(window) {
             return 0.;
         }
 
@@ -1841,7 +2440,7 @@ impl<W: LayoutElement> Workspace<W> {
         if self.floating.activate_window(window) {
             self.floating_is_active = FloatingActive::Yes;
             true
-        } else if self.scrolling.activate_window(window) {
+        } else if self.scrolling.activate_window(window) || self.tiling.activate_window(window) {
             self.floating_is_active = FloatingActive::No;
             true
         } else {
@@ -1853,7 +2452,7 @@ impl<W: LayoutElement> Workspace<W> {
         if self.floating.activate_window_without_raising(window) {
             self.floating_is_active = FloatingActive::Yes;
             true
-        } else if self.scrolling.activate_window(window) {
+        } else if self.scrolling.activate_window(window) || self.tiling.activate_window(window) {
             self.floating_is_active = match self.floating_is_active {
                 FloatingActive::No => FloatingActive::No,
                 FloatingActive::NoButRaised => FloatingActive::NoButRaised,
@@ -1866,14 +2465,31 @@ impl<W: LayoutElement> Workspace<W> {
     }
 
     pub(super) fn scrolling_insert_position(&self, pos: Point<f64, Logical>) -> InsertPosition {
-        self.scrolling.insert_position(pos)
+        if self.is_scrolling_layout() {
+            self.scrolling.insert_position(pos)
+        } else {
+            InsertPosition::NewColumn(self.tiling.insert_position(pos))
+        }
     }
 
     pub(super) fn insert_hint_area(
         &self,
         position: InsertPosition,
     ) -> Option<Rectangle<f64, Logical>> {
-        self.scrolling.insert_hint_area(position)
+        if let InsertPosition::SnapZone(zone) = position {
+            return Some(self.floating.snap_zone_area(zone));
+        }
+
+        if self.is_scrolling_layout() {
+            return self.scrolling.insert_hint_area(position);
+        }
+
+        match position {
+            InsertPosition::NewColumn(idx) => self.tiling.insert_hint_area(idx),
+            InsertPosition::InColumn(_, _)
+            | InsertPosition::Floating
+            | InsertPosition::SnapZone(_) => None,
+        }
     }
 
     pub fn view_offset_gesture_begin(&mut self, is_touchpad: bool) {
@@ -1903,8 +2519,11 @@ impl<W: LayoutElement> Workspace<W> {
         let trigger_width = config.trigger_width;
 
         // This working area intentionally does not include extra struts from Options.
-        let x = pos.x - self.working_area.loc.x;
-        let width = self.working_area.size.w;
+        let (x, width) = if self.is_scrolling_vertical() {
+            (pos.y - self.working_area.loc.y, self.working_area.size.h)
+        } else {
+            (pos.x - self.working_area.loc.x, self.working_area.size.w)
+        };
 
         let x = x.clamp(0., width);
         let trigger_width = trigger_width.clamp(0., width / 2.);
@@ -1936,6 +2555,9 @@ impl<W: LayoutElement> Workspace<W> {
     pub fn interactive_resize_begin(&mut self, window: W::Id, edges: ResizeEdge) -> bool {
         if self.floating.has_window(&window) {
             self.floating.interactive_resize_begin(window, edges)
+        } else if self.tiling.has_window(&window) {
+            // Windows in the tiling layout are sized by the tiling algorithm.
+            false
         } else {
             self.scrolling.interactive_resize_begin(window, edges)
         }
@@ -1948,6 +2570,8 @@ impl<W: LayoutElement> Workspace<W> {
     ) -> bool {
         if self.floating.has_window(window) {
             self.floating.interactive_resize_update(window, delta)
+        } else if self.tiling.has_window(window) {
+            false
         } else {
             self.scrolling.interactive_resize_update(window, delta)
         }
@@ -1957,7 +2581,7 @@ impl<W: LayoutElement> Workspace<W> {
         if let Some(window) = window {
             if self.floating.has_window(window) {
                 self.floating.interactive_resize_end(Some(window));
-            } else {
+            } else if !self.tiling.has_window(window) {
                 self.scrolling.interactive_resize_end(Some(window));
             }
         } else {
@@ -1993,6 +2617,10 @@ impl<W: LayoutElement> Workspace<W> {
         &mut self.scrolling
     }
 
+    pub fn tiling(&self) -> &TilingSpace<W> {
+        &self.tiling
+    }
+
     pub fn floating(&self) -> &FloatingSpace<W> {
         &self.floating
     }
@@ -2028,6 +2656,32 @@ impl<W: LayoutElement> Workspace<W> {
         assert!(Rc::ptr_eq(&self.options, self.scrolling.options()));
         self.scrolling.verify_invariants();
 
+        assert_eq!(self.view_size, self.tiling.view_size());
+        assert_eq!(self.working_area, self.tiling.parent_area());
+        assert_eq!(&self.clock, self.tiling.clock());
+        assert!(Rc::ptr_eq(&self.options, self.tiling.options()));
+        self.tiling.verify_invariants();
+
+        let layout_mode = self
+            .layout_mode_override
+            .unwrap_or(self.options.layout.layout_mode);
+        assert_eq!(self.layout_mode, layout_mode);
+        if self.is_scrolling_layout() {
+            assert!(
+                self.tiling.is_empty(),
+                "in the scrolling layout mode, tiling must be empty"
+            );
+        } else {
+            assert!(
+                self.scrolling.is_empty(),
+                "in the tiling layout modes, scrolling must be empty"
+            );
+            assert_eq!(
+                Some(self.tiling.mode()),
+                TilingMode::from_layout_mode(self.layout_mode)
+            );
+        }
+
         assert_eq!(self.view_size, self.floating.view_size());
         assert_eq!(self.working_area, self.floating.working_area());
         assert_eq!(&self.clock, self.floating.clock());
@@ -2039,7 +2693,7 @@ impl<W: LayoutElement> Workspace<W> {
                 !self.floating_is_active.get(),
                 "when floating is empty it must never be active"
             );
-        } else if self.scrolling.is_empty() {
+        } else if self.tiled_is_empty() {
             assert!(
                 self.floating_is_active.get(),
                 "when scrolling is empty but floating isn't, floating should be active"
@@ -2,7 +2,7 @@ use std::cell::{Cell, RefCell};
 use std::collections::{HashMap, HashSet};
 use std::ffi::OsString;
 use std::os::unix::net::UnixStream;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{self, Receiver, Sender};
@@ -13,11 +13,12 @@ use std::{env, mem, thread};
 use _server_decoration::server::org_kde_kwin_server_decoration_manager::Mode as KdeDecorationsMode;
 use anyhow::{bail, ensure, Context};
 use calloop::futures::Scheduler;
+use directories::BaseDirs;
 use niri_config::debug::PreviewRender;
 use niri_config::output::MaxBpc;
 use niri_config::{
-    Config, FloatOrInt, Key, Modifiers, OutputName, TrackLayout, WarpMouseToFocusMode,
-    WorkspaceReference, Xkb,
+    Bind, Config, FloatOrInt, Key, Modifiers, OutputName, SwallowParent, TrackLayout,
+    WarpMouseToFocusMode, WorkspaceReference, Xkb, ZoomFollow,
 };
 use smithay::backend::allocator::Fourcc;
 use smithay::backend::input::Keycode;
@@ -46,7 +47,7 @@ use smithay::desktop::{
     find_popup_root_surface, layer_map_for_output, LayerMap, LayerSurface, PopupGrab, PopupManager,
     PopupUngrabStrategy, Space, Window, WindowSurfaceType,
 };
-use smithay::input::keyboard::{Layout as KeyboardLayout, XkbConfig};
+use smithay::input::keyboard::{xkb, Layout as KeyboardLayout, ModifiersState, XkbConfig};
 use smithay::input::pointer::{
     CursorIcon, CursorImageStatus, CursorImageSurfaceData, Focus,
     GrabStartData as PointerGrabStartData, MotionEvent,
@@ -130,16 +131,20 @@ use crate::dbus::gnome_shell_introspect::{self, IntrospectToNiri, NiriToIntrospe
 use crate::dbus::gnome_shell_screenshot::{NiriToScreenshot, ScreenshotToNiri};
 use crate::frame_clock::FrameClock;
 use crate::handlers::{configure_lock_surface, XDG_ACTIVATION_TOKEN_TIMEOUT};
+use crate::input::key_filters::KeyFilters;
+use crate::input::key_remap::KeyRemapper;
+use crate::input::mouse_keys::MouseKeys;
 use crate::input::pick_color_grab::PickColorGrab;
 use crate::input::scroll_swipe_gesture::ScrollSwipeGesture;
 use crate::input::scroll_tracker::ScrollTracker;
 use crate::input::{
     apply_libinput_settings, mods_with_finger_scroll_binds, mods_with_mouse_binds,
-    mods_with_tablet_stylus_binds, mods_with_wheel_binds, TabletData,
+    mods_with_tablet_stylus_binds, mods_with_wheel_binds, GestureBindProgress, TabletData,
 };
 use crate::ipc::server::IpcServer;
 use crate::layer::mapped::LayerSurfaceRenderElement;
 use crate::layer::MappedLayer;
+use crate::layout::restore::SavedLayout;
 use crate::layout::tile::TileRenderElement;
 use crate::layout::workspace::{Workspace, WorkspaceId};
 use crate::layout::{
@@ -173,18 +178,22 @@ use crate::ui::hotkey_overlay::HotkeyOverlay;
 use crate::ui::mru::{MruCloseRequest, WindowMruUi, WindowMruUiRenderElement};
 use crate::ui::screen_transition::{self, ScreenTransition};
 use crate::ui::screenshot_ui::{OutputScreenshot, ScreenshotUi, ScreenshotUiRenderElement};
+use crate::ui::zoom::{self, Zoom, ZoomRenderElement, ZoomTarget};
 use crate::utils::scale::{closest_representable_scale, guess_monitor_scale};
 use crate::utils::spawning::{CHILD_DISPLAY, CHILD_ENV};
 use crate::utils::vblank_throttle::VBlankThrottle;
 use crate::utils::watcher::Watcher;
 use crate::utils::xwayland::satellite::Satellite;
 use crate::utils::{
-    center, center_f64, expand_home, get_monotonic_time, ipc_transform_to_smithay, is_mapped,
-    logical_output, make_screenshot_path, output_matches_name, output_size, panel_orientation,
-    send_scale_transform, write_png_rgba8, xwayland,
+    ancestor_pids, center, center_f64, expand_home, get_monotonic_time, ipc_transform_to_smithay,
+    is_mapped, logical_output, make_screenshot_path, output_matches_name, output_size,
+    panel_orientation, send_scale_transform, write_png_rgba8, xwayland,
 };
 use crate::window::mapped::MappedId;
-use crate::window::{InitialConfigureState, Mapped, ResolvedWindowRules, Unmapped, WindowRef};
+use crate::window::{
+    app_binds_match, swallow_parent_matches, InitialConfigureState, Mapped, ResolvedWindowRules,
+    Unmapped, WindowRef,
+};
 
 const CLEAR_COLOR_LOCKED: [f32; 4] = [0.3, 0.1, 0.1, 1.];
 
@@ -229,6 +238,12 @@ pub struct Niri {
     // Each workspace corresponds to a Space. Each workspace generally has one Output mapped to it,
     // however it may have none (when there are no outputs connected) or multiple (when mirroring).
     pub layout: Layout<Mapped>,
+    /// Layout last written to the state file for `restore-layout`.
+    pub saved_layout: Option<SavedLayout>,
+    /// When the layout was last checked for changes to save.
+    pub saved_layout_checked_at: Option<Instant>,
+    /// Sender to the thread writing the saved layout to disk.
+    pub saved_layout_tx: Option<Sender<SavedLayout>>,
 
     // This space does not actually contain any windows, but all outputs are mapped into it
     // according to their global position.
@@ -324,12 +339,26 @@ pub struct Niri {
     pub single_pixel_buffer_state: SinglePixelBufferState,
 
     pub seat: Seat<State>,
+    pub key_remapper: KeyRemapper,
+    pub key_filters: KeyFilters,
+    pub mouse_keys: MouseKeys,
+    /// Target factor of the accessibility zoom, 1 when not zoomed.
+    pub zoom_factor: f64,
     /// Scancodes of the keys to suppress.
     pub suppressed_keys: HashSet<Keycode>,
     /// Button codes of the mouse buttons to suppress.
     pub suppressed_buttons: HashSet<u32>,
     pub bind_cooldown_timers: HashMap<Key, RegistrationToken>,
     pub bind_repeat_timer: Option<RegistrationToken>,
+    /// Binds triggered on release, by the key code of their pressed key.
+    pub release_binds: HashMap<Keycode, Bind>,
+    /// Name of the active bind mode, if any.
+    pub bind_mode: Option<String>,
+    /// Name of the app binds matching the focused window, if any.
+    pub app_binds: Option<String>,
+    /// Keys pressed so far of a bind sequence in progress.
+    pub bind_sequence: Vec<Key>,
+    pub bind_sequence_timer: Option<RegistrationToken>,
     pub keyboard_focus: KeyboardFocus,
     pub layer_shell_on_demand_focus: Option<LayerSurface>,
     pub idle_inhibiting_surfaces: HashSet<WlSurface>,
@@ -338,6 +367,10 @@ pub struct Niri {
 
     /// Most recent XKB settings from org.freedesktop.locale1.
     pub xkb_from_locale1: Option<Xkb>,
+    /// Keyboard device whose own xkb settings are loaded, if any.
+    pub xkb_device: Option<String>,
+    /// Compiled keymap and saved state of each keymap loaded for a keyboard device.
+    pub xkb_devices: HashMap<Option<String>, DeviceXkb>,
 
     pub cursor_manager: CursorManager,
     pub cursor_texture_cache: CursorTextureCache,
@@ -373,6 +406,7 @@ pub struct Niri {
     pub pointer_constraint_position_hint: Option<Point<f64, Logical>>,
     pub tablet_cursor_location: Option<Point<f64, Logical>>,
     pub gesture_swipe_3f_cumulative: Option<(f64, f64)>,
+    pub gesture_bind: Option<GestureBindProgress>,
     pub overview_scroll_swipe_gesture: ScrollSwipeGesture,
     pub vertical_wheel_tracker: ScrollTracker,
     pub horizontal_wheel_tracker: ScrollTracker,
@@ -488,6 +522,7 @@ pub struct OutputState {
     pub lock_surface: Option<LockSurface>,
     pub lock_color_buffer: SolidColorBuffer,
     screen_transition: Option<ScreenTransition>,
+    zoom: Zoom,
     /// Damage tracker used for the debug damage visualization.
     pub debug_damage_tracker: OutputDamageTracker,
 }
@@ -636,6 +671,16 @@ pub struct PendingMruCommit {
     stamp: Duration,
 }
 
+/// Keymap of a keyboard device, with the state to restore when switching back to it.
+#[derive(Debug, Default)]
+pub struct DeviceXkb {
+    /// Compiled keymap in the xkb text format.
+    keymap: Option<String>,
+    layout: u32,
+    /// Modifiers when another keymap was loaded, unset if this keymap was never switched away from.
+    mods: Option<ModifiersState>,
+}
+
 impl RedrawState {
     fn queue_redraw(self) -> Self {
         match self {
@@ -796,6 +841,51 @@ impl State {
         self.backend.on_output_config_changed(&mut self.niri);
     }
 
+    pub fn set_bind_mode(&mut self, name: Option<String>) {
+        if self.niri.bind_mode == name {
+            return;
+        }
+
+        debug!("switching to bind mode {name:?}");
+        self.niri.bind_mode.clone_from(&name);
+
+        // The sequence was started from the binds of the previous mode.
+        self.set_bind_sequence(Vec::new());
+
+        if self.niri.hotkey_overlay.set_bind_mode(name) {
+            self.niri.queue_redraw_all();
+        }
+
+        self.ipc_refresh_bind_mode();
+    }
+
+    fn refresh_app_binds(&mut self) {
+        let name = {
+            let config = self.niri.config.borrow();
+            let mapped = match &self.niri.keyboard_focus {
+                KeyboardFocus::Layout {
+                    surface: Some(surface),
+                } => self.niri.layout.find_window_and_output(surface),
+                _ => None,
+            };
+            mapped.and_then(|(mapped, _)| {
+                let window = WindowRef::Mapped(mapped);
+                let app_binds = config.app_binds.iter();
+                let app_binds = app_binds.find(|app| app_binds_match(app, window))?;
+                Some(app_binds.name.clone())
+            })
+        };
+
+        if self.niri.app_binds == name {
+            return;
+        }
+
+        debug!("switching to app binds {name:?}");
+        self.niri.app_binds = name;
+
+        self.ipc_refresh_app_binds();
+    }
+
     fn refresh(&mut self) {
         let _span = tracy_client::span!("State::refresh");
 
@@ -832,9 +922,13 @@ impl State {
         self.ipc_refresh_casts();
 
         self.niri.refresh_window_rules();
+        // Needs to be called after updating the keyboard focus and the window titles.
+        self.refresh_app_binds();
         self.refresh_ipc_outputs();
         self.ipc_refresh_layout();
         self.ipc_refresh_keyboard_layout_index();
+        self.ipc_refresh_mouse_keys();
+        self.niri.refresh_saved_layout();
 
         // Needs to be called after updating the keyboard focus.
         #[cfg(feature = "dbus")]
@@ -1387,13 +1481,12 @@ impl State {
 
     /// Loads the xkb keymap from a file config setting.
     fn set_xkb_file(&mut self, xkb_file: String) -> anyhow::Result<()> {
-        let xkb_file = PathBuf::from(xkb_file);
-        let xkb_file = expand_home(&xkb_file)
-            .context("failed to expand ~")?
-            .unwrap_or(xkb_file);
-
-        let keymap = std::fs::read_to_string(xkb_file).context("failed to read xkb_file")?;
+        let keymap = read_xkb_file(xkb_file)?;
+        self.set_keymap_string(keymap)
+    }
 
+    /// Loads a keymap in the xkb text format.
+    fn set_keymap_string(&mut self, keymap: String) -> anyhow::Result<()> {
         let keyboard = self.niri.seat.get_keyboard().unwrap();
         let num_lock = keyboard.modifier_state().num_lock;
 
@@ -1420,6 +1513,133 @@ impl State {
         }
     }
 
+    /// Loads the keymap from the xkb settings, using locale1 settings if they are unset.
+    fn set_keyboard_xkb(&mut self, mut xkb: Xkb) {
+        // It's fine to .take() the xkb file, as this is a
+        // clone and the file field is not used in the XkbConfig.
+        if let Some(xkb_file) = xkb.file.take() {
+            if let Err(err) = self.set_xkb_file(xkb_file) {
+                warn!("error loading xkb_file: {err:?}");
+            } else {
+                // We successfully set xkb file so we don't need to fallback to XkbConfig.
+                return;
+            }
+        }
+
+        // If xkb is unset in the niri config, use settings from locale1.
+        if xkb == Xkb::default() {
+            trace!("using xkb from locale1");
+            xkb = self.niri.xkb_from_locale1.clone().unwrap_or_default();
+        }
+
+        self.set_xkb_config(xkb.to_xkb_config());
+    }
+
+    /// Compiles the keymap from the xkb settings, using locale1 settings if they are unset.
+    fn compile_xkb_keymap(&self, mut xkb: Xkb) -> anyhow::Result<String> {
+        if let Some(xkb_file) = xkb.file.take() {
+            match read_xkb_file(xkb_file) {
+                Ok(keymap) => return Ok(keymap),
+                Err(err) => warn!("error loading xkb_file: {err:?}"),
+            }
+        }
+
+        if xkb == Xkb::default() {
+            trace!("using xkb from locale1");
+            xkb = self.niri.xkb_from_locale1.clone().unwrap_or_default();
+        }
+
+        let config = xkb.to_xkb_config();
+        let context = xkb::Context::new(xkb::CONTEXT_NO_FLAGS);
+        let keymap = xkb::Keymap::new_from_names(
+            &context,
+            config.rules,
+            config.model,
+            config.layout,
+            config.variant,
+            config.options,
+            xkb::COMPILE_NO_FLAGS,
+        )
+        .context("failed to compile keymap")?;
+
+        Ok(keymap.get_as_string(xkb::KEYMAP_FORMAT_TEXT_V1))
+    }
+
+    /// Loads the keymap for the keyboard device that a key press came from.
+    ///
+    /// Keyboards with their own xkb settings in a device section get their own keymap, and the
+    /// rest share the keyboard section keymap.
+    ///
+    /// Each keymap is compiled once and cached as text until the next config reload. Switching
+    /// still has to parse it, which is much cheaper than resolving the xkb rules but not free, so
+    /// this returns early when the keymap is already loaded. The active layout and the latched
+    /// and locked modifiers are saved per keymap and restored when switching back to it.
+    pub fn switch_device_xkb(&mut self, device: Option<&input::Device>) {
+        let (name, xkb) = {
+            let config = self.niri.config.borrow();
+            let device_xkb = device.and_then(|device| {
+                let xkb = config
+                    .input
+                    .devices_matching(device.name(), device.id_vendor(), device.id_product())
+                    .filter_map(|c| c.xkb.clone())
+                    .last()?;
+                Some((device.name().to_owned(), xkb))
+            });
+            match device_xkb {
+                Some((name, xkb)) => (Some(name), xkb),
+                None => (None, config.input.keyboard.xkb.clone()),
+            }
+        };
+
+        if name == self.niri.xkb_device {
+            return;
+        }
+
+        let keyboard = self.niri.seat.get_keyboard().unwrap();
+        let layout = keyboard.with_xkb_state(self, |context| {
+            context.xkb().lock().unwrap().active_layout().0
+        });
+        let mods = keyboard.modifier_state();
+        let old_name = self.niri.xkb_device.take();
+        let old = self.niri.xkb_devices.entry(old_name).or_default();
+        old.layout = layout;
+        old.mods = Some(mods);
+
+        let cached = self
+            .niri
+            .xkb_devices
+            .get(&name)
+            .and_then(|d| d.keymap.clone());
+        let keymap = match cached {
+            Some(keymap) => Ok(keymap),
+            None => self.compile_xkb_keymap(xkb),
+        };
+        let keymap = match keymap {
+            Ok(keymap) => keymap,
+            Err(err) => {
+                warn!("error compiling keymap: {err:?}");
+                return;
+            }
+        };
+        if let Err(err) = self.set_keymap_string(keymap.clone()) {
+            warn!("error loading keymap: {err:?}");
+            return;
+        }
+
+        let saved = self.niri.xkb_devices.entry(name.clone()).or_default();
+        saved.keymap = Some(keymap);
+        if let Some(mods) = saved.mods {
+            let layout = saved.layout;
+            keyboard.set_modifier_state(mods);
+            keyboard.with_xkb_state(self, |mut context| {
+                context.set_layout(KeyboardLayout(layout));
+            });
+        }
+        self.niri.xkb_device = name;
+
+        self.ipc_keyboard_layouts_changed();
+    }
+
     pub fn set_xkb_config(&mut self, xkb: XkbConfig) {
         let keyboard = self.niri.seat.get_keyboard().unwrap();
         let num_lock = keyboard.modifier_state().num_lock;
@@ -1504,10 +1724,26 @@ impl State {
         }
 
         // We need &mut self to reload the xkb config, so just store it here.
-        if config.input.keyboard.xkb != old_config.input.keyboard.xkb {
+        if config.input.keyboard.xkb != old_config.input.keyboard.xkb
+            || !device_xkbs(&config.input).eq(device_xkbs(&old_config.input))
+        {
             reload_xkb = Some(config.input.keyboard.xkb.clone());
         }
 
+        // Config changes to the key filters and mouse keys override their toggled state.
+        let keyboard = &config.input.keyboard;
+        let old_keyboard = &old_config.input.keyboard;
+        let sticky_keys =
+            (keyboard.sticky_keys != old_keyboard.sticky_keys).then_some(keyboard.sticky_keys);
+        if keyboard.slow_keys.on != old_keyboard.slow_keys.on {
+            self.niri.key_filters.slow_keys = keyboard.slow_keys.on;
+        }
+        if keyboard.bounce_keys.on != old_keyboard.bounce_keys.on {
+            self.niri.key_filters.bounce_keys = keyboard.bounce_keys.on;
+        }
+        let mouse_keys = (config.input.mouse_keys.on != old_config.input.mouse_keys.on)
+            .then_some(config.input.mouse_keys.on);
+
         // Reload the repeat info.
         if config.input.keyboard.repeat_rate != old_config.input.keyboard.repeat_rate
             || config.input.keyboard.repeat_delay != old_config.input.keyboard.repeat_delay
@@ -1525,6 +1761,7 @@ impl State {
             || config.input.trackpoint != old_config.input.trackpoint
             || config.input.tablet != old_config.input.tablet
             || config.input.touch != old_config.input.touch
+            || config.input.devices != old_config.input.devices
         {
             libinput_config_changed = true;
         }
@@ -1544,8 +1781,9 @@ impl State {
         }
 
         let binds_changed = config.binds != old_config.binds;
+        let bind_modes_changed = config.bind_modes != old_config.bind_modes;
         let new_mod_key = self.backend.mod_key(&config);
-        if new_mod_key != self.backend.mod_key(&old_config) || binds_changed {
+        if new_mod_key != self.backend.mod_key(&old_config) || binds_changed || bind_modes_changed {
             self.niri
                 .hotkey_overlay
                 .on_hotkey_config_updated(new_mod_key);
@@ -1634,31 +1872,20 @@ impl State {
         // Release the borrow.
         drop(old_config);
 
-        // Now with a &mut self we can reload the xkb config.
-        if let Some(mut xkb) = reload_xkb {
-            let mut set_xkb_config = true;
-
-            // It's fine to .take() the xkb file, as this is a
-            // clone and the file field is not used in the XkbConfig.
-            if let Some(xkb_file) = xkb.file.take() {
-                if let Err(err) = self.set_xkb_file(xkb_file) {
-                    warn!("error reloading xkb_file: {err:?}");
-                } else {
-                    // We successfully set xkb file so we don't need to fallback to XkbConfig.
-                    set_xkb_config = false;
-                }
-            }
-
-            if set_xkb_config {
-                // If xkb is unset in the niri config, use settings from locale1.
-                if xkb == Xkb::default() {
-                    trace!("using xkb from locale1");
-                    xkb = self.niri.xkb_from_locale1.clone().unwrap_or_default();
-                }
+        if let Some(on) = sticky_keys {
+            self.set_sticky_keys(on);
+        }
+        if let Some(on) = mouse_keys {
+            self.set_mouse_keys(on);
+        }
 
-                self.set_xkb_config(xkb.to_xkb_config());
-            }
+        // Now with a &mut self we can reload the xkb config.
+        if let Some(xkb) = reload_xkb {
+            // The next key press loads the device keymap again if needed.
+            self.niri.xkb_device = None;
+            self.niri.xkb_devices.clear();
 
+            self.set_keyboard_xkb(xkb);
             self.ipc_keyboard_layouts_changed();
         }
 
@@ -1699,6 +1926,21 @@ impl State {
             self.niri.window_mru_ui.update_binds();
         }
 
+        if bind_modes_changed {
+            // Leave the bind mode if it was removed from the config.
+            let config = self.niri.config.borrow();
+            let removed = self
+                .niri
+                .bind_mode
+                .as_ref()
+                .is_some_and(|name| config.bind_mode(name).is_none());
+            drop(config);
+
+            if removed {
+                self.set_bind_mode(None);
+            }
+        }
+
         if recent_windows_changed {
             self.niri.window_mru_ui.update_config();
         }
@@ -2248,6 +2490,8 @@ impl State {
         }
 
         let xkb = xkb.clone();
+        self.niri.xkb_device = None;
+        self.niri.xkb_devices.clear();
         self.set_xkb_config(xkb.to_xkb_config());
         self.ipc_keyboard_layouts_changed();
     }
@@ -2278,7 +2522,15 @@ impl Niri {
         animation_clock.set_rate(rate);
         animation_clock.set_complete_instantly(config_.animations.off);
 
-        let layout = Layout::new(animation_clock.clone(), &config_);
+        let mut layout = Layout::new(animation_clock.clone(), &config_);
+        if config_.restore_layout {
+            if let Some(path) = saved_layout_path().filter(|path| path.exists()) {
+                match read_saved_layout(&path) {
+                    Ok(saved) => layout.set_pending_restore(saved),
+                    Err(err) => warn!("error loading saved layout from {path:?}: {err:?}"),
+                }
+            }
+        }
 
         let (blocker_cleared_tx, blocker_cleared_rx) = mpsc::channel();
 
@@ -2478,7 +2730,8 @@ impl Niri {
             socket_name
         });
 
-        let ipc_server = match IpcServer::start(&event_loop, socket_name.as_deref()) {
+        let ipc_server = match IpcServer::start(&event_loop, socket_name.as_deref(), config.clone())
+        {
             Ok(server) => Some(server),
             Err(err) => {
                 warn!("error starting IPC server: {err:?}");
@@ -2513,6 +2766,9 @@ impl Niri {
             )
             .unwrap();
 
+        let key_filters = KeyFilters::new(&config_.input.keyboard);
+        let mouse_keys = MouseKeys::new(&config_.input.mouse_keys);
+
         drop(config_);
         let mut niri = Self {
             config,
@@ -2530,6 +2786,9 @@ impl Niri {
             clock: animation_clock,
 
             layout,
+            saved_layout: None,
+            saved_layout_checked_at: None,
+            saved_layout_tx: None,
             global_space: Space::default(),
             sorted_outputs: Vec::default(),
             output_state: HashMap::new(),
@@ -2582,10 +2841,19 @@ impl Niri {
             ext_data_control_state,
             popups: PopupManager::default(),
             popup_grab: None,
+            key_remapper: KeyRemapper::default(),
+            key_filters,
+            mouse_keys,
+            zoom_factor: 1.,
             suppressed_keys: HashSet::new(),
             suppressed_buttons: HashSet::new(),
             bind_cooldown_timers: HashMap::new(),
             bind_repeat_timer: Option::default(),
+            release_binds: HashMap::new(),
+            bind_mode: None,
+            app_binds: None,
+            bind_sequence: Vec::new(),
+            bind_sequence_timer: None,
             presentation_state,
             security_context_state,
             gamma_control_manager_state,
@@ -2601,6 +2869,8 @@ impl Niri {
             is_fdo_idle_inhibited: Arc::new(AtomicBool::new(false)),
             keyboard_shortcuts_inhibiting_surfaces: HashMap::new(),
             xkb_from_locale1: None,
+            xkb_device: None,
+            xkb_devices: HashMap::new(),
             cursor_manager,
             cursor_texture_cache: Default::default(),
             cursor_shape_manager_state,
@@ -2614,6 +2884,7 @@ impl Niri {
             pointer_constraint_position_hint: None,
             tablet_cursor_location: None,
             gesture_swipe_3f_cumulative: None,
+            gesture_bind: None,
             overview_scroll_swipe_gesture: ScrollSwipeGesture::new(),
             vertical_wheel_tracker: ScrollTracker::new(120),
             horizontal_wheel_tracker: ScrollTracker::new(120),
@@ -2908,6 +3179,7 @@ impl Niri {
             lock_surface: None,
             lock_color_buffer: SolidColorBuffer::new(size, CLEAR_COLOR_LOCKED),
             screen_transition: None,
+            zoom: Zoom::default(),
             debug_damage_tracker: OutputDamageTracker::from_output(&output),
         };
         let rv = self.output_state.insert(output.clone(), state);
@@ -3491,6 +3763,38 @@ impl Niri {
         rv
     }
 
+    /// Finds a window of a parent process that a new window can swallow.
+    ///
+    /// Closer ancestors win. Among several windows of one process, like with terminal servers,
+    /// the focused one wins.
+    pub fn find_swallow_parent(&self, rule: &SwallowParent, pid: i32) -> Option<Window> {
+        for ancestor in ancestor_pids(pid) {
+            let candidates: Vec<_> = self
+                .layout
+                .windows()
+                .map(|(_, mapped)| mapped)
+                .filter(|mapped| {
+                    mapped
+                        .credentials()
+                        .is_some_and(|creds| creds.pid == ancestor)
+                        && self.layout.can_swallow(&mapped.window)
+                        && swallow_parent_matches(rule, WindowRef::Mapped(mapped))
+                })
+                .collect();
+
+            let Some(found) = candidates
+                .iter()
+                .find(|mapped| mapped.is_focused())
+                .or(candidates.first())
+            else {
+                continue;
+            };
+            return Some(found.window.clone());
+        }
+
+        None
+    }
+
     pub fn output_under_cursor(&self) -> Option<Output> {
         let pos = self.seat.get_pointer().unwrap().current_location();
         self.global_space.output_under(pos).next().cloned()
@@ -3637,6 +3941,27 @@ impl Niri {
             .map(|(_, m)| m.window.clone())
     }
 
+    pub fn find_window_by_mark(&self, mark: &str) -> Option<Window> {
+        let mut found = None;
+        self.layout.with_windows(|mapped, _, _, _| {
+            if mapped.mark() == Some(mark) {
+                found = Some(mapped.window.clone());
+            }
+        });
+        found
+    }
+
+    /// Sets a mark on a window, removing it from the window that had it before.
+    pub fn set_window_mark(&mut self, window: &Window, mark: &str) {
+        self.layout.with_windows_mut(|mapped, _| {
+            if mapped.window == *window {
+                mapped.set_mark(Some(mark.to_owned()));
+            } else if mapped.mark() == Some(mark) {
+                mapped.set_mark(None);
+            }
+        });
+    }
+
     pub fn output_for_tablet(&self) -> Option<&Output> {
         let config = self.config.borrow();
         if config.input.tablet.map_to_focused_output {
@@ -4024,6 +4349,74 @@ impl Niri {
         self.layout.refresh(layout_is_active);
     }
 
+    pub fn refresh_saved_layout(&mut self) {
+        const INTERVAL: Duration = Duration::from_secs(1);
+
+        if !self.config.borrow().restore_layout {
+            return;
+        }
+
+        // Don't overwrite the saved layout while windows are still being restored from it.
+        if self.layout.has_pending_restore() {
+            return;
+        }
+
+        let now = Instant::now();
+        if self
+            .saved_layout_checked_at
+            .is_some_and(|last| now.saturating_duration_since(last) < INTERVAL)
+        {
+            return;
+        }
+        self.saved_layout_checked_at = Some(now);
+
+        let Some(saved) = self.layout.saved_layout(Mapped::window_hints) else {
+            return;
+        };
+        if self.saved_layout.as_ref() == Some(&saved) {
+            return;
+        }
+
+        let Some(path) = saved_layout_path() else {
+            return;
+        };
+
+        // Writing happens on a separate thread to keep slow disks from blocking the event loop.
+        if self.saved_layout_tx.is_none() {
+            let (tx, rx) = mpsc::channel::<SavedLayout>();
+            let res = thread::Builder::new()
+                .name("Layout Saver".to_owned())
+                .spawn(move || {
+                    while let Ok(mut saved) = rx.recv() {
+                        // Only the latest layout matters.
+                        while let Ok(newer) = rx.try_recv() {
+                            saved = newer;
+                        }
+
+                        if let Err(err) = write_saved_layout(&path, &saved) {
+                            warn!("error saving layout to {path:?}: {err:?}");
+                        }
+                    }
+                });
+
+            match res {
+                Ok(_) => self.saved_layout_tx = Some(tx),
+                Err(err) => {
+                    warn!("error spawning a thread to save the layout: {err:?}");
+                    return;
+                }
+            }
+        }
+
+        let tx = self.saved_layout_tx.as_ref().unwrap();
+        if tx.send(saved.clone()).is_err() {
+            warn!("error sending the layout to the saving thread");
+            self.saved_layout_tx = None;
+            return;
+        }
+        self.saved_layout = Some(saved);
+    }
+
     pub fn refresh_idle_inhibit(&mut self) {
         let _span = tracy_client::span!("Niri::refresh_idle_inhibit");
 
@@ -4099,6 +4492,67 @@ impl Niri {
                 }
             }
         }
+
+        self.advance_zoom();
+    }
+
+    fn advance_zoom(&mut self) {
+        let config = self.config.borrow().zoom;
+        // Zoom ignores animation slowdown, like the screen transition.
+        let now = self.clock.now_unadjusted();
+        let pointer_pos = self.seat.get_pointer().unwrap().current_location();
+        let active_output = self.layout.active_output();
+
+        for (output, state) in &mut self.output_state {
+            let Some(output_geo) = self.global_space.output_geometry(output) else {
+                continue;
+            };
+
+            let target = match config.follow {
+                ZoomFollow::Pointer => output_geo
+                    .to_f64()
+                    .contains(pointer_pos)
+                    .then(|| ZoomTarget::Pointer(pointer_pos - output_geo.loc.to_f64())),
+                ZoomFollow::Focus => active_output
+                    .filter(|active| *active == output)
+                    .and_then(|output| self.layout.monitor_for_output(output))
+                    .and_then(|mon| mon.active_window_visual_rectangle())
+                    .map(ZoomTarget::Focus),
+            };
+
+            state.zoom.advance(
+                now,
+                self.zoom_factor,
+                target,
+                output_size(output),
+                config.smoothing_ms,
+            );
+        }
+    }
+
+    /// Sets the target factor of the accessibility zoom, where 1 turns the zoom off.
+    pub fn set_zoom_factor(&mut self, factor: f64) {
+        let factor = factor.clamp(1., zoom::MAX_FACTOR);
+        if self.zoom_factor == factor {
+            return;
+        }
+
+        self.zoom_factor = factor;
+        self.queue_redraw_all();
+    }
+
+    /// Converts a position on a zoomed output to the position of what is displayed there.
+    pub fn unzoom_position(&self, pos: Point<f64, Logical>) -> Point<f64, Logical> {
+        let Some((output, pos_within_output)) = self.output_under(pos) else {
+            return pos;
+        };
+
+        let zoom = &self.output_state[output].zoom;
+        if !zoom.is_active() {
+            return pos;
+        }
+
+        pos - pos_within_output + zoom.unproject(pos_within_output)
     }
 
     pub fn update_render_elements(&mut self, output: Option<&Output>) {
@@ -4218,6 +4672,24 @@ impl Niri {
         let state = self.output_state.get(output).unwrap();
         ctx.xray = Some(&state.xray);
 
+        // Zoom magnifies everything on screen, including the pointer.
+        if ctx.target == RenderTarget::Output && state.zoom.is_active() {
+            let mut elements = Vec::new();
+            self.render_inner(ctx.as_gles(), output, include_pointer, &mut |elem| {
+                elements.push(elem)
+            });
+
+            let scale = Scale::from(output.current_scale().fractional_scale());
+            match state.zoom.render(ctx.as_gles().renderer, scale, &elements) {
+                Ok(elem) => {
+                    push(elem.into());
+                    self.clear_xray_elements(output);
+                    return;
+                }
+                Err(err) => warn!("error rendering zoom: {err:?}"),
+            }
+        }
+
         self.render_inner(ctx, output, include_pointer, push);
 
         self.clear_xray_elements(output);
@@ -4636,6 +5108,7 @@ impl Niri {
             state.unfinished_animations_remain |= self.screenshot_ui.are_animations_ongoing();
             state.unfinished_animations_remain |= self.window_mru_ui.are_animations_ongoing();
             state.unfinished_animations_remain |= state.screen_transition.is_some();
+            state.unfinished_animations_remain |= state.zoom.are_animations_ongoing();
 
             // Also keep redrawing if the current cursor is animated.
             state.unfinished_animations_remain |= self
@@ -4774,6 +5247,16 @@ impl Niri {
         output: &Output,
         render_element_states: &RenderElementStates,
     ) {
+        // While zoomed, everything is rendered into the zoom offscreen, so look up the elements in
+        // its states.
+        let zoom_data = self.output_state[output].zoom.offscreen_data();
+        let render_element_states = match &*zoom_data {
+            Some(data) if render_element_states.element_was_presented(data.id.clone()) => {
+                &data.states
+            }
+            _ => render_element_states,
+        };
+
         // FIXME: potentially tweak the compare function. The default one currently always prefers a
         // higher refresh-rate output, which is not always desirable (i.e. with a very small
         // overlap).
@@ -6519,6 +7002,53 @@ impl ClientData for ClientState {
     fn disconnected(&self, _client_id: ClientId, _reason: DisconnectReason) {}
 }
 
+fn read_xkb_file(xkb_file: String) -> anyhow::Result<String> {
+    let xkb_file = PathBuf::from(xkb_file);
+    let xkb_file = expand_home(&xkb_file)
+        .context("failed to expand ~")?
+        .unwrap_or(xkb_file);
+
+    std::fs::read_to_string(xkb_file).context("failed to read xkb_file")
+}
+
+/// Returns the xkb settings of the device sections, along with the devices they match.
+fn device_xkbs(
+    input: &niri_config::Input,
+) -> impl Iterator<Item = (&Option<String>, Option<u32>, Option<u32>, &Xkb)> {
+    input
+        .devices
+        .iter()
+        .filter_map(|d| Some((&d.name, d.vendor, d.product, d.xkb.as_ref()?)))
+}
+
+fn saved_layout_path() -> Option<PathBuf> {
+    let dirs = BaseDirs::new()?;
+    let mut path = dirs.state_dir()?.to_owned();
+    path.push("niri");
+    path.push("layout.json");
+    Some(path)
+}
+
+fn read_saved_layout(path: &Path) -> anyhow::Result<SavedLayout> {
+    let json = std::fs::read_to_string(path).context("error reading file")?;
+    serde_json::from_str(&json).context("error parsing layout")
+}
+
+fn write_saved_layout(path: &Path, saved: &SavedLayout) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).context("error creating directory")?;
+    }
+
+    let json = serde_json::to_string(saved).context("error serializing layout")?;
+
+    // Write to a temporary file first so that a crash never leaves a truncated layout behind.
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, json).context("error writing file")?;
+    std::fs::rename(&tmp, path).context("error renaming file")?;
+
+    Ok(())
+}
+
 fn scale_relocate_crop<E: Element>(
     elem: E,
     output_scale: Scale<f64>,
@@ -6563,6 +7093,7 @@ niri_render_elements! {
         WindowMruUi = WindowMruUiRenderElement<R>,
         ExitConfirmDialog = ExitConfirmDialogRenderElement,
         Texture = PrimaryGpuTextureRenderElement,
+        Zoom = ZoomRenderElement,
         // Used for the CPU-rendered panels.
         RelocatedMemoryBuffer = RelocateRenderElement<MemoryRenderBufferRenderElement<R>>,
     }
@@ -31,6 +31,10 @@ pub struct HotkeyOverlay {
     is_open: bool,
     config: Rc<RefCell<Config>>,
     mod_key: ModKey,
+    /// Name of the active bind mode, whose binds the overlay shows instead.
+    bind_mode: Option<String>,
+    /// Keys pressed so far of a bind sequence, whose continuations the overlay shows.
+    bind_sequence: Vec<Key>,
     buffers: RefCell<HashMap<WeakOutput, RenderedOverlay>>,
 }
 
@@ -44,6 +48,8 @@ impl HotkeyOverlay {
             is_open: false,
             config,
             mod_key,
+            bind_mode: None,
+            bind_sequence: Vec::new(),
             buffers: RefCell::new(HashMap::new()),
         }
     }
@@ -75,12 +81,40 @@ impl HotkeyOverlay {
         self.buffers.borrow_mut().clear();
     }
 
+    /// Sets the bind mode to show the binds of.
+    ///
+    /// Returns whether the overlay needs a redraw.
+    pub fn set_bind_mode(&mut self, bind_mode: Option<String>) -> bool {
+        if self.bind_mode == bind_mode {
+            return false;
+        }
+
+        self.bind_mode = bind_mode;
+        self.buffers.borrow_mut().clear();
+        self.is_open
+    }
+
+    /// Sets the keys pressed so far of a bind sequence.
+    ///
+    /// While a sequence is in progress, the overlay shows the keys that can continue it.
+    ///
+    /// Returns whether the overlay needs a redraw.
+    pub fn set_bind_sequence(&mut self, sequence: Vec<Key>) -> bool {
+        if self.bind_sequence == sequence {
+            return false;
+        }
+
+        self.bind_sequence = sequence;
+        self.buffers.borrow_mut().clear();
+        true
+    }
+
     pub fn render<R: NiriRenderer>(
         &self,
         renderer: &mut R,
         output: &Output,
     ) -> Option<PrimaryGpuTextureRenderElement> {
-        if !self.is_open {
+        if !self.is_open && self.bind_sequence.is_empty() {
             return None;
         }
 
@@ -102,7 +136,15 @@ impl HotkeyOverlay {
 
         let rendered = buffers.entry(weak).or_insert_with(|| {
             let renderer = renderer.as_gles_renderer();
-            render(renderer, &self.config.borrow(), self.mod_key, scale)
+            let config = self.config.borrow();
+            let (title, lines) = collect_lines(
+                &config,
+                self.bind_mode.as_deref(),
+                &self.bind_sequence,
+                self.mod_key,
+                false,
+            );
+            render(renderer, &title, &lines, scale)
                 .unwrap_or_else(|_| RenderedOverlay { buffer: None })
         });
         let buffer = rendered.buffer.as_ref()?;
@@ -127,17 +169,18 @@ impl HotkeyOverlay {
 
     pub fn a11y_text(&self) -> String {
         let config = self.config.borrow();
-        let actions = collect_actions(&config);
+        let (title, lines) = collect_lines(
+            &config,
+            self.bind_mode.as_deref(),
+            &self.bind_sequence,
+            self.mod_key,
+            true,
+        );
 
         let mut buf = String::new();
-        writeln!(&mut buf, "{TITLE}").unwrap();
+        writeln!(&mut buf, "{title}").unwrap();
 
-        for action in actions {
-            let Some((key, action)) = format_bind(&config.binds.0, action) else {
-                continue;
-            };
-
-            let key = key.map(|key| key_name(true, self.mod_key, &key));
+        for (key, action) in lines {
             let key = key.as_deref().unwrap_or("not bound");
 
             let action = match pango::parse_markup(&action, '\0') {
@@ -152,7 +195,7 @@ impl HotkeyOverlay {
     }
 }
 
-fn format_bind(binds: &[Bind], action: &Action) -> Option<(Option<Key>, String)> {
+fn format_bind<'a>(binds: &'a [Bind], action: &Action) -> Option<(Option<&'a Bind>, String)> {
     let mut bind_with_non_null = None;
     let mut bind_with_custom_title = None;
     let mut found_null_title = false;
@@ -180,18 +223,96 @@ fn format_bind(binds: &[Bind], action: &Action) -> Option<(Option<Key>, String)>
     }
 
     let mut title = None;
-    let key = if let Some(bind) = bind_with_custom_title.or(bind_with_non_null) {
-        if let Some(Some(custom)) = &bind.hotkey_overlay_title {
-            title = Some(custom.clone());
+    let bind = bind_with_custom_title.or(bind_with_non_null);
+    if let Some(Some(custom)) = bind.and_then(|bind| bind.hotkey_overlay_title.as_ref()) {
+        title = Some(custom.clone());
+    }
+    let title = title.unwrap_or_else(|| action_name(action));
+
+    Some((bind, title))
+}
+
+/// Returns the title and the lines of keys and actions to show in the overlay.
+///
+/// Keys are `None` for actions that aren't bound.
+fn collect_lines(
+    config: &Config,
+    bind_mode: Option<&str>,
+    sequence: &[Key],
+    mod_key: ModKey,
+    screen_reader: bool,
+) -> (String, Vec<(Option<String>, String)>) {
+    if !sequence.is_empty() {
+        let binds = match bind_mode.and_then(|name| config.bind_mode(name)) {
+            Some(mode) => &mode.binds.0,
+            None => &config.binds.0,
+        };
+
+        let title = keys_name(screen_reader, mod_key, sequence);
+        let lines = collect_continuations(binds, sequence)
+            .into_iter()
+            .map(|(keys, action)| (Some(keys_name(screen_reader, mod_key, keys)), action))
+            .collect();
+        return (title, lines);
+    }
+
+    let (title, binds, actions) = collect_contents(config, bind_mode);
+    let lines = actions
+        .into_iter()
+        .filter_map(|action| format_bind(binds, action))
+        .map(|(bind, action)| {
+            let key = bind.map(|bind| keys_name(screen_reader, mod_key, bind.keys()));
+            (key, action)
+        })
+        .collect();
+    (title, lines)
+}
+
+/// Returns the remaining keys and the titles of the binds continuing a sequence.
+fn collect_continuations<'a>(binds: &'a [Bind], sequence: &[Key]) -> Vec<(&'a [Key], String)> {
+    binds
+        .iter()
+        .filter(|bind| bind.sequence.len() >= sequence.len())
+        .filter(|bind| bind.keys().take(sequence.len()).eq(sequence))
+        .filter_map(|bind| {
+            let title = match &bind.hotkey_overlay_title {
+                Some(Some(title)) => title.clone(),
+                Some(None) => return None,
+                None => action_name(&bind.action),
+            };
+            Some((&bind.sequence[sequence.len() - 1..], title))
+        })
+        .collect()
+}
+
+/// Returns the title, the binds, and the actions to show in the overlay.
+///
+/// In a bind mode, the overlay shows every action of the mode.
+fn collect_contents<'a>(
+    config: &'a Config,
+    bind_mode: Option<&str>,
+) -> (String, &'a [Bind], Vec<&'a Action>) {
+    if let Some(mode) = bind_mode.and_then(|name| config.bind_mode(name)) {
+        let title = mode
+            .hotkey_overlay_title
+            .clone()
+            .unwrap_or_else(|| format!("Mode: {}", mode.name));
+
+        let mut actions = Vec::new();
+        for bind in &mode.binds.0 {
+            if !actions.contains(&&bind.action) {
+                actions.push(&bind.action);
+            }
         }
 
-        Some(bind.key)
-    } else {
-        None
-    };
-    let title = title.unwrap_or_else(|| action_name(action));
+        return (title, &mode.binds.0, actions);
+    }
 
-    Some((key, title))
+    (
+        String::from(TITLE),
+        &config.binds.0,
+        collect_actions(config),
+    )
 }
 
 fn collect_actions(config: &Config) -> Vec<&Action> {
@@ -278,6 +399,13 @@ fn collect_actions(config: &Config) -> Vec<&Action> {
         }
     }
 
+    // Add the binds entering bind modes, whose binds the overlay shows from within the mode.
+    for bind in binds {
+        if matches!(bind.action, Action::EnterBindMode(_)) && !actions.contains(&&bind.action) {
+            actions.push(&bind.action);
+        }
+    }
+
     // Add the spawn actions.
     for bind in binds.iter().filter(|bind| {
         matches!(bind.action, Action::Spawn(_) | Action::SpawnSh(_))
@@ -305,8 +433,8 @@ fn collect_actions(config: &Config) -> Vec<&Action> {
 
 fn render(
     renderer: &mut GlesRenderer,
-    config: &Config,
-    mod_key: ModKey,
+    title: &str,
+    lines: &[(Option<String>, String)],
     scale: f64,
 ) -> anyhow::Result<RenderedOverlay> {
     let _span = tracy_client::span!("hotkey_overlay::render");
@@ -321,16 +449,16 @@ fn render(
     // target_size.h -= margin * 2;
     // anyhow::ensure!(target_size.w > 0 && target_size.h > 0);
 
-    let strings = collect_actions(config)
-        .into_iter()
-        .filter_map(|action| format_bind(&config.binds.0, action))
+    let strings = lines
+        .iter()
         .map(|(key, action)| {
-            let key = key.map(|key| key_name(false, mod_key, &key));
             let key = key.as_deref().unwrap_or("(not bound)");
             let key = format!(" {key} ");
-            (key, action)
+            (key, action.clone())
         })
         .collect::<Vec<_>>();
+    // A bind mode can have nothing to show.
+    anyhow::ensure!(!strings.is_empty());
 
     let mut font = FontDescription::from_string(FONT);
     font.set_absolute_size(to_physical_precise_round(scale, font.size()));
@@ -344,7 +472,7 @@ fn render(
     let bold = AttrList::new();
     bold.insert(AttrInt::new_weight(Weight::Bold));
     layout.set_attributes(Some(&bold));
-    layout.set_text(TITLE);
+    layout.set_text(title);
     let title_size = layout.pixel_size();
 
     let attrs = AttrList::new();
@@ -397,7 +525,7 @@ fn render(
 
     cr.move_to(((width - title_size.0) / 2).into(), padding.into());
     layout.set_attributes(Some(&bold));
-    layout.set_text(TITLE);
+    layout.set_text(title);
     pangocairo::functions::show_layout(&cr, &layout);
 
     cr.move_to(padding.into(), (padding + title_size.1 + padding).into());
@@ -480,6 +608,11 @@ fn action_name(action: &Action) -> String {
         }
         Action::ToggleOverview => String::from("Open the Overview"),
         Action::Screenshot(_, _) => String::from("Take a Screenshot"),
+        Action::EnterBindMode(name) => format!(
+            "Enter <span face='monospace' bgcolor='#000000'>{}</span> Mode",
+            pango::glib::markup_escape_text(name)
+        ),
+        Action::ExitBindMode => String::from("Exit Bind Mode"),
         Action::Spawn(args) => format!(
             "Spawn <span face='monospace' bgcolor='#000000'>{}</span>",
             args.first().unwrap_or(&String::new())
@@ -489,10 +622,26 @@ fn action_name(action: &Action) -> String {
             // Fairly crude but should get the job done in most cases.
             command.split_ascii_whitespace().next().unwrap_or("")
         ),
+        Action::Sequence(actions) => {
+            let names: Vec<_> = actions.iter().map(action_name).collect();
+            names.join(", ")
+        }
         _ => String::from("FIXME: Unknown"),
     }
 }
 
+fn keys_name<'a>(
+    screen_reader: bool,
+    mod_key: ModKey,
+    keys: impl IntoIterator<Item = &'a Key>,
+) -> String {
+    let names: Vec<_> = keys
+        .into_iter()
+        .map(|key| key_name(screen_reader, mod_key, key))
+        .collect();
+    names.join(" ")
+}
+
 fn key_name(screen_reader: bool, mod_key: ModKey, key: &Key) -> String {
     let mut name = String::new();
 
@@ -618,8 +767,8 @@ mod tests {
     #[track_caller]
     fn check(config: &str, action: Action) -> String {
         let config = Config::parse_mem(config).unwrap();
-        if let Some((key, title)) = format_bind(&config.binds.0, &action) {
-            let key = key.map(|key| key_name(false, ModKey::Super, &key));
+        if let Some((bind, title)) = format_bind(&config.binds.0, &action) {
+            let key = bind.map(|bind| keys_name(false, ModKey::Super, bind.keys()));
             let key = key.as_deref().unwrap_or("(not bound)");
             format!(" {key} : {title}")
         } else {
@@ -714,4 +863,60 @@ mod tests {
             @" Super + P : Hello"
         );
     }
+
+    #[test]
+    fn test_bind_mode_contents() {
+        let config = Config::parse_mem(
+            r#"
+            binds {
+                Mod+R { enter-bind-mode "resize"; }
+            }
+
+            bind-mode "resize" {
+                H hotkey-overlay-title="Narrower" { set-column-width "-10%"; }
+                L hotkey-overlay-title=null { set-column-width "+10%"; }
+                Escape { exit-bind-mode; }
+            }
+            "#,
+        )
+        .unwrap();
+
+        let (title, binds, actions) = collect_contents(&config, Some("resize"));
+        let strings: Vec<_> = actions
+            .into_iter()
+            .filter_map(|action| format_bind(binds, action))
+            .map(|(bind, title)| {
+                let key = keys_name(false, ModKey::Super, bind.unwrap().keys());
+                format!(" {key} : {title}")
+            })
+            .collect();
+
+        assert_eq!(title, "Mode: resize");
+        assert_eq!(strings, [" H : Narrower", " Escape : Exit Bind Mode"]);
+    }
+
+    #[test]
+    fn test_bind_sequence_continuations() {
+        let config = Config::parse_mem(
+            r#"
+            binds {
+                Mod+Space W H { focus-column-left; }
+                Mod+Space W L hotkey-overlay-title="Right" { focus-column-right; }
+                Mod+Space W Q hotkey-overlay-title=null { close-window; }
+                Mod+Space T { spawn "alacritty"; }
+            }
+            "#,
+        )
+        .unwrap();
+
+        let sequence = [config.binds.0[0].key, config.binds.0[0].sequence[0]];
+        let (title, lines) = collect_lines(&config, None, &sequence, ModKey::Super, false);
+        let lines: Vec<_> = lines
+            .into_iter()
+            .map(|(key, action)| format!(" {} : {action}", key.unwrap()))
+            .collect();
+
+        assert_eq!(title, "Super + Space W");
+        assert_eq!(lines, [" H : Focus Column to the Left", " L : Right"]);
+    }
 }
@@ -4,3 +4,4 @@ pub mod hotkey_overlay;
 pub mod mru;
 pub mod screen_transition;
 pub mod screenshot_ui;
+pub mod zoom;
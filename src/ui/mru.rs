@@ -1834,8 +1834,11 @@ fn make_preset_opened_binds() -> Vec<Bind> {
                 // The modifier is filled dynamically.
                 modifiers: Modifiers::empty(),
             },
+            sequence: Vec::new(),
             action,
             repeat: true,
+            on_release: false,
+            tap: false,
             cooldown: None,
             allow_when_locked: false,
             allow_inhibiting: false,
@@ -1927,6 +1930,7 @@ fn make_dynamic_opened_binds(config: &Config) -> Vec<Bind> {
                 // The modifier is filled dynamically.
                 modifiers: Modifiers::empty(),
             },
+            sequence: Vec::new(),
             ..bind
         });
     }
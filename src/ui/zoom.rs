@@ -0,0 +1,246 @@
+use std::cell::{Ref, RefCell};
+use std::time::Duration;
+
+use anyhow::Context as _;
+use smithay::backend::renderer::element::utils::{
+    Relocate, RelocateRenderElement, RescaleRenderElement,
+};
+use smithay::backend::renderer::element::RenderElement;
+use smithay::backend::renderer::gles::GlesRenderer;
+use smithay::utils::{Logical, Point, Rectangle, Scale, Size};
+
+use crate::render_helpers::offscreen::{OffscreenBuffer, OffscreenData, OffscreenRenderElement};
+
+/// Maximum zoom factor.
+pub const MAX_FACTOR: f64 = 32.;
+
+pub type ZoomRenderElement = RelocateRenderElement<RescaleRenderElement<OffscreenRenderElement>>;
+
+/// Accessibility zoom of an output.
+///
+/// The zoomed view is rendered by drawing the whole output into an offscreen buffer, then scaling
+/// it up so that the visible area starts at `origin`.
+#[derive(Debug)]
+pub struct Zoom {
+    /// Current zoom factor, 1 when not zoomed.
+    factor: f64,
+    /// Top-left corner of the visible area, in output-local logical coordinates.
+    origin: Point<f64, Logical>,
+    /// Monotonic time of the last update while moving towards the target.
+    last_update: Option<Duration>,
+    buffer: OffscreenBuffer,
+    /// Data of the last render into the offscreen buffer, for the primary scanout output.
+    offscreen_data: RefCell<Option<OffscreenData>>,
+}
+
+/// What the zoomed view should keep visible.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ZoomTarget {
+    /// The pointer, moving the view proportionally so that the pointer stays in place on screen.
+    Pointer(Point<f64, Logical>),
+    /// A rectangle, centered in the view.
+    Focus(Rectangle<f64, Logical>),
+}
+
+impl Zoom {
+    pub fn is_active(&self) -> bool {
+        self.factor > 1.
+    }
+
+    pub fn are_animations_ongoing(&self) -> bool {
+        self.last_update.is_some()
+    }
+
+    pub fn factor(&self) -> f64 {
+        self.factor
+    }
+
+    /// Moves the zoom factor and the view towards their targets.
+    ///
+    /// Without a target, the view keeps its position.
+    pub fn advance(
+        &mut self,
+        now: Duration,
+        target_factor: f64,
+        target: Option<ZoomTarget>,
+        output_size: Size<f64, Logical>,
+        smoothing_ms: u16,
+    ) {
+        // The first update after settling doesn't move anything since we don't know how much time
+        // passed since the target changed.
+        let t = if smoothing_ms == 0 {
+            1.
+        } else if let Some(last) = self.last_update {
+            let dt = now.saturating_sub(last).as_secs_f64() * 1000.;
+            // Covers 95% of the distance in smoothing_ms.
+            1. - (-3. * dt / f64::from(smoothing_ms)).exp()
+        } else {
+            0.
+        };
+
+        let target_factor = target_factor.clamp(1., MAX_FACTOR);
+        self.factor += (target_factor - self.factor) * t;
+        if (target_factor - self.factor).abs() < 0.001 {
+            self.factor = target_factor;
+        }
+
+        // The target view depends on the current factor, so that the zoom animation itself keeps
+        // the target in place.
+        let view_size = output_size.downscale(self.factor);
+        let target_origin = match target {
+            Some(ZoomTarget::Pointer(pos)) => pos.upscale(1. - 1. / self.factor),
+            Some(ZoomTarget::Focus(rect)) => {
+                rect.loc + rect.size.downscale(2.).to_point() - view_size.downscale(2.).to_point()
+            }
+            None => self.origin,
+        };
+        let target_origin = clamp_origin(target_origin, output_size, view_size);
+
+        self.origin += (target_origin - self.origin).upscale(t);
+        if (target_origin.x - self.origin.x).abs() < 0.01
+            && (target_origin.y - self.origin.y).abs() < 0.01
+        {
+            self.origin = target_origin;
+        }
+        self.origin = clamp_origin(self.origin, output_size, view_size);
+
+        let done = self.factor == target_factor && self.origin == target_origin;
+        self.last_update = (!done).then_some(now);
+    }
+
+    /// Converts a position on the zoomed output to the position on the output contents.
+    pub fn unproject(&self, pos: Point<f64, Logical>) -> Point<f64, Logical> {
+        self.origin + pos.downscale(self.factor)
+    }
+
+    pub fn render(
+        &self,
+        renderer: &mut GlesRenderer,
+        scale: Scale<f64>,
+        elements: &[impl RenderElement<GlesRenderer>],
+    ) -> anyhow::Result<ZoomRenderElement> {
+        let (elem, _sync_point, data) = self
+            .buffer
+            .render(renderer, scale, elements)
+            .context("error rendering to offscreen buffer")?;
+        self.offscreen_data.replace(Some(data));
+
+        let elem = RescaleRenderElement::from_element(elem, Point::from((0, 0)), self.factor);
+
+        let offset = self.origin.upscale(-self.factor);
+        let elem = RelocateRenderElement::from_element(
+            elem,
+            offset.to_physical_precise_round(scale),
+            Relocate::Relative,
+        );
+
+        Ok(elem)
+    }
+
+    pub fn offscreen_data(&self) -> Ref<'_, Option<OffscreenData>> {
+        self.offscreen_data.borrow()
+    }
+}
+
+impl Default for Zoom {
+    fn default() -> Self {
+        Self {
+            factor: 1.,
+            origin: Point::from((0., 0.)),
+            last_update: None,
+            buffer: OffscreenBuffer::default(),
+            offscreen_data: RefCell::new(None),
+        }
+    }
+}
+
+fn clamp_origin(
+    origin: Point<f64, Logical>,
+    output_size: Size<f64, Logical>,
+    view_size: Size<f64, Logical>,
+) -> Point<f64, Logical> {
+    let max_x = f64::max(0., output_size.w - view_size.w);
+    let max_y = f64::max(0., output_size.h - view_size.h);
+    Point::from((origin.x.clamp(0., max_x), origin.y.clamp(0., max_y)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output_size() -> Size<f64, Logical> {
+        Size::from((1920., 1080.))
+    }
+
+    #[test]
+    fn pointer_stays_in_place() {
+        let mut zoom = Zoom::default();
+        let pos = Point::from((1500., 200.));
+        zoom.advance(
+            Duration::ZERO,
+            4.,
+            Some(ZoomTarget::Pointer(pos)),
+            output_size(),
+            0,
+        );
+
+        assert_eq!(zoom.factor(), 4.);
+        assert!(!zoom.are_animations_ongoing());
+        assert_eq!(zoom.unproject(pos), pos);
+        assert_eq!(
+            zoom.unproject(Point::from((0., 0.))),
+            Point::from((1125., 150.))
+        );
+    }
+
+    #[test]
+    fn focus_is_centered_and_clamped() {
+        let mut zoom = Zoom::default();
+        let rect = Rectangle::new(Point::from((900., 500.)), Size::from((120., 80.)));
+        zoom.advance(
+            Duration::ZERO,
+            2.,
+            Some(ZoomTarget::Focus(rect)),
+            output_size(),
+            0,
+        );
+        assert_eq!(
+            zoom.unproject(Point::from((960., 540.))),
+            Point::from((960., 540.))
+        );
+
+        let rect = Rectangle::new(Point::from((1900., 0.)), Size::from((20., 20.)));
+        zoom.advance(
+            Duration::ZERO,
+            2.,
+            Some(ZoomTarget::Focus(rect)),
+            output_size(),
+            0,
+        );
+        assert_eq!(
+            zoom.unproject(Point::from((0., 0.))),
+            Point::from((960., 0.))
+        );
+    }
+
+    #[test]
+    fn smoothing() {
+        let mut zoom = Zoom::default();
+        let target = Some(ZoomTarget::Pointer(Point::from((0., 0.))));
+
+        zoom.advance(Duration::ZERO, 2., target, output_size(), 100);
+        assert_eq!(zoom.factor(), 1.);
+        assert!(zoom.are_animations_ongoing());
+
+        zoom.advance(Duration::from_millis(50), 2., target, output_size(), 100);
+        assert!(zoom.is_active());
+        assert!(zoom.factor() < 2.);
+
+        zoom.advance(Duration::from_secs(1), 2., target, output_size(), 100);
+        assert_eq!(zoom.factor(), 2.);
+        assert!(!zoom.are_animations_ongoing());
+
+        zoom.advance(Duration::from_secs(2), 1., target, output_size(), 0);
+        assert!(!zoom.is_active());
+    }
+}
@@ -490,6 +490,40 @@ pub fn get_credentials_for_client(dh: &DisplayHandle, client: &Client) -> Option
     client.get_credentials(dh).ok()
 }
 
+/// Returns the parent process id of a process, according to `/proc`.
+pub fn parent_pid(pid: i32) -> Option<i32> {
+    let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    parse_ppid_from_stat(&stat)
+}
+
+fn parse_ppid_from_stat(stat: &str) -> Option<i32> {
+    // The process name is in parentheses and can contain spaces and parentheses itself, so skip
+    // past the last closing parenthesis. Then come the state and the parent pid.
+    let (_, rest) = stat.rsplit_once(')')?;
+    let mut fields = rest.split_whitespace();
+    let _state = fields.next()?;
+    fields.next()?.parse().ok()
+}
+
+/// Returns the parent process ids of a process, closest first.
+pub fn ancestor_pids(pid: i32) -> Vec<i32> {
+    let mut rv = Vec::new();
+    let mut pid = pid;
+
+    // Stop at init, and limit the walk in case of a cycle from pid reuse.
+    while rv.len() < 64 {
+        match parent_pid(pid) {
+            Some(ppid) if ppid > 1 => {
+                rv.push(ppid);
+                pid = ppid;
+            }
+            _ => break,
+        }
+    }
+
+    rv
+}
+
 pub fn ensure_min_max_size(mut x: i32, min_size: i32, max_size: i32) -> i32 {
     if max_size > 0 {
         x = min(x, max_size);
@@ -603,6 +637,17 @@ pub fn cause_panic() {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_ppid_from_stat() {
+        let stat = "1234 (foot) S 1000 1234 1234 0 -1 4194560 0 0 0 0";
+        assert_eq!(parse_ppid_from_stat(stat), Some(1000));
+
+        let stat = "1234 (a) b (c)) R 42 1234 1234 0 -1";
+        assert_eq!(parse_ppid_from_stat(stat), Some(42));
+
+        assert_eq!(parse_ppid_from_stat("garbage"), None);
+    }
+
     #[test]
     fn test_clamp_preferring_top_left() {
         fn check(
@@ -64,6 +64,15 @@ pub fn restore_nofile_rlimit() {
 
 /// Spawns the command to run independently of the compositor.
 pub fn spawn<T: AsRef<OsStr> + Send + 'static>(command: Vec<T>, token: Option<XdgActivationToken>) {
+    spawn_with_env(command, token, Vec::new());
+}
+
+/// Spawns the command with extra environment variables set on top of the configured ones.
+pub fn spawn_with_env<T: AsRef<OsStr> + Send + 'static>(
+    command: Vec<T>,
+    token: Option<XdgActivationToken>,
+    extra_env: Vec<(String, String)>,
+) {
     let _span = tracy_client::span!();
 
     if command.is_empty() {
@@ -75,7 +84,7 @@ pub fn spawn<T: AsRef<OsStr> + Send + 'static>(command: Vec<T>, token: Option<Xd
         .name("Command Spawner".to_owned())
         .spawn(move || {
             let (command, args) = command.split_first().unwrap();
-            spawn_sync(command, args, token);
+            spawn_sync(command, args, token, extra_env);
         });
 
     if let Err(err) = res {
@@ -97,6 +106,7 @@ fn spawn_sync(
     command: impl AsRef<OsStr>,
     args: impl IntoIterator<Item = impl AsRef<OsStr>>,
     token: Option<XdgActivationToken>,
+    extra_env: Vec<(String, String)>,
 ) {
     let _span = tracy_client::span!();
 
@@ -149,6 +159,10 @@ fn spawn_sync(
     }
     drop(env);
 
+    for (name, value) in extra_env {
+        process.env(name, value);
+    }
+
     if let Some(token) = token.as_ref() {
         process.env("XDG_ACTIVATION_TOKEN", token.as_str());
         process.env("DESKTOP_STARTUP_ID", token.as_str());
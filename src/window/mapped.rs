@@ -23,6 +23,7 @@ use wayland_backend::server::Credentials;
 
 use super::{ResolvedWindowRules, WindowRef};
 use crate::handlers::KdeDecorationsModeState;
+use crate::layout::restore::WindowHints;
 use crate::layout::{
     ConfigureIntent, InteractiveResizeData, LayoutElement, LayoutElementRenderElement,
     LayoutElementRenderSnapshot, SizingMode,
@@ -97,6 +98,17 @@ pub struct Mapped {
     /// Whether this window is floating.
     is_floating: bool,
 
+    /// Whether this window is sticky, following the active workspace.
+    is_sticky: bool,
+
+    /// Whether this window is minimized.
+    is_minimized: bool,
+
+    /// Mark set on this window for jumping back to it.
+    ///
+    /// Marks are unique: setting a mark on a window removes it from any other window.
+    mark: Option<String>,
+
     /// Whether this window is a target of a window cast.
     is_window_cast_target: bool,
 
@@ -289,6 +301,9 @@ impl Mapped {
             is_focused: false,
             is_active_in_column: true,
             is_floating: false,
+            is_sticky: false,
+            is_minimized: false,
+            mark: None,
             is_window_cast_target: false,
             ignore_opacity_window_rule: false,
             block_out_buffer: RefCell::new(SolidColorBuffer::new((0., 0.), [0., 0., 0., 1.])),
@@ -363,6 +378,14 @@ impl Mapped {
         self.credentials.as_ref()
     }
 
+    /// Returns the hints used to recognize this window when restoring the layout.
+    pub fn window_hints(&self) -> WindowHints {
+        with_toplevel_role(self.toplevel(), |role| WindowHints {
+            app_id: role.app_id.clone(),
+            title: role.title.clone(),
+        })
+    }
+
     pub fn offscreen_data(&self) -> Ref<'_, Option<OffscreenData>> {
         self.offscreen_data.borrow()
     }
@@ -379,6 +402,14 @@ impl Mapped {
         self.is_floating
     }
 
+    pub fn is_sticky(&self) -> bool {
+        self.is_sticky
+    }
+
+    pub fn is_minimized(&self) -> bool {
+        self.is_minimized
+    }
+
     pub fn is_window_cast_target(&self) -> bool {
         self.is_window_cast_target
     }
@@ -611,6 +642,14 @@ impl Mapped {
     pub fn is_urgent(&self) -> bool {
         self.is_urgent
     }
+
+    pub fn set_mark(&mut self, mark: Option<String>) {
+        self.mark = mark;
+    }
+
+    pub fn mark(&self) -> Option<&str> {
+        self.mark.as_deref()
+    }
 }
 
 impl Drop for Mapped {
@@ -825,6 +864,12 @@ impl LayoutElement for Mapped {
         }
     }
 
+    fn share_transaction(&mut self, transaction: &Transaction) {
+        if let Some(current) = &mut self.transaction_for_next_configure {
+            *current = transaction.clone();
+        }
+    }
+
     fn request_size_once(&mut self, size: Size<i32, Logical>, animate: bool) {
         // Assume that when calling this function, the window is going floating, so it can no
         // longer participate in any transactions with other windows.
@@ -978,6 +1023,10 @@ impl LayoutElement for Mapped {
         self.is_urgent
     }
 
+    fn mark(&self) -> Option<&str> {
+        self.mark.as_deref()
+    }
+
     fn set_activated(&mut self, active: bool) {
         let changed = self.toplevel().with_pending_state(|state| {
             if active {
@@ -1001,6 +1050,14 @@ impl LayoutElement for Mapped {
         self.need_to_recompute_rules |= changed;
     }
 
+    fn set_sticky(&mut self, sticky: bool) {
+        self.is_sticky = sticky;
+    }
+
+    fn set_minimized(&mut self, minimized: bool) {
+        self.is_minimized = minimized;
+    }
+
     fn set_bounds(&self, bounds: Size<i32, Logical>) {
         self.toplevel().with_pending_state(|state| {
             state.bounds = Some(bounds);
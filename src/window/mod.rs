@@ -3,8 +3,8 @@ use std::cmp::{max, min};
 use niri_config::utils::MergeWith as _;
 use niri_config::window_rule::{Match, OnXdgActivate, WindowRule};
 use niri_config::{
-    BackgroundEffect, BlockOutFrom, BorderRule, CornerRadius, FloatingPosition, PresetSize,
-    ResolvedPopupsRules, ShadowRule, TabIndicatorRule,
+    AppBinds, BackgroundEffect, BlockOutFrom, BorderRule, CornerRadius, FloatingPosition,
+    PresetSize, ResolvedPopupsRules, ShadowRule, SwallowParent, TabIndicatorRule,
 };
 use niri_ipc::ColumnDisplay;
 use smithay::reexports::wayland_protocols::xdg::shell::server::xdg_toplevel;
@@ -73,6 +73,15 @@ pub struct ResolvedWindowRules {
     /// Whether the window should open focused.
     pub open_focused: Option<bool>,
 
+    /// Whether the window should open hidden in the scratchpad.
+    pub open_in_scratchpad: Option<bool>,
+
+    /// Whether the window should open sticky, following the active workspace.
+    pub open_sticky: Option<bool>,
+
+    /// Which parent process windows this window should swallow.
+    pub swallow_parent: Option<SwallowParent>,
+
     /// What to do on xdg-activation requests.
     pub on_xdg_activate: Option<OnXdgActivate>,
 
@@ -260,6 +269,16 @@ impl ResolvedWindowRules {
                     resolved.open_focused = Some(x);
                 }
 
+                if let Some(x) = rule.open_in_scratchpad {
+                    resolved.open_in_scratchpad = Some(x);
+                }
+                if let Some(x) = rule.open_sticky {
+                    resolved.open_sticky = Some(x);
+                }
+                if let Some(x) = &rule.swallow_parent {
+                    resolved.swallow_parent = Some(x.clone());
+                }
+
                 if let Some(x) = rule.on_xdg_activate {
                     resolved.on_xdg_activate = Some(x);
                 }
@@ -373,6 +392,11 @@ impl ResolvedWindowRules {
             return res;
         }
 
+        // Sticky windows only work in the floating layout.
+        if self.open_sticky == Some(true) {
+            return true;
+        }
+
         // Windows with a parent (usually dialogs) open as floating by default.
         if toplevel.parent().is_some() {
             return true;
@@ -390,6 +414,36 @@ impl ResolvedWindowRules {
     }
 }
 
+/// Returns whether a window can be swallowed according to the `swallow-parent` rule.
+pub fn swallow_parent_matches(rule: &SwallowParent, window: WindowRef) -> bool {
+    if rule.matches.is_empty() {
+        return true;
+    }
+
+    with_toplevel_role(window.toplevel(), |role| {
+        // Ensure server_pending like in Smithay's with_pending_state().
+        if role.server_pending.is_none() {
+            role.server_pending = Some(role.current_server_state().clone());
+        }
+
+        rule.matches.iter().any(|m| window_matches(window, role, m))
+    })
+}
+
+/// Returns whether the app binds apply while the window is focused.
+pub fn app_binds_match(app_binds: &AppBinds, window: WindowRef) -> bool {
+    with_toplevel_role(window.toplevel(), |role| {
+        // Ensure server_pending like in Smithay's with_pending_state().
+        if role.server_pending.is_none() {
+            role.server_pending = Some(role.current_server_state().clone());
+        }
+
+        let matches = |m: &Match| window_matches(window, role, m);
+        (app_binds.matches.is_empty() || app_binds.matches.iter().any(matches))
+            && !app_binds.excludes.iter().any(matches)
+    })
+}
+
 fn window_matches(window: WindowRef, role: &XdgToplevelSurfaceRoleAttributes, m: &Match) -> bool {
     // Must be ensured by the caller.
     let server_pending = role.server_pending.as_ref().unwrap();